@@ -0,0 +1,97 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Criterion benchmarks for every registered solution and every input next
+//! to it, with parsing and solving timed separately:
+//!
+//! ```text
+//! cargo bench -- y2021::d19
+//! ```
+//!
+//! The day modules are compiled into this target as they are into the
+//! binary, so each `#[solution]` registers itself here too. An input that
+//! does not parse, or whose `inputs/<name>.params` file the solution does
+//! not accept, is skipped with the reason: the malformed inputs that tests
+//! expect to be rejected, and parameters meant for the other part.
+
+// Cargo builds benches with `cfg(test)` but without the test harness, so
+// the days' test modules are compiled while their `#[test]` functions are
+// not, leaving the helpers and imports those functions use unused here.
+#[cfg(feature = "y2021")]
+#[path = "../src/y2021/mod.rs"]
+#[allow(dead_code, unused_imports)]
+mod y2021;
+
+#[cfg(feature = "y2022")]
+#[path = "../src/y2022/mod.rs"]
+#[allow(dead_code, unused_imports)]
+mod y2022;
+
+use std::fs;
+
+use anyhow::Context;
+use aoc::solution::Solution;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BatchSize;
+use criterion::Criterion;
+
+/// The input named `name` and its parameters, or why it can not be
+/// benchmarked.
+fn load(solution: &Solution, name: &str) -> anyhow::Result<(String, Vec<(String, String)>)> {
+    let path = solution.input_path(name);
+    let input = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+    let params = solution.input_params(name)?;
+    drop((solution.prepare)(&input, &params)?);
+    Ok((input, params))
+}
+
+fn solutions(c: &mut Criterion) {
+    for solution in aoc::solution::all() {
+        let id = format!(
+            "y{}::d{:02}::part{}",
+            solution.year, solution.day, solution.part
+        );
+        let mut group = c.benchmark_group(&id);
+        for name in solution.inputs() {
+            let (input, params) = match load(solution, &name) {
+                Ok(loaded) => loaded,
+                Err(err) => {
+                    eprintln!("skipping {id}/{name}: {err:#}");
+                    continue;
+                }
+            };
+            group.bench_function(format!("{name}/parse"), |b| {
+                b.iter(|| (solution.prepare)(&input, &params))
+            });
+            group.bench_function(format!("{name}/solve"), |b| {
+                b.iter_batched(
+                    || (solution.prepare)(&input, &params).unwrap(),
+                    |solve| solve(),
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, solutions);
+criterion_main!(benches);
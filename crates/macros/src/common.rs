@@ -18,11 +18,11 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use proc_macro2::Span;
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::parse::Parse;
 use syn::parse::ParseStream;
-use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::Data;
 use syn::DeriveInput;
@@ -31,12 +31,42 @@ use syn::Field;
 use syn::Fields;
 use syn::Generics;
 use syn::Ident;
+use syn::LitStr;
+use syn::Member;
 use syn::Result;
+use syn::Token;
 use syn::Type;
 
+/// How the records of a `FromLines` input are split and filtered.
+#[derive(Default)]
+pub struct LineOptions {
+    /// `sep = "…"`: split on this instead of on line endings.
+    pub sep: Option<LitStr>,
+    /// `skip_blank`: drop empty records.
+    pub skip_blank: Option<Ident>,
+    /// `comment = "…"`: drop records starting with this.
+    pub comment: Option<LitStr>,
+    /// `at_line`: the records' error places itself with
+    /// `aoc::nom::AtLine` rather than being wrapped in a `LineError`.
+    pub at_line: Option<Ident>,
+}
+
+impl LineOptions {
+    /// The span of the first option that was given, if any.
+    fn first_span(&self) -> Option<Span> {
+        self.sep
+            .as_ref()
+            .map(LitStr::span)
+            .or_else(|| self.skip_blank.as_ref().map(Ident::span))
+            .or_else(|| self.comment.as_ref().map(LitStr::span))
+            .or_else(|| self.at_line.as_ref().map(Ident::span))
+    }
+}
+
 struct CommonAttr {
     ty: Type,
     skip_from_iter: bool,
+    lines: LineOptions,
 }
 
 impl CommonAttr {
@@ -58,44 +88,123 @@ impl CommonAttr {
 impl Parse for CommonAttr {
     fn parse(input: ParseStream) -> Result<Self> {
         let ty = input.parse()?;
-        let comma = input.parse::<Option<Comma>>()?;
-        let skip_from_iter = match &comma {
-            Some(_) => match input.parse::<Ident>() {
-                Ok(ident) if ident == "skip_from_iter" => Ok(true),
-                Ok(ident) => Err(Error::new(ident.span(), "expected `skip_from_iter`")),
-                Err(err) => Err(err),
-            },
-            None => Ok(false),
-        }?;
-
-        Ok(Self { ty, skip_from_iter })
+        let mut skip_from_iter = false;
+        let mut lines = LineOptions::default();
+        while input.parse::<Option<Comma>>()?.is_some() {
+            if input.is_empty() {
+                break;
+            }
+            let ident = input.parse::<Ident>()?;
+            let duplicate = match ident.to_string().as_str() {
+                "skip_from_iter" => std::mem::replace(&mut skip_from_iter, true),
+                "skip_blank" => lines.skip_blank.replace(ident.clone()).is_some(),
+                "at_line" => lines.at_line.replace(ident.clone()).is_some(),
+                "sep" => {
+                    input.parse::<Token![=]>()?;
+                    lines.sep.replace(input.parse()?).is_some()
+                }
+                "comment" => {
+                    input.parse::<Token![=]>()?;
+                    lines.comment.replace(input.parse()?).is_some()
+                }
+                _ => return Err(Error::new(
+                    ident.span(),
+                    "expected `skip_from_iter`, `skip_blank`, `at_line`, `sep = \"…\"` or `comment = \"…\"`",
+                )),
+            };
+            if duplicate {
+                return Err(Error::new(ident.span(), format!("duplicate `{ident}`")));
+            }
+        }
+        if let Some(sep) = &lines.sep {
+            if sep.value().is_empty() {
+                return Err(Error::new(sep.span(), "the separator cannot be empty"));
+            }
+        }
+
+        Ok(Self {
+            ty,
+            skip_from_iter,
+            lines,
+        })
     }
 }
 
+/// A field parsed from the first record rather than collected.
+pub struct Header {
+    pub member: Member,
+    pub ty: Type,
+}
+
 pub struct Common {
     pub attr_type: Type,
     pub skip_from_iter: bool,
+    pub lines: LineOptions,
     pub struct_ident: Ident,
     pub struct_generics: Generics,
-    pub field_ident: Option<Ident>,
+    pub field_member: Member,
     pub field_type: Type,
+    pub header: Option<Header>,
+    /// Whether the fields are named, for building the struct.
+    pub named: bool,
 }
 
 impl Common {
     pub fn parse(input: ParseStream, trait_name: &str, attr_name: &str) -> Result<Self> {
         let derive_input = DeriveInput::parse(input)?;
         let attr = CommonAttr::from_input(attr_name, &derive_input)?;
-        let field = field(trait_name, &derive_input)?;
+        let (field, header) = fields(trait_name, attr_name, &derive_input)?;
+        let named = field.ident.is_some();
         Ok(Self {
             attr_type: attr.ty,
             skip_from_iter: attr.skip_from_iter,
+            lines: attr.lines,
             struct_ident: derive_input.ident,
             struct_generics: derive_input.generics,
-            field_ident: field.ident,
+            field_member: field.member,
             field_type: field.ty,
+            header,
+            named,
         })
     }
 
+    /// Reject the options that only make sense for line-based input.
+    pub fn records_only(&self, trait_name: &str, attr_name: &str) -> Result<()> {
+        if let Some(span) = self.lines.first_span() {
+            return Err(Error::new(
+                span,
+                format!("#[derive({trait_name})] does not split lines; remove this option"),
+            ));
+        }
+        if let Some(header) = &self.header {
+            return Err(Error::new_spanned(
+                &header.member,
+                format!("#[{attr_name}(header)] is only supported by #[derive(FromLines)]"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Build `Self` from the collected records and, if there is one, the
+    /// header.
+    pub fn constructor(&self, records: TokenStream, header: Option<TokenStream>) -> TokenStream {
+        let mut fields = vec![(&self.field_member, records)];
+        if let (Some(h), Some(value)) = (&self.header, header) {
+            fields.push((&h.member, value));
+        }
+        if self.named {
+            let (members, values): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
+            quote!(Self { #(#members: #values),* })
+        } else {
+            fields.sort_by_key(|(member, _)| match member {
+                Member::Unnamed(index) => index.index,
+                Member::Named(_) => unreachable!("tuple structs have unnamed fields"),
+            });
+            let values = fields.into_iter().map(|(_, value)| value);
+            quote!(Self(#(#values),*))
+        }
+    }
+
     pub fn impl_try_from_str(&self) -> TokenStream {
         let Self {
             struct_ident,
@@ -109,6 +218,7 @@ impl Common {
             .insert(0, syn::parse_quote!('__try_from));
         let (try_from_impl_generics, _, _) = try_from_generics.split_for_impl();
         quote! {
+            #[allow(clippy::infallible_try_from)]
             impl #try_from_impl_generics ::core::convert::TryFrom<&'__try_from str> for #struct_ident #ty_generics #where_clause {
                 type Error = <Self as ::core::str::FromStr>::Err;
 
@@ -119,37 +229,23 @@ impl Common {
         }
     }
 
+    /// `FromIterator` over the records; a struct with a header cannot be
+    /// built from records alone, so it gets none.
     pub fn impl_from_iterator(&self) -> TokenStream {
-        if self.skip_from_iter {
+        if self.skip_from_iter || self.header.is_some() {
             return quote! {};
         }
 
         let Self {
             attr_type,
-            skip_from_iter: _,
             struct_ident,
             struct_generics,
-            field_ident,
             field_type,
+            ..
         } = self;
 
         let (impl_generics, ty_generics, where_clause) = struct_generics.split_for_impl();
-
-        let impl_ = quote! {
-            <#field_type>::from_iter(iter)
-        };
-
-        let ctor = if let Some(ident) = field_ident {
-            quote! {
-                Self {
-                    #ident: #impl_,
-                }
-            }
-        } else {
-            quote! {
-                Self(#impl_)
-            }
-        };
+        let ctor = self.constructor(quote!(<#field_type>::from_iter(iter)), None);
 
         quote! {
             impl #impl_generics ::core::iter::FromIterator<#attr_type> for #struct_ident #ty_generics #where_clause {
@@ -161,40 +257,111 @@ impl Common {
     }
 }
 
-fn field(trait_name: &str, derive_input: &DeriveInput) -> Result<Field> {
-    match &derive_input.data {
+struct RecordField {
+    ident: Option<Ident>,
+    member: Member,
+    ty: Type,
+}
+
+/// Whether `field` is marked `#[<attr_name>(header)]`.
+fn is_header(attr_name: &str, field: &Field) -> Result<bool> {
+    match field
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident(attr_name))
+    {
+        Some(attr) => {
+            let ident = attr.parse_args::<Ident>()?;
+            if ident == "header" {
+                Ok(true)
+            } else {
+                Err(Error::new(ident.span(), "expected `header`"))
+            }
+        }
+        None => Ok(false),
+    }
+}
+
+/// The field the records are collected into, and the header field if one is
+/// marked.
+fn fields(
+    trait_name: &str,
+    attr_name: &str,
+    derive_input: &DeriveInput,
+) -> Result<(RecordField, Option<Header>)> {
+    let fields = match &derive_input.data {
         Data::Struct(data) => match &data.fields {
-            Fields::Named(fields) => exactly_one_field(trait_name, &fields.named),
-            Fields::Unnamed(fields) => exactly_one_field(trait_name, &fields.unnamed),
-            Fields::Unit => Err(Error::new(
-                derive_input.ident.span(),
-                format!("cannot #[derive({trait_name})] for unit structs"),
-            )),
+            Fields::Named(fields) => &fields.named,
+            Fields::Unnamed(fields) => &fields.unnamed,
+            Fields::Unit => {
+                return Err(Error::new(
+                    derive_input.ident.span(),
+                    format!("cannot #[derive({trait_name})] for unit structs"),
+                ))
+            }
         },
-        Data::Enum(data) => Err(Error::new(
-            data.enum_token.span,
-            format!("cannot #[derive({trait_name})] for enums"),
-        )),
-        Data::Union(data) => Err(Error::new(
-            data.union_token.span,
-            format!("cannot #[derive({trait_name})] for unions"),
-        )),
+        Data::Enum(data) => {
+            return Err(Error::new(
+                data.enum_token.span,
+                format!("cannot #[derive({trait_name})] for enums"),
+            ))
+        }
+        Data::Union(data) => {
+            return Err(Error::new(
+                data.union_token.span,
+                format!("cannot #[derive({trait_name})] for unions"),
+            ))
+        }
+    };
+
+    let mut header = None;
+    let mut records = Vec::new();
+    for (idx, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(idx.into()),
+        };
+        if !is_header(attr_name, field)? {
+            records.push((member, field));
+        } else if header.is_some() {
+            return Err(Error::new_spanned(
+                field,
+                format!("only one field can be #[{attr_name}(header)]"),
+            ));
+        } else {
+            header = Some(Header {
+                member,
+                ty: field.ty.clone(),
+            });
+        }
     }
+
+    let (member, field) = exactly_one_field(trait_name, derive_input, records)?;
+    let field = RecordField {
+        member,
+        ident: field.ident.clone(),
+        ty: field.ty.clone(),
+    };
+    Ok((field, header))
 }
 
-fn exactly_one_field(trait_name: &str, fields: &Punctuated<Field, Comma>) -> Result<Field> {
+fn exactly_one_field<'a>(
+    trait_name: &str,
+    derive_input: &DeriveInput,
+    fields: Vec<(Member, &'a Field)>,
+) -> Result<(Member, &'a Field)> {
     let mut iter = fields.into_iter();
     let f1 = iter.next();
     let f2 = iter.next();
     match (f1, f2) {
-        (Some(field), None) => Ok(field.clone()),
-        (_, Some(field)) => Err(Error::new_spanned(
+        (Some(field), None) => Ok(field),
+        (_, Some((_, field))) => Err(Error::new_spanned(
             field,
-            format!("can only #[derive({trait_name}] for structs with a single field"),
+            format!("can only #[derive({trait_name})] for structs with a single field"),
         )),
-        (None, None) => Err(Error::new_spanned(
-            fields,
-            format!("can only #[derive({trait_name}] for structs with a single field"),
+        (None, None) => Err(Error::new(
+            derive_input.ident.span(),
+            format!("can only #[derive({trait_name})] for structs with a single field"),
         )),
     }
 }
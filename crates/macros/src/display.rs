@@ -0,0 +1,123 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use quote::ToTokens;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse::Parser;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Result;
+
+use crate::common::Common;
+use crate::from_sections::FromSections;
+use crate::from_sections::Kind;
+
+pub fn expand(tokens: TokenStream) -> TokenStream {
+    Display::parse
+        .parse2(tokens)
+        .map(Display::into_token_stream)
+        .unwrap_or_else(syn::Error::into_compile_error)
+}
+
+/// The parser metadata that the output mirrors.
+enum Display {
+    /// `#[from_lines(…)]`: the records, and the header if there is one.
+    Lines(Box<Common>),
+    /// Any other struct is read section by section.
+    Sections(FromSections),
+}
+
+const NOT_A_STRUCT: &str = "can only #[derive(Display)] for structs; \
+     #[derive(Scan)] and #[derive(FromGrid)] already implement Display for enums";
+
+impl Parse for Display {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let derive_input = DeriveInput::parse(input)?;
+        match &derive_input.data {
+            Data::Struct(_) => {}
+            Data::Enum(data) => return Err(Error::new(data.enum_token.span, NOT_A_STRUCT)),
+            Data::Union(data) => return Err(Error::new(data.union_token.span, NOT_A_STRUCT)),
+        }
+        let tokens = derive_input.to_token_stream();
+        if derive_input
+            .attrs
+            .iter()
+            .any(|attr| attr.path.is_ident("from_lines"))
+        {
+            let parse = |input: ParseStream| Common::parse(input, "Display", "from_lines");
+            parse
+                .parse2(tokens)
+                .map(|common| Self::Lines(Box::new(common)))
+        } else {
+            syn::parse2(tokens).map(Self::Sections)
+        }
+    }
+}
+
+/// `values`, an `&IntoIterator`, as an iterator of `&dyn Display`.
+fn displays(values: TokenStream) -> TokenStream {
+    quote! {
+        ::core::iter::IntoIterator::into_iter(#values)
+            .map(|__value| __value as &dyn ::core::fmt::Display)
+    }
+}
+
+impl Display {
+    fn into_token_stream(self) -> TokenStream {
+        let (ident, generics, body) = match &self {
+            Self::Lines(common) => {
+                let member = &common.field_member;
+                let sep = match &common.lines.sep {
+                    Some(sep) => quote!(::core::option::Option::Some(#sep)),
+                    None => quote!(::core::option::Option::None),
+                };
+                let records = displays(quote!(&self.#member));
+                let records = match &common.header {
+                    Some(header) => {
+                        let header = &header.member;
+                        quote! {
+                            ::core::iter::once(&self.#header as &dyn ::core::fmt::Display)
+                                .chain(#records)
+                        }
+                    }
+                    None => records,
+                };
+                let body = quote!(::aoc::lines::write(f, #sep, #records));
+                (&common.struct_ident, &common.struct_generics, body)
+            }
+            Self::Sections(sections) => {
+                let writes = sections.fields.iter().map(|section| {
+                    let member = &section.member;
+                    match &section.kind {
+                        Kind::Whole(_) => quote!(__writer.whole(&self.#member)?;),
+                        Kind::Lines(_) => {
+                            let values = displays(quote!(&self.#member));
+                            quote!(__writer.lines(#values)?;)
+                        }
+                        Kind::Each(_) => {
+                            let values = displays(quote!(&self.#member));
+                            quote!(__writer.each(#values)?;)
+                        }
+                    }
+                });
+                let body = quote! {
+                    let mut __writer = ::aoc::sections::SectionWriter::new(f);
+                    #(#writes)*
+                    Ok(())
+                };
+                (&sections.ident, &sections.generics, body)
+            }
+        };
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    #body
+                }
+            }
+        }
+    }
+}
@@ -62,6 +62,8 @@ impl FromBytes {
 
 impl Parse for FromBytes {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        Common::parse(input, "FromBytes", "from_bytes").map(Self)
+        let common = Common::parse(input, "FromBytes", "from_bytes")?;
+        common.records_only("FromBytes", "from_bytes")?;
+        Ok(Self(common))
     }
 }
@@ -0,0 +1,190 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::Data;
+use syn::Error;
+use syn::Fields;
+use syn::Generics;
+use syn::Ident;
+use syn::LitChar;
+use syn::Result;
+use syn::Type;
+
+pub fn expand(tokens: TokenStream) -> TokenStream {
+    FromGrid::parse
+        .parse2(tokens)
+        .map(FromGrid::into_token_stream)
+        .unwrap_or_else(syn::Error::into_compile_error)
+}
+
+struct FromGrid {
+    ident: Ident,
+    generics: Generics,
+    variants: Vec<Tile>,
+}
+
+enum Tile {
+    /// A unit variant and its characters; the first one is written back.
+    Chars(Ident, Vec<LitChar>),
+    /// A single-field tuple variant whose field is itself a tile.
+    Inner(Ident, Box<Type>),
+}
+
+struct TileAttr(Vec<LitChar>);
+
+impl Parse for TileAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let chars = Punctuated::<LitChar, Comma>::parse_separated_nonempty(input)?;
+        Ok(Self(chars.into_iter().collect()))
+    }
+}
+
+impl Parse for FromGrid {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let derive_input = syn::DeriveInput::parse(input)?;
+        let data = match derive_input.data {
+            Data::Enum(data) => data,
+            Data::Struct(data) => {
+                return Err(Error::new(
+                    data.struct_token.span,
+                    "can only #[derive(FromGrid)] for enums",
+                ))
+            }
+            Data::Union(data) => {
+                return Err(Error::new(
+                    data.union_token.span,
+                    "can only #[derive(FromGrid)] for enums",
+                ))
+            }
+        };
+
+        let mut seen = Vec::<LitChar>::new();
+        let mut variants = Vec::new();
+        for variant in data.variants {
+            let attr = variant
+                .attrs
+                .iter()
+                .find(|attr| attr.path.is_ident("tile"))
+                .map(|attr| attr.parse_args::<TileAttr>())
+                .transpose()?;
+            let tile = match (attr, variant.fields) {
+                (Some(TileAttr(chars)), Fields::Unit) => {
+                    for c in chars.iter() {
+                        if seen.iter().any(|s| s.value() == c.value()) {
+                            return Err(Error::new(
+                                c.span(),
+                                format!("{:?} is already a tile", c.value()),
+                            ));
+                        }
+                        seen.push(c.clone());
+                    }
+                    Tile::Chars(variant.ident, chars)
+                }
+                (None, Fields::Unnamed(fields)) if fields.unnamed.len() == 1 => {
+                    Tile::Inner(variant.ident, Box::new(fields.unnamed[0].ty.clone()))
+                }
+                (None, _) => {
+                    return Err(Error::new(
+                        variant.ident.span(),
+                        "expected #[tile('…')] or a single field that is a tile",
+                    ))
+                }
+                (Some(_), _) => {
+                    return Err(Error::new(
+                        variant.ident.span(),
+                        "#[tile('…')] can only be used on unit variants",
+                    ))
+                }
+            };
+            variants.push(tile);
+        }
+
+        Ok(Self {
+            ident: derive_input.ident,
+            generics: derive_input.generics,
+            variants,
+        })
+    }
+}
+
+impl FromGrid {
+    fn into_token_stream(self) -> TokenStream {
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let chars = self.variants.iter().filter_map(|tile| match tile {
+            Tile::Chars(variant, chars) => {
+                Some(quote!(#(#chars)|* => return Some(Self::#variant),))
+            }
+            Tile::Inner(..) => None,
+        });
+        let inners = self.variants.iter().filter_map(|tile| match tile {
+            Tile::Inner(variant, ty) => Some(quote! {
+                if let Some(tile) = <#ty as ::aoc::matrix::GridTile>::from_char(c) {
+                    return Some(Self::#variant(tile));
+                }
+            }),
+            Tile::Chars(..) => None,
+        });
+        let to_chars = self.variants.iter().map(|tile| match tile {
+            Tile::Chars(variant, chars) => {
+                let c = &chars[0];
+                quote!(Self::#variant => #c,)
+            }
+            Tile::Inner(variant, ty) => {
+                quote!(Self::#variant(tile) => <#ty as ::aoc::matrix::GridTile>::to_char(tile),)
+            }
+        });
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::aoc::matrix::GridTile for #ident #ty_generics #where_clause {
+                fn from_char(c: char) -> ::core::option::Option<Self> {
+                    #[allow(clippy::match_single_binding)]
+                    match c {
+                        #(#chars)*
+                        _ => {}
+                    }
+                    #(#inners)*
+                    None
+                }
+
+                fn to_char(&self) -> char {
+                    match self {
+                        #(#to_chars)*
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::TryFrom<char> for #ident #ty_generics #where_clause {
+                type Error = ::aoc::matrix::TileError;
+
+                fn try_from(found: char) -> ::core::result::Result<Self, Self::Error> {
+                    <Self as ::aoc::matrix::GridTile>::from_char(found)
+                        .ok_or(::aoc::matrix::TileError { found })
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::core::convert::TryFrom<u8> for #ident #ty_generics #where_clause {
+                type Error = ::aoc::matrix::TileError;
+
+                fn try_from(value: u8) -> ::core::result::Result<Self, Self::Error> {
+                    Self::try_from(char::from(value))
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    ::core::fmt::Write::write_char(f, <Self as ::aoc::matrix::GridTile>::to_char(self))
+                }
+            }
+        }
+    }
+}
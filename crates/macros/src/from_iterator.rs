@@ -41,6 +41,8 @@ impl FromIterator {
 
 impl Parse for FromIterator {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        Common::parse(input, "FromIterator", "from_iterator").map(Self)
+        let common = Common::parse(input, "FromIterator", "from_iterator")?;
+        common.records_only("FromIterator", "from_iterator")?;
+        Ok(Self(common))
     }
 }
@@ -22,6 +22,7 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use syn::parse::Parse;
 use syn::parse::Parser;
+use syn::Type;
 
 use crate::common::Common;
 
@@ -38,27 +39,118 @@ impl FromLines {
     pub fn into_token_stream(self) -> TokenStream {
         let Common {
             attr_type,
+            lines,
             struct_ident,
             struct_generics,
+            field_type,
+            header,
             ..
         } = &self.0;
         let (impl_generics, ty_generics, where_clause) = struct_generics.split_for_impl();
         let from_iter = self.0.impl_from_iterator();
         let try_from_str = self.0.impl_try_from_str();
+
+        let sep = match &lines.sep {
+            Some(sep) => quote!(::core::option::Option::Some(#sep)),
+            None => quote!(::core::option::Option::None),
+        };
+        let skip_blank = lines.skip_blank.is_some();
+        let comment = match &lines.comment {
+            Some(comment) => quote!(::core::option::Option::Some(#comment)),
+            None => quote!(::core::option::Option::None),
+        };
+        let at_line = lines.at_line.is_some();
+        let parse = |ty: &Type| {
+            let locate = if at_line {
+                quote!(::aoc::nom::AtLine::at_line(err, line))
+            } else {
+                quote!(::aoc::nom::LineError { line, error: err })
+            };
+            quote! {
+                |(line, record): (usize, &str)| {
+                    record.parse::<#ty>().map_err(|err| #locate)
+                }
+            }
+        };
+        let parse_record = parse(attr_type);
+        let body = match header {
+            None => quote! {
+                __records.map(#parse_record).collect()
+            },
+            Some(header) => {
+                let ty = &header.ty;
+                let parse_header = if at_line || !is_string(ty) {
+                    let parse_header = parse(ty);
+                    let convert = if at_line {
+                        quote!()
+                    } else {
+                        // The header's error must convert to the records'.
+                        quote! {
+                            .map_err(|err| ::aoc::nom::LineError {
+                                line: err.line,
+                                error: ::core::convert::From::from(err.error),
+                            })
+                        }
+                    };
+                    // An empty input still has a header to fail on.
+                    quote!((#parse_header)(__records.next().unwrap_or((1, "")))#convert?)
+                } else {
+                    // A `String` header cannot fail, and an empty input has
+                    // an empty one.
+                    quote! {
+                        __records.next().map_or_else(::std::string::String::new, |(_, record)| record.to_owned())
+                    }
+                };
+                let ctor = self
+                    .0
+                    .constructor(quote!(__collected), Some(quote!(__header)));
+                quote! {
+                    let __header = #parse_header;
+                    let __collected = __records
+                        .map(#parse_record)
+                        .collect::<::core::result::Result<#field_type, _>>()?;
+                    Ok(#ctor)
+                }
+            }
+        };
+        let err = if at_line {
+            quote!(<<#attr_type as ::core::str::FromStr>::Err as ::aoc::nom::AtLine>::Located)
+        } else {
+            quote!(::aoc::nom::LineError<<#attr_type as ::core::str::FromStr>::Err>)
+        };
+
         quote! {
             #from_iter
             #try_from_str
             impl #impl_generics ::core::str::FromStr for #struct_ident #ty_generics #where_clause {
-                type Err = <#attr_type as ::core::str::FromStr>::Err;
+                type Err = #err;
 
                 fn from_str(s: &str) -> ::core::result::Result<Self, <Self as ::core::str::FromStr>::Err> {
-                    s.lines().map(str::parse::<#attr_type>).collect()
+                    #[allow(unused_mut)]
+                    let mut __records = ::aoc::lines::Records::new(s, ::aoc::lines::Format {
+                        sep: #sep,
+                        skip_blank: #skip_blank,
+                        comment: #comment,
+                    });
+                    #body
                 }
             }
         }
     }
 }
 
+/// Whether `ty` is `String`, which parses from a line without failing.
+fn is_string(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "String"),
+        _ => false,
+    }
+}
+
 impl Parse for FromLines {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         Common::parse(input, "FromLines", "from_lines").map(Self)
@@ -0,0 +1,197 @@
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse::Parser;
+use syn::Data;
+use syn::Error;
+use syn::Field;
+use syn::Fields;
+use syn::GenericArgument;
+use syn::Generics;
+use syn::Ident;
+use syn::Member;
+use syn::PathArguments;
+use syn::Result;
+use syn::Type;
+
+pub fn expand(tokens: TokenStream) -> TokenStream {
+    FromSections::parse
+        .parse2(tokens)
+        .map(FromSections::into_token_stream)
+        .unwrap_or_else(syn::Error::into_compile_error)
+}
+
+pub(crate) struct FromSections {
+    pub(crate) ident: Ident,
+    pub(crate) generics: Generics,
+    named: bool,
+    pub(crate) fields: Vec<Section>,
+}
+
+pub(crate) struct Section {
+    span: Span,
+    pub(crate) member: Member,
+    name: String,
+    pub(crate) kind: Kind,
+}
+
+pub(crate) enum Kind {
+    /// The whole section, through the field's `FromStr`.
+    Whole(Type),
+    /// One element per line of the section.
+    Lines(Type),
+    /// One element per remaining section.
+    Each(Type),
+}
+
+/// The `T` of a `Vec<T>`.
+fn vec_element(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl Section {
+    fn new(idx: usize, field: &Field) -> Result<Self> {
+        let (member, name) = match &field.ident {
+            Some(ident) => (Member::Named(ident.clone()), ident.to_string()),
+            None => (Member::Unnamed(idx.into()), idx.to_string()),
+        };
+        let each = match field
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("section"))
+        {
+            Some(attr) => {
+                let ident = attr.parse_args::<Ident>()?;
+                if ident != "each" {
+                    return Err(Error::new(ident.span(), "expected `each`"));
+                }
+                true
+            }
+            None => false,
+        };
+        let kind = match (vec_element(&field.ty), each) {
+            (Some(element), true) => Kind::Each(element.clone()),
+            (None, true) => {
+                return Err(Error::new_spanned(
+                    &field.ty,
+                    "#[section(each)] needs a `Vec<T>` field",
+                ))
+            }
+            (Some(element), false) => Kind::Lines(element.clone()),
+            (None, false) => Kind::Whole(field.ty.clone()),
+        };
+        let span = match &field.ident {
+            Some(ident) => ident.span(),
+            None => syn::spanned::Spanned::span(&field.ty),
+        };
+        Ok(Self {
+            span,
+            member,
+            name,
+            kind,
+        })
+    }
+
+    fn read(&self) -> TokenStream {
+        let name = &self.name;
+        match &self.kind {
+            Kind::Whole(ty) => quote!(__sections.parse::<#ty>(#name)?),
+            Kind::Lines(ty) => quote!(__sections.lines::<#ty, _>(#name)?),
+            Kind::Each(ty) => quote!(__sections.each::<#ty, _>(#name)?),
+        }
+    }
+}
+
+impl Parse for FromSections {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let derive_input = syn::DeriveInput::parse(input)?;
+        let (named, fields) = match derive_input.data {
+            Data::Struct(data) => match data.fields {
+                Fields::Named(fields) => (true, fields.named),
+                Fields::Unnamed(fields) => (false, fields.unnamed),
+                Fields::Unit => {
+                    return Err(Error::new(
+                        derive_input.ident.span(),
+                        "cannot #[derive(FromSections)] for unit structs",
+                    ))
+                }
+            },
+            Data::Enum(data) => {
+                return Err(Error::new(
+                    data.enum_token.span,
+                    "can only #[derive(FromSections)] for structs",
+                ))
+            }
+            Data::Union(data) => {
+                return Err(Error::new(
+                    data.union_token.span,
+                    "can only #[derive(FromSections)] for structs",
+                ))
+            }
+        };
+        let fields = fields
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| Section::new(idx, field))
+            .collect::<Result<Vec<_>>>()?;
+        if let Some(pos) = fields.iter().position(|f| matches!(f.kind, Kind::Each(_))) {
+            if pos + 1 != fields.len() {
+                return Err(Error::new(
+                    fields[pos].span,
+                    format!(
+                        "#[section(each)] field `{}` must be the last field",
+                        fields[pos].name
+                    ),
+                ));
+            }
+        }
+        Ok(Self {
+            ident: derive_input.ident,
+            generics: derive_input.generics,
+            named,
+            fields,
+        })
+    }
+}
+
+impl FromSections {
+    fn into_token_stream(self) -> TokenStream {
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let reads = self.fields.iter().map(Section::read);
+        let ctor = if self.named {
+            let members = self.fields.iter().map(|f| &f.member);
+            quote!(Self { #(#members: #reads),* })
+        } else {
+            quote!(Self(#(#reads),*))
+        };
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::str::FromStr for #ident #ty_generics #where_clause {
+                type Err = ::aoc::sections::SectionError;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, <Self as ::core::str::FromStr>::Err> {
+                    let mut __sections = ::aoc::sections::Sections::new(s);
+                    let value = #ctor;
+                    __sections.finish()?;
+                    Ok(value)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ItemFn;
+use syn::LitInt;
+use syn::Result;
+
+use crate::solution::check_date;
+use crate::solution::parse_args;
+use crate::solution::Args;
+
+pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
+    Generator::parse(attr, item)
+        .map(Generator::into_token_stream)
+        .unwrap_or_else(syn::Error::into_compile_error)
+}
+
+struct Generator {
+    year: LitInt,
+    day: LitInt,
+    item: ItemFn,
+}
+
+impl Generator {
+    fn parse(attr: TokenStream, item: TokenStream) -> Result<Self> {
+        let Args {
+            ints: [year, day], ..
+        } = parse_args(attr, "generator", ["year", "day"], [], [])?;
+        let item = syn::parse2::<ItemFn>(item)?;
+        check_date(&year, &day)?;
+        Ok(Self { year, day, item })
+    }
+
+    fn into_token_stream(self) -> TokenStream {
+        let Self { year, day, item } = &self;
+        let generate = &item.sig.ident;
+        quote! {
+            #item
+
+            const _: () = {
+                ::aoc::solution::inventory::submit! {
+                    ::aoc::gen::Registered {
+                        year: #year,
+                        day: #day,
+                        generate: #generate,
+                    }
+                }
+            };
+        }
+    }
+}
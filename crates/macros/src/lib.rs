@@ -19,17 +19,24 @@
 // SOFTWARE.
 
 pub(crate) mod common;
+mod display;
 mod from_bytes;
+mod from_grid;
 mod from_iterator;
 mod from_lines;
+mod from_sections;
+mod generator;
+mod pack;
 mod roundtrip;
+mod scan;
+mod solution;
 mod try_from_str;
 mod unwrap;
 
 macro_rules! derive {
-    ($mod:ident, $ty:ident $(, $attr:ident)? $(,)?) => {
+    ($mod:ident, $ty:ident $(, $attr:ident)* $(,)?) => {
         paste::paste! {
-            #[proc_macro_derive($ty $(, attributes($attr))?)]
+            #[proc_macro_derive($ty, attributes($($attr),*))]
             #[proc_macro_error::proc_macro_error]
             pub fn [<derive_ $mod>](input: ::proc_macro::TokenStream) -> ::proc_macro::TokenStream {
                 self::$mod::expand(input.into()).into()
@@ -38,11 +45,16 @@ macro_rules! derive {
     }
 }
 
+derive!(display, Display, from_lines, section);
 derive!(from_bytes, FromBytes, from_bytes);
+derive!(from_grid, FromGrid, tile);
 derive!(from_iterator, FromIterator, from_iterator);
 derive!(from_lines, FromLines, from_lines);
+derive!(from_sections, FromSections, section);
+derive!(pack, Pack, bits);
+derive!(scan, Scan, scan);
 derive!(try_from_str, TryFromStr);
-derive!(unwrap, Unwrap);
+derive!(unwrap, Unwrap, unwrap);
 
 #[proc_macro]
 #[proc_macro_error::proc_macro_error]
@@ -50,6 +62,24 @@ pub fn test_roundtrip(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     self::roundtrip::expand(input.into()).into()
 }
 
+#[proc_macro_attribute]
+#[proc_macro_error::proc_macro_error]
+pub fn solution(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    self::solution::expand(attr.into(), item.into()).into()
+}
+
+#[proc_macro_attribute]
+#[proc_macro_error::proc_macro_error]
+pub fn generator(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    self::generator::expand(attr.into(), item.into()).into()
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -83,13 +113,19 @@ mod tests {
     const FUNCTION_LIKE: &[[Macro; 3]] = &[macro_!(test_roundtrip, roundtrip)];
 
     const DERIVE: &[[Macro; 3]] = &[
+        macro_!(Display, display),
         macro_!(FromBytes, from_bytes),
+        macro_!(FromGrid, from_grid),
         macro_!(FromIterator, from_iterator),
         macro_!(FromLines, from_lines),
+        macro_!(FromSections, from_sections),
+        macro_!(Pack, pack),
+        macro_!(Scan, scan),
         macro_!(TryFromStr, try_from_str),
         macro_!(Unwrap, unwrap),
     ];
-    const ATTRIBUTE: &[[AttrMacro; 3]] = &[];
+    const ATTRIBUTE: &[[AttrMacro; 3]] =
+        &[macro_!(generator, generator), macro_!(solution, solution)];
 
     const SRC_ROOT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../src");
 
@@ -98,7 +134,7 @@ mod tests {
             .into_iter()
             .map(Result::unwrap)
             .map(DirEntry::into_path)
-            .filter(|p| p.extension().map_or(false, |p| p == "rs"))
+            .filter(|p| p.extension().is_some_and(|p| p == "rs"))
     }
 
     fn flatten<const N: usize, T>(arr: &[[T; N]]) -> &[T] {
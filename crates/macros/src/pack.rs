@@ -0,0 +1,289 @@
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::parse::Parser;
+use syn::spanned::Spanned;
+use syn::Attribute;
+use syn::Data;
+use syn::Error;
+use syn::Fields;
+use syn::Generics;
+use syn::Ident;
+use syn::Index;
+use syn::LitInt;
+use syn::Member;
+use syn::Result;
+use syn::Type;
+
+pub fn expand(tokens: TokenStream) -> TokenStream {
+    Pack::parse
+        .parse2(tokens)
+        .map(Pack::into_token_stream)
+        .unwrap_or_else(syn::Error::into_compile_error)
+}
+
+struct Pack {
+    ident: Ident,
+    generics: Generics,
+    key: Type,
+    layout: Layout,
+}
+
+enum Layout {
+    Struct(Vec<Field>),
+    Enum(Vec<(Ident, Vec<Field>)>),
+}
+
+struct Field {
+    member: Member,
+    ty: Type,
+    /// The `n` of `#[bits(n)]`, if the field is narrowed.
+    bits: Option<LitInt>,
+}
+
+impl Field {
+    fn width(&self) -> TokenStream {
+        match &self.bits {
+            Some(bits) => quote!(#bits),
+            None => {
+                let ty = &self.ty;
+                quote!(<#ty as ::aoc::pack::Pack>::BITS)
+            }
+        }
+    }
+
+    fn binding(&self) -> Ident {
+        match &self.member {
+            Member::Named(ident) => format_ident!("__{}", ident),
+            Member::Unnamed(index) => format_ident!("__{}", index.index),
+        }
+    }
+
+    /// Fails the build if `#[bits(n)]` is wider than the field's type.
+    fn check(&self) -> Option<TokenStream> {
+        let bits = self.bits.as_ref()?;
+        let ty = &self.ty;
+        let message = match &self.member {
+            Member::Named(ident) => format!("#[bits({bits})] on `{ident}` is wider than its type"),
+            Member::Unnamed(index) => {
+                format!(
+                    "#[bits({bits})] on field {} is wider than its type",
+                    index.index
+                )
+            }
+        };
+        Some(quote!(assert!(#bits <= <#ty as ::aoc::pack::Pack>::BITS, #message);))
+    }
+}
+
+/// The single `#[bits(…)]` among `attrs`, if any.
+fn bits_attr(attrs: &[Attribute]) -> Result<Option<&Attribute>> {
+    let mut bits = attrs.iter().filter(|attr| attr.path.is_ident("bits"));
+    let first = bits.next();
+    match bits.next() {
+        Some(attr) => Err(Error::new(attr.path.span(), "duplicate #[bits(…)]")),
+        None => Ok(first),
+    }
+}
+
+fn fields(fields: Fields) -> Result<Vec<Field>> {
+    fields
+        .into_iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let bits = bits_attr(&field.attrs)?
+                .map(|attr| {
+                    let bits = attr.parse_args::<LitInt>()?;
+                    match bits.base10_parse::<u32>() {
+                        Ok(1..=128) => Ok(bits),
+                        _ => Err(Error::new(
+                            bits.span(),
+                            "a field takes between 1 and 128 bits",
+                        )),
+                    }
+                })
+                .transpose()?;
+            let member = match field.ident {
+                Some(ident) => Member::Named(ident),
+                None => Member::Unnamed(Index {
+                    index: index as u32,
+                    span: field.ty.span(),
+                }),
+            };
+            Ok(Field {
+                member,
+                ty: field.ty,
+                bits,
+            })
+        })
+        .collect()
+}
+
+impl Parse for Pack {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let derive_input = syn::DeriveInput::parse(input)?;
+        let key = match bits_attr(&derive_input.attrs)? {
+            Some(attr) => {
+                if let Ok(bits) = attr.parse_args::<LitInt>() {
+                    return Err(Error::new(
+                        bits.span(),
+                        "#[bits(…)] on a type names its key, like #[bits(u64)]",
+                    ));
+                }
+                attr.parse_args::<Type>()?
+            }
+            None => syn::parse_quote!(u64),
+        };
+        let layout = match derive_input.data {
+            Data::Struct(data) => Layout::Struct(fields(data.fields)?),
+            Data::Enum(data) => {
+                if data.variants.is_empty() {
+                    return Err(Error::new(
+                        derive_input.ident.span(),
+                        "can not #[derive(Pack)] for an enum with no variants",
+                    ));
+                }
+                let variants = data
+                    .variants
+                    .into_iter()
+                    .map(|variant| Ok((variant.ident, fields(variant.fields)?)))
+                    .collect::<Result<_>>()?;
+                Layout::Enum(variants)
+            }
+            Data::Union(data) => {
+                return Err(Error::new(
+                    data.union_token.span,
+                    "can only #[derive(Pack)] for structs and enums",
+                ))
+            }
+        };
+
+        Ok(Self {
+            ident: derive_input.ident,
+            generics: derive_input.generics,
+            key,
+            layout,
+        })
+    }
+}
+
+/// The total width of `fields`, and the expressions that pack each of them
+/// (bound by [`Field::binding`]) above `base` and unpack them again.
+fn pack_fields(
+    fields: &[Field],
+    base: &TokenStream,
+) -> (TokenStream, Vec<TokenStream>, Vec<TokenStream>) {
+    let mut offset = base.clone();
+    let mut puts = Vec::new();
+    let mut takes = Vec::new();
+    for field in fields {
+        let (member, binding, width) = (&field.member, field.binding(), field.width());
+        puts.push(quote!(::aoc::pack::put(#binding, #width, #offset)));
+        takes.push(quote!(#member: ::aoc::pack::take(bits, #width, #offset)));
+        offset = quote!(#offset + #width);
+    }
+    (offset, puts, takes)
+}
+
+impl Pack {
+    fn into_token_stream(self) -> TokenStream {
+        let ident = &self.ident;
+        let key = &self.key;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let (width, checks, pack, unpack) = match &self.layout {
+            Layout::Struct(fields) => {
+                let (width, puts, takes) = pack_fields(fields, &quote!(0));
+                let members = fields.iter().map(|field| &field.member);
+                let bindings = fields.iter().map(Field::binding);
+                let pack = quote! {
+                    let Self { #(#members: #bindings),* } = self;
+                    0 #(| #puts)*
+                };
+                let unpack = quote!(Self { #(#takes),* });
+                (
+                    width,
+                    fields.iter().filter_map(Field::check).collect::<Vec<_>>(),
+                    pack,
+                    unpack,
+                )
+            }
+            Layout::Enum(variants) => {
+                // enough bits to count to the last variant; the parse has
+                // already rejected enums without any
+                let last = variants.len().saturating_sub(1) as u128;
+                let tag = u128::BITS - last.leading_zeros();
+                let mut width = quote!(0);
+                let mut packs = Vec::new();
+                let mut unpacks = Vec::new();
+                for (index, (variant, fields)) in variants.iter().enumerate() {
+                    let index = index as u128;
+                    let (fields_width, puts, takes) = pack_fields(fields, &quote!(#tag));
+                    let members = fields.iter().map(|field| &field.member);
+                    let bindings = fields.iter().map(Field::binding);
+                    packs.push(quote! {
+                        Self::#variant { #(#members: #bindings),* } => #index #(| #puts)*,
+                    });
+                    unpacks.push(quote!(#index => Self::#variant { #(#takes),* },));
+                    width = quote!(::aoc::pack::max(#width, #fields_width));
+                }
+                let pack = quote!(match self { #(#packs)* });
+                let unpack = quote! {
+                    match bits & ::aoc::pack::mask(#tag) {
+                        #(#unpacks)*
+                        _ => panic!("{:?} is not a packed `{}`", key, stringify!(#ident)),
+                    }
+                };
+                let checks = variants
+                    .iter()
+                    .flat_map(|(_, fields)| fields.iter().filter_map(Field::check))
+                    .collect();
+                (width, checks, pack, unpack)
+            }
+        };
+
+        let overflow = format!(
+            "`{ident}` needs more bits than `{}` has",
+            quote!(#key).to_string().replace(' ', "")
+        );
+        // Generic layouts are checked when `pack` or `unpack` is
+        // instantiated; others as soon as the crate is built.
+        let eager = self.generics.params.is_empty().then(|| {
+            quote! {
+                const _: u32 = <#ident as ::aoc::pack::Pack>::BITS;
+            }
+        });
+
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::aoc::pack::Pack for #ident #ty_generics #where_clause {
+                type Key = #key;
+
+                const BITS: u32 = {
+                    #(#checks)*
+                    let bits = #width;
+                    assert!(bits <= <#key as ::aoc::pack::Key>::BITS, #overflow);
+                    bits
+                };
+
+                #[allow(clippy::identity_op)]
+                fn pack(&self) -> #key {
+                    let _ = <Self as ::aoc::pack::Pack>::BITS;
+                    let bits = { #pack };
+                    <#key as ::aoc::pack::Key>::from_bits(bits)
+                }
+
+                #[allow(unused_variables)]
+                fn unpack(key: #key) -> Self {
+                    let _ = <Self as ::aoc::pack::Pack>::BITS;
+                    let bits = <#key as ::aoc::pack::Key>::into_bits(key);
+                    #unpack
+                }
+            }
+
+            #eager
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::parse::Parse;
+use syn::parse::Parser;
+use syn::Attribute;
+use syn::Data;
+use syn::Error;
+use syn::Fields;
+use syn::Generics;
+use syn::Ident;
+use syn::LitStr;
+use syn::Member;
+use syn::Result;
+use syn::Type;
+
+pub fn expand(tokens: TokenStream) -> TokenStream {
+    Scan::parse
+        .parse2(tokens)
+        .map(Scan::into_token_stream)
+        .unwrap_or_else(syn::Error::into_compile_error)
+}
+
+struct Scan {
+    ident: Ident,
+    generics: Generics,
+    cases: Vec<Case>,
+}
+
+/// A struct, or one variant of an enum, with its own pattern.
+struct Case {
+    path: TokenStream,
+    fields: Fields,
+    pattern: Pattern,
+}
+
+/// `literals` surround the placeholders: there is always one more literal
+/// than there are placeholders, and only the first and last may be empty.
+struct Pattern {
+    literals: Vec<String>,
+    placeholders: Vec<Member>,
+}
+
+impl Parse for Scan {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let derive_input = syn::DeriveInput::parse(input)?;
+        let ident = derive_input.ident;
+        let cases = match derive_input.data {
+            Data::Struct(data) => vec![Case::new(
+                quote!(Self),
+                &ident,
+                &derive_input.attrs,
+                data.fields,
+            )?],
+            Data::Enum(data) => data
+                .variants
+                .into_iter()
+                .map(|v| {
+                    let variant = &v.ident;
+                    Case::new(quote!(Self::#variant), &v.ident, &v.attrs, v.fields)
+                })
+                .collect::<Result<_>>()?,
+            Data::Union(data) => {
+                return Err(Error::new(
+                    data.union_token.span,
+                    "cannot #[derive(Scan)] for unions",
+                ))
+            }
+        };
+        Ok(Self {
+            ident,
+            generics: derive_input.generics,
+            cases,
+        })
+    }
+}
+
+impl Scan {
+    fn into_token_stream(self) -> TokenStream {
+        let ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let scans = self.cases.iter().map(Case::scan);
+        let displays = self.cases.iter().map(Case::display);
+        quote! {
+            #[automatically_derived]
+            impl #impl_generics ::core::str::FromStr for #ident #ty_generics #where_clause {
+                type Err = ::aoc::nom::Error;
+
+                fn from_str(s: &str) -> ::core::result::Result<Self, <Self as ::core::str::FromStr>::Err> {
+                    let mut __scanner = ::aoc::scan::Scanner::new(s);
+                    #(#scans)*
+                    Err(__scanner.finish())
+                }
+            }
+
+            #[automatically_derived]
+            impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    match self {
+                        #(#displays)*
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn binding(member: &Member) -> Ident {
+    match member {
+        Member::Named(ident) => format_ident!("__field_{}", ident),
+        Member::Unnamed(index) => format_ident!("__field_{}", index.index),
+    }
+}
+
+impl Case {
+    fn new(path: TokenStream, ident: &Ident, attrs: &[Attribute], fields: Fields) -> Result<Self> {
+        let lit = attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("scan"))
+            .ok_or_else(|| Error::new(ident.span(), "missing #[scan(\"...\")] attribute"))?
+            .parse_args::<LitStr>()?;
+        let pattern = Pattern::parse(&lit, &fields)?;
+        Ok(Self {
+            path,
+            fields,
+            pattern,
+        })
+    }
+
+    fn members(&self) -> Vec<Member> {
+        self.fields
+            .iter()
+            .enumerate()
+            .map(|(idx, field)| match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(idx.into()),
+            })
+            .collect()
+    }
+
+    /// `Path { a: __field_a, .. }`, usable both to build and to match.
+    fn constructor(&self) -> TokenStream {
+        let path = &self.path;
+        let members = self.members();
+        let bindings = members.iter().map(binding);
+        match &self.fields {
+            Fields::Named(_) => quote!(#path { #(#members: #bindings),* }),
+            Fields::Unnamed(_) => quote!(#path ( #(#bindings),* )),
+            Fields::Unit => quote!(#path),
+        }
+    }
+
+    fn field_type(&self, member: &Member) -> &Type {
+        let idx = self
+            .members()
+            .iter()
+            .position(|m| m == member)
+            .expect("placeholders are checked against the fields");
+        &self.fields.iter().nth(idx).unwrap().ty
+    }
+
+    /// Try this case's pattern, returning from `from_str` if it matches.
+    fn scan(&self) -> TokenStream {
+        let Pattern {
+            literals,
+            placeholders,
+        } = &self.pattern;
+        let ctor = self.constructor();
+
+        let mut body = match placeholders.last() {
+            None => quote! {
+                if __scanner.end(__rest) {
+                    return Ok(#ctor);
+                }
+            },
+            Some(member) => {
+                let ty = self.field_type(member);
+                let bind = binding(member);
+                let name = member_name(member);
+                let suffix = literals.last().unwrap();
+                quote! {
+                    if let Some(__text) = __scanner.suffix(__rest, #suffix) {
+                        if let Some(#bind) = __scanner.field::<#ty>(__text, #name) {
+                            return Ok(#ctor);
+                        }
+                    }
+                }
+            }
+        };
+
+        let inner = placeholders.len().saturating_sub(1);
+        for (member, literal) in placeholders[..inner].iter().zip(&literals[1..]).rev() {
+            let ty = self.field_type(member);
+            let bind = binding(member);
+            let name = member_name(member);
+            body = quote! {
+                for __split in __scanner.splits(__rest, #literal) {
+                    let Some(#bind) = __scanner.field::<#ty>(&__rest[..__split], #name) else {
+                        continue;
+                    };
+                    let __rest = &__rest[__split + #literal.len()..];
+                    #body
+                }
+            };
+        }
+
+        let prefix = &literals[0];
+        quote! {
+            if let Some(__rest) = __scanner.literal(s, #prefix) {
+                #body
+            }
+        }
+    }
+
+    fn display(&self) -> TokenStream {
+        let ctor = self.constructor();
+        let Pattern {
+            literals,
+            placeholders,
+        } = &self.pattern;
+        let writes = literals.iter().enumerate().map(|(idx, literal)| {
+            let literal = (!literal.is_empty()).then(|| quote!(f.write_str(#literal)?;));
+            let field = placeholders.get(idx).map(|member| {
+                let bind = binding(member);
+                quote!(::core::fmt::Display::fmt(#bind, f)?;)
+            });
+            quote!(#literal #field)
+        });
+        quote! {
+            #ctor => {
+                #(#writes)*
+                Ok(())
+            }
+        }
+    }
+}
+
+fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+impl Pattern {
+    fn parse(lit: &LitStr, fields: &Fields) -> Result<Self> {
+        let err = |msg: String| Error::new(lit.span(), msg);
+        let text = lit.value();
+        let mut literals = vec![String::new()];
+        let mut placeholders = Vec::<Member>::new();
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literals.last_mut().unwrap().push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literals.last_mut().unwrap().push('}');
+                }
+                '}' => {
+                    return Err(err(
+                        "unmatched `}` in pattern; use `}}` for a literal brace".into(),
+                    ))
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => {
+                                return Err(err(
+                                    "unclosed `{` in pattern; use `{{` for a literal brace".into(),
+                                ))
+                            }
+                        }
+                    }
+                    let member = member(&name, fields, lit.span()).map_err(err)?;
+                    if placeholders.contains(&member) {
+                        return Err(err(format!("`{{{name}}}` appears more than once")));
+                    }
+                    if let (Some(prev), true) =
+                        (placeholders.last(), literals.last().unwrap().is_empty())
+                    {
+                        let prev = member_name(prev);
+                        return Err(err(format!(
+                            "`{{{prev}}}` and `{{{name}}}` must be separated by literal text"
+                        )));
+                    }
+                    placeholders.push(member);
+                    literals.push(String::new());
+                }
+                c => literals.last_mut().unwrap().push(c),
+            }
+        }
+
+        for (idx, field) in fields.iter().enumerate() {
+            let member = match &field.ident {
+                Some(ident) => Member::Named(ident.clone()),
+                None => Member::Unnamed(idx.into()),
+            };
+            if !placeholders.contains(&member) {
+                let name = member_name(&member);
+                return Err(err(format!("the pattern has no `{{{name}}}` placeholder")));
+            }
+        }
+
+        Ok(Self {
+            literals,
+            placeholders,
+        })
+    }
+}
+
+fn member(name: &str, fields: &Fields, span: Span) -> std::result::Result<Member, String> {
+    let name = name.trim();
+    match fields {
+        Fields::Named(named) => named
+            .named
+            .iter()
+            .filter_map(|f| f.ident.as_ref())
+            .find(|ident| *ident == name)
+            .map(|ident| Member::Named(ident.clone()))
+            .ok_or_else(|| format!("there is no field named `{name}`")),
+        Fields::Unnamed(unnamed) => match name.parse::<u32>() {
+            Ok(idx) if (idx as usize) < unnamed.unnamed.len() => {
+                Ok(Member::Unnamed(syn::Index { index: idx, span }))
+            }
+            _ => Err(format!("there is no field `{name}`; use `{{0}}`, `{{1}}`…")),
+        },
+        Fields::Unit => Err(format!(
+            "`{{{name}}}` refers to a field, but there are none"
+        )),
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::Error;
+use syn::Ident;
+use syn::ItemFn;
+use syn::Lit;
+use syn::LitInt;
+use syn::Meta;
+use syn::NestedMeta;
+use syn::Result;
+
+pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
+    Solution::parse(attr, item)
+        .map(Solution::into_token_stream)
+        .unwrap_or_else(syn::Error::into_compile_error)
+}
+
+struct Solution {
+    year: LitInt,
+    day: LitInt,
+    part: LitInt,
+    /// The input is parsed from `&[u8]` rather than `&str`.
+    bytes: bool,
+    /// The solver takes a parameter set as its second argument.
+    params: bool,
+    /// `invalid(…)`: the inputs that the parser is expected to reject.
+    invalid: Vec<Ident>,
+    item: ItemFn,
+}
+
+/// Check that `lit` is an integer in `range`.
+fn in_range(lit: &LitInt, name: &str, range: std::ops::RangeInclusive<u16>) -> Result<()> {
+    match lit.base10_parse::<u16>() {
+        Ok(v) if range.contains(&v) => Ok(()),
+        _ => Err(Error::new(
+            lit.span(),
+            format!(
+                "`{name}` must be between {} and {}",
+                range.start(),
+                range.end()
+            ),
+        )),
+    }
+}
+
+/// The arguments of an attribute, in the order their names were given to
+/// [`parse_args`].
+pub(crate) struct Args<const I: usize, const F: usize, const L: usize> {
+    /// The `name = …` integers, all required.
+    pub ints: [LitInt; I],
+    /// Whether each bare flag was given.
+    pub flags: [bool; F],
+    /// The names in each `name(a, b, …)`, empty unless given.
+    pub lists: [Vec<Ident>; L],
+}
+
+/// Parse the arguments of `#[{name}(...)]`.
+pub(crate) fn parse_args<const I: usize, const F: usize, const L: usize>(
+    attr: TokenStream,
+    name: &str,
+    ints: [&str; I],
+    flags: [&str; F],
+    lists: [&str; L],
+) -> Result<Args<I, F, L>> {
+    let args = Punctuated::<NestedMeta, Comma>::parse_terminated.parse2(attr)?;
+    let expected = || {
+        let mut names = ints
+            .iter()
+            .chain(&flags)
+            .map(|n| format!("`{n}`"))
+            .chain(lists.iter().map(|n| format!("`{n}(…)`")));
+        let last = names.next_back().unwrap_or_default();
+        let names = names.collect::<Vec<_>>();
+        if names.is_empty() {
+            format!("expected {last}")
+        } else {
+            format!("expected {} or {last}", names.join(", "))
+        }
+    };
+
+    let mut values: [Option<LitInt>; I] = std::array::from_fn(|_| None);
+    let mut given = [false; F];
+    let mut listed: [Vec<Ident>; L] = std::array::from_fn(|_| Vec::new());
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::NameValue(nv)) => {
+                let Some(slot) = ints
+                    .iter()
+                    .position(|n| nv.path.is_ident(n))
+                    .map(|i| &mut values[i])
+                else {
+                    return Err(Error::new_spanned(&nv.path, expected()));
+                };
+                let Lit::Int(lit) = nv.lit else {
+                    return Err(Error::new_spanned(nv.lit, "expected an integer"));
+                };
+                if slot.is_some() {
+                    return Err(Error::new_spanned(&nv.path, "duplicate argument"));
+                }
+                *slot = Some(lit);
+            }
+            NestedMeta::Meta(Meta::Path(path)) if flags.iter().any(|f| path.is_ident(f)) => {
+                let i = flags.iter().position(|f| path.is_ident(f)).unwrap();
+                given[i] = true;
+            }
+            NestedMeta::Meta(Meta::List(list)) if lists.iter().any(|l| list.path.is_ident(l)) => {
+                let i = lists.iter().position(|l| list.path.is_ident(l)).unwrap();
+                for nested in list.nested {
+                    let ident = match &nested {
+                        NestedMeta::Meta(Meta::Path(path)) => path.get_ident(),
+                        NestedMeta::Meta(_) | NestedMeta::Lit(_) => None,
+                    };
+                    match ident {
+                        Some(ident) => listed[i].push(ident.clone()),
+                        None => return Err(Error::new_spanned(nested, "expected a name")),
+                    }
+                }
+            }
+            arg => return Err(Error::new_spanned(arg, expected())),
+        }
+    }
+
+    if let Some(missing) = values.iter().position(Option::is_none) {
+        return Err(Error::new(
+            Span::call_site(),
+            format!("missing `{} = …` in #[{name}(...)]", ints[missing]),
+        ));
+    }
+    Ok(Args {
+        ints: values.map(Option::unwrap),
+        flags: given,
+        lists: listed,
+    })
+}
+
+/// Check the `year` and `day` that a solver or generator is for.
+pub(crate) fn check_date(year: &LitInt, day: &LitInt) -> Result<()> {
+    in_range(year, "year", 2015..=9999)?;
+    in_range(day, "day", 1..=25)
+}
+
+impl Solution {
+    fn parse(attr: TokenStream, item: TokenStream) -> Result<Self> {
+        let Args {
+            ints: [year, day, part],
+            flags: [bytes],
+            lists: [invalid],
+        } = parse_args(attr, "solution", ["year", "day", "part"], ["bytes"], [
+            "invalid",
+        ])?;
+        let item = syn::parse2::<ItemFn>(item)?;
+        check_date(&year, &day)?;
+        in_range(&part, "part", 1..=2)?;
+
+        let params = match item.sig.inputs.len() {
+            1 => false,
+            2 => true,
+            _ => {
+                return Err(Error::new_spanned(
+                    &item.sig.inputs,
+                    "a solver takes its input and, optionally, its parameters",
+                ))
+            }
+        };
+
+        Ok(Self {
+            year,
+            day,
+            part,
+            bytes,
+            params,
+            invalid,
+            item,
+        })
+    }
+
+    fn into_token_stream(self) -> TokenStream {
+        let Self {
+            year,
+            day,
+            part,
+            bytes,
+            params,
+            invalid,
+            item,
+        } = &self;
+        let solve = &item.sig.ident;
+        let input = if *bytes {
+            quote!(s.as_bytes())
+        } else {
+            quote!(s)
+        };
+        let solver = if *params {
+            quote!(#solve)
+        } else {
+            quote!(|input, ()| #solve(input))
+        };
+        let test = format_ident!("test_{}_registration", solve);
+        let invalid = invalid.iter().map(Ident::to_string);
+        quote! {
+            #item
+
+            #[cfg(test)]
+            #[test]
+            fn #test() {
+                ::aoc::solution::check_registration(
+                    #year,
+                    #day,
+                    #part,
+                    ::core::concat!(::core::env!("CARGO_MANIFEST_DIR"), "/", ::core::file!()),
+                    &[#(#invalid),*],
+                );
+            }
+
+            const _: () = {
+                ::aoc::solution::inventory::submit! {
+                    ::aoc::solution::Solution {
+                        year: #year,
+                        day: #day,
+                        part: #part,
+                        file: ::core::concat!(::core::env!("CARGO_MANIFEST_DIR"), "/", ::core::file!()),
+                        prepare: |s, pairs| ::aoc::solution::prepare(#input, pairs, #solver),
+                    }
+                }
+            };
+        }
+    }
+}
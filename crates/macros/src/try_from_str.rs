@@ -52,6 +52,7 @@ impl TryFromStr {
         let (impl_generics, _, _) = trait_generics.split_for_impl();
 
         quote! {
+            #[allow(clippy::infallible_try_from)]
             impl #impl_generics ::core::convert::TryFrom<&'__try_from str> for #struct_ident #ty_generics #where_clause {
                 type Error = <Self as ::core::str::FromStr>::Err;
 
@@ -3,19 +3,20 @@ use convert_case::Casing;
 use proc_macro2::Delimiter;
 use proc_macro2::Group;
 use proc_macro2::Ident;
-use proc_macro2::Span;
 use proc_macro2::TokenStream;
 use quote::format_ident;
 use quote::quote;
 use quote::ToTokens;
 use syn::parse::Parse;
 use syn::parse::Parser;
+use syn::Attribute;
 use syn::Data;
 use syn::Error;
 use syn::Fields;
 use syn::Generics;
 use syn::Type;
 use syn::Variant;
+use syn::Visibility;
 
 pub fn expand(tokens: TokenStream) -> TokenStream {
     Unwrap::parse.parse2(tokens).map_or_else(
@@ -30,26 +31,54 @@ fn snake_ident(ident: &Ident) -> Ident {
 }
 
 struct Unwrap {
+    vis: Visibility,
     ident: Ident,
     generics: Generics,
     variants: Vec<Variant>,
+    kind: bool,
+}
+
+/// The single identifier in `#[unwrap(...)]`, if the attribute is present.
+fn unwrap_attr(attrs: &[Attribute], expected: &str) -> syn::Result<bool> {
+    match attrs.iter().find(|attr| attr.path.is_ident("unwrap")) {
+        Some(attr) => {
+            let ident = attr.parse_args::<Ident>()?;
+            if ident == expected {
+                Ok(true)
+            } else {
+                Err(Error::new(ident.span(), format!("expected `{expected}`")))
+            }
+        }
+        None => Ok(false),
+    }
 }
 
 impl Parse for Unwrap {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let derive_input = syn::DeriveInput::parse(input)?;
 
+        let kind = unwrap_attr(&derive_input.attrs, "kind")?;
+        let vis = derive_input.vis;
         let ident = derive_input.ident;
         let generics = derive_input.generics;
         let variants = match derive_input.data {
             Data::Enum(data) => Ok(data.variants.into_iter().collect()),
-            _ => Err(Error::new(Span::call_site(), "expected enum")),
+            Data::Struct(data) => Err(Error::new(
+                data.struct_token.span,
+                "can only #[derive(Unwrap)] for enums",
+            )),
+            Data::Union(data) => Err(Error::new(
+                data.union_token.span,
+                "can only #[derive(Unwrap)] for enums",
+            )),
         }?;
 
         Ok(Self {
+            vis,
             ident,
             generics,
             variants,
+            kind,
         })
     }
 }
@@ -58,11 +87,56 @@ impl Unwrap {
     fn into_token_stream(self) -> TokenStream {
         let ident = &self.ident;
         let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
-        let methods = self.variants.iter().map(|v| self.impl_variant(v));
+        let methods = self
+            .variants
+            .iter()
+            .map(|v| match unwrap_attr(&v.attrs, "skip") {
+                Ok(true) => quote!(),
+                Ok(false) => self.impl_variant(v),
+                Err(err) => err.to_compile_error(),
+            })
+            .collect::<TokenStream>();
+        let kind = self.kind.then(|| self.impl_kind());
         quote! {
             #[automatically_derived]
             impl #impl_generics #ident #ty_generics #where_clause {
-                #(#methods)*
+                #methods
+            }
+
+            #kind
+        }
+    }
+
+    /// A fieldless copy of the enum and a `kind()` method returning it.
+    fn impl_kind(&self) -> TokenStream {
+        let vis = &self.vis;
+        let ident = &self.ident;
+        let kind_ident = format_ident!("{}Kind", ident);
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let names = self.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+        let patterns = self.variants.iter().map(|v| {
+            let name = &v.ident;
+            match &v.fields {
+                Fields::Named(_) => quote!(Self::#name { .. }),
+                Fields::Unnamed(_) => quote!(Self::#name(..)),
+                Fields::Unit => quote!(Self::#name),
+            }
+        });
+        let doc = format!("The variants of [`{ident}`] without their fields.");
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            #vis enum #kind_ident {
+                #(#names,)*
+            }
+
+            #[automatically_derived]
+            impl #impl_generics #ident #ty_generics #where_clause {
+                pub fn kind(&self) -> #kind_ident {
+                    match self {
+                        #(#patterns => #kind_ident::#names,)*
+                    }
+                }
             }
         }
     }
@@ -116,7 +190,7 @@ impl Unwrap {
 
         let is_enum = UnwrapMethod {
             method_name: format_ident!("is_{}", snake_ident),
-            receiver: &recv_ref,
+            params: &recv_ref,
             return_type: &quote!(bool),
             pattern: &match delimiter {
                 Delimiter::Parenthesis => quote!(Self::#ident ( .. )),
@@ -151,12 +225,19 @@ impl Unwrap {
 
         let panic_default = quote!(panic!("unexpected variant"));
         let none_default = quote!(None);
+        let self_default = quote!(self);
+        let err_default = quote!(Err(self));
+        let map_params = quote!(self, f: impl ::core::ops::FnOnce(#rt_owned) -> #rt_owned);
+        let map_expr = quote!({
+            let #expr = f(#expr);
+            #pattern
+        });
 
         [
             is_enum,
             UnwrapMethod {
                 method_name: format_ident!("unwrap_{snake_ident}"),
-                receiver: &receiver,
+                params: &receiver,
                 return_type: &rt_owned,
                 pattern,
                 expr,
@@ -164,7 +245,7 @@ impl Unwrap {
             },
             UnwrapMethod {
                 method_name: format_ident!("unwrap_{snake_ident}_ref"),
-                receiver: &recv_ref,
+                params: &recv_ref,
                 return_type: &rt_ref,
                 pattern,
                 expr,
@@ -172,7 +253,7 @@ impl Unwrap {
             },
             UnwrapMethod {
                 method_name: format_ident!("unwrap_{snake_ident}_mut"),
-                receiver: &recv_mut,
+                params: &recv_mut,
                 return_type: &rt_mut,
                 pattern,
                 expr,
@@ -180,7 +261,7 @@ impl Unwrap {
             },
             UnwrapMethod {
                 method_name: format_ident!("as_{snake_ident}"),
-                receiver: &receiver,
+                params: &receiver,
                 return_type: &quote!(Option<#rt_owned>),
                 pattern,
                 expr: some_expr,
@@ -188,7 +269,7 @@ impl Unwrap {
             },
             UnwrapMethod {
                 method_name: format_ident!("as_{snake_ident}_ref"),
-                receiver: &recv_ref,
+                params: &recv_ref,
                 return_type: &quote!(Option<#rt_ref>),
                 pattern,
                 expr: some_expr,
@@ -196,12 +277,28 @@ impl Unwrap {
             },
             UnwrapMethod {
                 method_name: format_ident!("as_{snake_ident}_mut"),
-                receiver: &recv_mut,
+                params: &recv_mut,
                 return_type: &quote!(Option<#rt_mut>),
                 pattern,
                 expr: some_expr,
                 default: &none_default,
             },
+            UnwrapMethod {
+                method_name: format_ident!("try_into_{snake_ident}"),
+                params: &receiver,
+                return_type: &quote!(Result<#rt_owned, Self>),
+                pattern,
+                expr: &quote!(Ok(#expr)),
+                default: &err_default,
+            },
+            UnwrapMethod {
+                method_name: format_ident!("map_{snake_ident}"),
+                params: &map_params,
+                return_type: &quote!(Self),
+                pattern,
+                expr: &map_expr,
+                default: &self_default,
+            },
         ]
         .into_iter()
         .map(ToTokens::into_token_stream)
@@ -211,7 +308,8 @@ impl Unwrap {
 
 struct UnwrapMethod<'a, 'b, 'c, 'd, 'e> {
     method_name: Ident,
-    receiver: &'a TokenStream,
+    /// The receiver, followed by any other parameters.
+    params: &'a TokenStream,
     return_type: &'b TokenStream,
     pattern: &'c TokenStream,
     expr: &'d TokenStream,
@@ -221,13 +319,13 @@ struct UnwrapMethod<'a, 'b, 'c, 'd, 'e> {
 impl<'a, 'b, 'c, 'd, 'e> ToTokens for UnwrapMethod<'a, 'b, 'c, 'd, 'e> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let method_name = &self.method_name;
-        let receiver = self.receiver;
+        let params = self.params;
         let return_type = self.return_type;
         let pattern = self.pattern;
         let expr = self.expr;
         let default = self.default;
         tokens.extend(quote! {
-            pub fn #method_name(#receiver) -> #return_type {
+            pub fn #method_name(#params) -> #return_type {
                 #[allow(clippy::match_like_matches_macro)]
                 match self {
                     #pattern => #expr,
@@ -0,0 +1,7 @@
+#[derive(macros::Display)]
+enum Step {
+    Up,
+    Down,
+}
+
+fn main() {}
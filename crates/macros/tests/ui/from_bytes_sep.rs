@@ -0,0 +1,5 @@
+#[derive(macros::FromBytes)]
+#[from_bytes(u8, sep = ",")]
+struct Bytes(Vec<u8>);
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(macros::FromBytes)]
+#[from_bytes(u8)]
+union Bytes {
+    value: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(macros::FromGrid)]
+enum Tile {
+    #[tile('#')]
+    Wall,
+    #[tile('.', '#')]
+    Open,
+}
+
+fn main() {}
@@ -0,0 +1,5 @@
+#[derive(macros::FromLines)]
+#[from_lines(u32, skip_from_iterator)]
+struct Lines(Vec<u32>);
+
+fn main() {}
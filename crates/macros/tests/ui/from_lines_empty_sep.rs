@@ -0,0 +1,5 @@
+#[derive(macros::FromLines)]
+#[from_lines(u32, sep = "")]
+struct Lines(Vec<u32>);
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(macros::FromLines)]
+#[from_lines(u32)]
+enum Lines {
+    Values(Vec<u32>),
+}
+
+fn main() {}
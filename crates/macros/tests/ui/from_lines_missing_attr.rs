@@ -0,0 +1,4 @@
+#[derive(macros::FromLines)]
+struct Lines(Vec<u32>);
+
+fn main() {}
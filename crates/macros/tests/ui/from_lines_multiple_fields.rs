@@ -0,0 +1,8 @@
+#[derive(macros::FromLines)]
+#[from_lines(u32)]
+struct Lines {
+    values: Vec<u32>,
+    count: usize,
+}
+
+fn main() {}
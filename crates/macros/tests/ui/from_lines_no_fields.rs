@@ -0,0 +1,5 @@
+#[derive(macros::FromLines)]
+#[from_lines(u32)]
+struct Lines {}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[derive(macros::FromLines)]
+#[from_lines(u32)]
+struct Lines {
+    #[from_lines(header)]
+    name: String,
+    #[from_lines(header)]
+    unit: String,
+    values: Vec<u32>,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(macros::FromSections)]
+struct Input {
+    #[section(each)]
+    groups: Vec<u32>,
+    total: u32,
+}
+
+fn main() {}
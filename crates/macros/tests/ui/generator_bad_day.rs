@@ -0,0 +1,6 @@
+#[macros::generator(year = 2022, day = 0)]
+fn generate(_: u64, _: usize) -> String {
+    String::new()
+}
+
+fn main() {}
@@ -0,0 +1,4 @@
+#[derive(macros::Pack)]
+enum Never {}
+
+fn main() {}
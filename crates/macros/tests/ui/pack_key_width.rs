@@ -0,0 +1,7 @@
+#[derive(macros::Pack)]
+#[bits(32)]
+struct Player {
+    score: u8,
+}
+
+fn main() {}
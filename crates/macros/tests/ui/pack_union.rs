@@ -0,0 +1,7 @@
+#[derive(macros::Pack)]
+union Bits {
+    a: u8,
+    b: bool,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[derive(macros::Pack)]
+struct Player {
+    #[bits(0)]
+    score: u8,
+}
+
+fn main() {}
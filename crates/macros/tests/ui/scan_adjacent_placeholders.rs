@@ -0,0 +1,5 @@
+#[derive(macros::Scan)]
+#[scan("{0}{1}")]
+struct Pair(u32, u32);
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(macros::Scan)]
+#[scan("move {quantity} from {from} to {dest}")]
+struct Instruction {
+    quantity: usize,
+    from: usize,
+    to: usize,
+}
+
+fn main() {}
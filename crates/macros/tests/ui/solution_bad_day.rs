@@ -0,0 +1,6 @@
+#[macros::solution(year = 2022, day = 26, part = 1)]
+fn solve(input: &str) -> usize {
+    input.len()
+}
+
+fn main() {}
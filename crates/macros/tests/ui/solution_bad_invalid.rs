@@ -0,0 +1,6 @@
+#[macros::solution(year = 2022, day = 12, part = 1, invalid("no_start"))]
+fn solve(input: &str) -> usize {
+    input.len()
+}
+
+fn main() {}
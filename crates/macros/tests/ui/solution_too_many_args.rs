@@ -0,0 +1,6 @@
+#[macros::solution(year = 2022, day = 1, part = 1)]
+fn solve(input: &str, steps: usize, rows: usize) -> usize {
+    input.len() + steps + rows
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(macros::Unwrap)]
+#[unwrap(kinds)]
+enum Register {
+    W(u32),
+    X(u32),
+}
+
+fn main() {}
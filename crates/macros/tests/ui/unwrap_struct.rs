@@ -0,0 +1,4 @@
+#[derive(macros::Unwrap)]
+struct Register(u32);
+
+fn main() {}
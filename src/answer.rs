@@ -0,0 +1,321 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! A puzzle answer in one type, whatever the solver returned.
+//!
+//! Solvers return `usize`, `isize`, `String` or a drawing of the screen;
+//! converting to [`Answer`] lets a runner print, store and compare them all
+//! the same way. Grids are compared without trailing whitespace, since
+//! stored drawings rarely agree on it.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub enum Answer {
+    /// Any integer that fits in an `i64`.
+    Int(i64),
+    /// An integer too large for [`Answer::Int`].
+    BigInt(i128),
+    /// A single line of text, like a crate order or a list of letters.
+    Str(String),
+    /// A multi-line drawing, one string per row.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// A grid from rows of text, dropping trailing whitespace from each row
+    /// and any blank rows at the end.
+    pub fn grid<S: AsRef<str>>(rows: impl IntoIterator<Item = S>) -> Self {
+        let mut rows = rows
+            .into_iter()
+            .map(|row| row.as_ref().trim_end().to_owned())
+            .collect::<Vec<_>>();
+        while rows.last().is_some_and(String::is_empty) {
+            rows.pop();
+        }
+        Self::Grid(rows)
+    }
+
+    fn int(&self) -> Option<i128> {
+        match *self {
+            Self::Int(v) => Some(v.into()),
+            Self::BigInt(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn rows(&self) -> Option<impl Iterator<Item = &str>> {
+        let Self::Grid(rows) = self else {
+            return None;
+        };
+        let len = rows.iter().rposition(|row| !row.trim_end().is_empty());
+        Some(
+            rows[..len.map_or(0, |n| n + 1)]
+                .iter()
+                .map(|row| row.trim_end()),
+        )
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Str(a), Self::Str(b)) => a == b,
+            (Self::Grid(_), Self::Grid(_)) => self.rows().unwrap().eq(other.rows().unwrap()),
+            _ => self.int().is_some() && self.int() == other.int(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(v) => write!(f, "{v}"),
+            Self::BigInt(v) => write!(f, "{v}"),
+            Self::Str(s) => write!(f, "{s}"),
+            // An empty grid is a lone newline, so it still reads back as one.
+            Self::Grid(_) if self.rows().unwrap().next().is_none() => writeln!(f),
+            Self::Grid(_) => {
+                for row in self.rows().unwrap() {
+                    writeln!(f, "{row}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Read a stored answer as [`Display`](fmt::Display) wrote it: text ending
+/// in a newline is a grid, since every row of one is written with its own;
+/// otherwise an integer written the way it would be displayed, so `007`
+/// stays text; and any other line is text.
+impl FromStr for Answer {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.ends_with('\n') {
+            return Ok(Self::grid(s.lines()));
+        }
+        match s.parse::<i128>() {
+            Ok(v) if v.to_string() == s => Ok(Self::from(v)),
+            _ => Ok(Self::Str(s.to_owned())),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),+) => {$(
+        impl From<$t> for Answer {
+            fn from(v: $t) -> Self {
+                match i64::try_from(v) {
+                    Ok(v) => Self::Int(v),
+                    Err(_) => Self::BigInt(v as i128),
+                }
+            }
+        }
+    )+};
+}
+
+from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl From<u128> for Answer {
+    /// Values beyond `i128::MAX` are kept as text.
+    fn from(v: u128) -> Self {
+        match i128::try_from(v) {
+            Ok(v) => Self::from(v),
+            Err(_) => Self::Str(v.to_string()),
+        }
+    }
+}
+
+/// Text is kept as it is; build a drawing with [`Answer::grid`] and read a
+/// stored answer with [`str::parse`].
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::Str(s.to_owned())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::Str(s)
+    }
+}
+
+/// Pairs are answered the way the puzzles ask for them: `a,b`.
+impl<A, B> From<(A, B)> for Answer
+where
+    A: Into<Answer>,
+    B: Into<Answer>,
+{
+    fn from((a, b): (A, B)) -> Self {
+        Self::Str(format!("{},{}", a.into(), b.into()))
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::fmt;
+
+    use serde::de;
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+
+    use super::Answer;
+
+    /// Integers that fit in an `i64` are written as numbers and everything
+    /// else as a string, since many formats cannot hold larger numbers; a
+    /// small [`Answer::BigInt`] is a number too, or it would read back as
+    /// text. Grids are joined by newlines.
+    impl Serialize for Answer {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self {
+                Answer::Int(v) => serializer.serialize_i64(*v),
+                Answer::BigInt(v) => match i64::try_from(*v) {
+                    Ok(v) => serializer.serialize_i64(v),
+                    Err(_) => serializer.collect_str(self),
+                },
+                Answer::Grid(_) => serializer.collect_str(self),
+                Answer::Str(s) => serializer.serialize_str(s),
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Answer {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(AnswerVisitor)
+        }
+    }
+
+    struct AnswerVisitor;
+
+    impl de::Visitor<'_> for AnswerVisitor {
+        type Value = Answer;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "an integer or a string")
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Answer, E> {
+            Ok(v.into())
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Answer, E> {
+            Ok(v.into())
+        }
+
+        fn visit_i128<E: de::Error>(self, v: i128) -> Result<Answer, E> {
+            Ok(v.into())
+        }
+
+        fn visit_u128<E: de::Error>(self, v: u128) -> Result<Answer, E> {
+            Ok(v.into())
+        }
+
+        /// Integers that fit in an `i64` are written as numbers, so a
+        /// string that reads as one was text.
+        fn visit_str<E: de::Error>(self, s: &str) -> Result<Answer, E> {
+            match s.parse() {
+                Ok(Answer::Int(_)) => Ok(Answer::Str(s.to_owned())),
+                Ok(answer) => Ok(answer),
+                Err(never) => match never {},
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Answer::from(5934usize), Answer::Int(5934));
+        assert_eq!(Answer::from(-3isize), Answer::Int(-3));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInt(u64::MAX.into()));
+        assert_eq!(Answer::from(u128::MAX), Answer::Str(u128::MAX.to_string()));
+        assert_eq!(Answer::from("CMZ"), Answer::Str("CMZ".into()));
+        assert_eq!(Answer::from("007"), Answer::Str("007".into()));
+        assert_eq!(Answer::from("12".to_owned()), Answer::Str("12".into()));
+        assert_eq!("1710623015163".parse(), Ok(Answer::Int(1710623015163)));
+        assert_eq!("-3".parse(), Ok(Answer::Int(-3)));
+        assert_eq!("007".parse(), Ok(Answer::Str("007".into())));
+        assert_eq!("+7".parse(), Ok(Answer::Str("+7".into())));
+        assert_eq!(Answer::from((3, -4)), Answer::Str("3,-4".into()));
+        assert_eq!(Answer::BigInt(12), Answer::Int(12));
+        assert_ne!(Answer::Int(12), Answer::Str("12".into()));
+    }
+
+    #[test]
+    fn test_grid() {
+        let grid = "#..#  \n####\n#..#\n\n".parse::<Answer>().unwrap();
+        assert_eq!(grid, Answer::grid(["#..#", "####", "#..#"]));
+        assert_eq!(
+            grid,
+            Answer::Grid(vec![
+                "#..#".into(),
+                "####  ".into(),
+                "#..#".into(),
+                "".into()
+            ])
+        );
+        assert_ne!(grid, Answer::grid(["#..#", "####"]));
+        assert_eq!(grid.to_string(), "#..#\n####\n#..#\n");
+        assert_eq!(grid.to_string().parse::<Answer>(), Ok(grid));
+
+        let row = Answer::grid(["##"]);
+        assert_eq!(row.to_string(), "##\n");
+        assert_eq!(row.to_string().parse::<Answer>(), Ok(row));
+        assert_eq!("##".parse(), Ok(Answer::Str("##".into())));
+
+        let empty = Answer::grid([" ", ""]);
+        assert_eq!(empty, Answer::Grid(Vec::new()));
+        assert_eq!(empty.to_string(), "\n");
+        assert_eq!(empty.to_string().parse::<Answer>(), Ok(empty));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let answers = vec![
+            Answer::Int(45),
+            Answer::BigInt(1 << 70),
+            Answer::Str("RLEZFLGE".into()),
+            Answer::Str("12".into()),
+            Answer::grid(["##.", "#.#"]),
+            Answer::grid(["##"]),
+            Answer::grid::<&str>([]),
+            Answer::BigInt(12),
+        ];
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(
+            json,
+            r###"[45,"1180591620717411303424","RLEZFLGE","12","##.\n#.#\n","##\n","\n",12]"###
+        );
+        let read = serde_json::from_str::<Vec<Answer>>(&json).unwrap();
+        assert_eq!(read, answers);
+        assert!(matches!(read[6], Answer::Grid(_)));
+        assert!(matches!(read[7], Answer::Int(12)));
+    }
+}
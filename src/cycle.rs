@@ -0,0 +1,294 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Find where a deterministic simulation starts repeating itself.
+//!
+//! Every finder treats the simulation as the sequence `x0, f(x0), f(f(x0))…`
+//! and returns a [`Cycle`]: the step where the loop is first entered and how
+//! many steps the loop takes. The finders run forever if the sequence never
+//! repeats.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::Add;
+use std::ops::Mul;
+use std::ops::Sub;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cycle {
+    /// The first step that is part of the loop.
+    pub start: usize,
+    /// The number of steps before the loop repeats.
+    pub len: usize,
+}
+
+impl Cycle {
+    pub const fn new(start: usize, len: usize) -> Self {
+        Self { start, len }
+    }
+
+    /// The earliest step with the same state as step `n`.
+    pub const fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.len
+        }
+    }
+
+    /// Extend a metric that grows by the same amount on every trip around the
+    /// loop (e.g. the tower height after `n` rocks) out to step `n`.
+    ///
+    /// `history[i]` is the metric after step `i` and must cover at least
+    /// `0..=start + len`.
+    pub fn extrapolate<T>(&self, history: &[T], n: usize) -> T
+    where
+        T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + TryFrom<usize>,
+        <T as TryFrom<usize>>::Error: Debug,
+    {
+        let end = self.start + self.len;
+        assert!(
+            history.len() > end,
+            "history has {} steps; need at least {}",
+            history.len(),
+            end + 1
+        );
+        if n < history.len() {
+            return history[n];
+        }
+        let laps = T::try_from((n - self.start) / self.len).unwrap();
+        let per_lap = history[end] - history[self.start];
+        history[self.reduce(n)] + laps * per_lap
+    }
+}
+
+/// Floyd's tortoise and hare. Keeps at most two states alive.
+pub fn floyd<S, F>(x0: S, mut f: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = f(&x0);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let next = f(&hare);
+        hare = f(&next);
+    }
+
+    let mut start = 0;
+    tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut len = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        len += 1;
+    }
+
+    Cycle::new(start, len)
+}
+
+/// Brent's algorithm. Calls `f` fewer times than [`floyd`] on average.
+pub fn brent<S, F>(x0: S, mut f: F) -> Cycle
+where
+    S: Clone + PartialEq,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut len = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == len {
+            tortoise = hare.clone();
+            power *= 2;
+            len = 0;
+        }
+        hare = f(&hare);
+        len += 1;
+    }
+
+    tortoise = x0.clone();
+    hare = x0;
+    for _ in 0..len {
+        hare = f(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle::new(start, len)
+}
+
+/// Remembers the step at which every state key was first seen.
+///
+/// Useful when the full state is too big to compare cheaply, or when only
+/// part of it matters (e.g. the top few rows of a tower and the position in
+/// the jet pattern).
+#[derive(Debug, Clone)]
+pub struct Seen<K> {
+    seen: HashMap<K, usize>,
+    step: usize,
+}
+
+impl<K: Hash + Eq> Seen<K> {
+    pub fn new() -> Self {
+        Self {
+            seen: HashMap::new(),
+            step: 0,
+        }
+    }
+
+    /// The step the next call to [`Seen::insert`] will record.
+    pub const fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Record the key for the current step and move to the next one.
+    ///
+    /// Returns the cycle if this key was already recorded at an earlier step.
+    pub fn insert(&mut self, key: K) -> Option<Cycle> {
+        let step = self.step;
+        self.step += 1;
+        match self.seen.entry(key) {
+            std::collections::hash_map::Entry::Occupied(e) => {
+                let start = *e.get();
+                Some(Cycle::new(start, step - start))
+            }
+            std::collections::hash_map::Entry::Vacant(e) => {
+                e.insert(step);
+                None
+            }
+        }
+    }
+}
+
+impl<K: Hash + Eq> Default for Seen<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Step the simulation until `key` produces a key that was seen before.
+pub fn find_by_key<S, K, F, G>(x0: S, mut f: F, mut key: G) -> Cycle
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut seen = Seen::new();
+    let mut state = x0;
+    loop {
+        if let Some(cycle) = seen.insert(key(&state)) {
+            return cycle;
+        }
+        state = f(&state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cycle;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3 ...
+    fn step(&x: &u32) -> u32 {
+        if x == 6 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    #[test]
+    fn test_floyd() {
+        assert_eq!(super::floyd(0, step), Cycle::new(3, 4));
+        assert_eq!(super::floyd(5, step), Cycle::new(0, 4));
+        assert_eq!(super::floyd(7u8, |&x| x), Cycle::new(0, 1));
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(super::brent(0, step), Cycle::new(3, 4));
+        assert_eq!(super::brent(5, step), Cycle::new(0, 4));
+        assert_eq!(super::brent(7u8, |&x| x), Cycle::new(0, 1));
+    }
+
+    #[test]
+    fn test_find_by_key() {
+        assert_eq!(super::find_by_key(0, step, |&x| x), Cycle::new(3, 4));
+        // only the parity is tracked, so the loop shows up after two steps
+        assert_eq!(super::find_by_key(0, step, |&x| x % 2), Cycle::new(0, 2));
+    }
+
+    #[test]
+    fn test_finders_agree() {
+        // x -> x^2 + 1 mod 255 has a tail before it loops
+        let f = |&x: &u64| (x * x + 1) % 255;
+        for x0 in 0..255 {
+            let cycle = super::floyd(x0, f);
+            assert_eq!(super::brent(x0, f), cycle, "x0 = {x0}");
+            assert_eq!(super::find_by_key(x0, f, |&x| x), cycle, "x0 = {x0}");
+        }
+    }
+
+    #[test]
+    fn test_reduce() {
+        let cycle = Cycle::new(3, 4);
+        let states = std::iter::successors(Some(0), |x| Some(step(x)))
+            .take(100)
+            .collect::<Vec<_>>();
+        for (n, state) in states.iter().enumerate() {
+            assert_eq!(states[cycle.reduce(n)], *state, "n = {n}");
+        }
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // running total of the states: 0, 1, 3, 6, 10, 15, 21, 24, 28, ...
+        let totals = std::iter::successors(Some(0), |x| Some(step(x)))
+            .scan(0i64, |total, x| {
+                *total += x as i64;
+                Some(*total)
+            })
+            .take(1000)
+            .collect::<Vec<_>>();
+        let cycle = Cycle::new(3, 4);
+        let history = &totals[..8];
+        for (n, &total) in totals.iter().enumerate() {
+            assert_eq!(cycle.extrapolate(history, n), total, "n = {n}");
+        }
+        // 0 + 1 + 2, then 3 + 4 + 5 + 6 for every full lap, then what's left
+        const N: usize = 1_000_000_000_001;
+        let laps = ((N - 2) / 4) as i64;
+        let rest = [0, 3, 7, 12][(N - 2) % 4];
+        assert_eq!(cycle.extrapolate(history, N), 3 + 18 * laps + rest);
+    }
+}
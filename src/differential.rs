@@ -0,0 +1,81 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Check a solution against a slow reference implementation.
+//!
+//! Days whose solutions lean on properties of the puzzle input keep a
+//! `reference` module that answers the same question the obvious way. The
+//! two are run side by side on generated inputs and the first input where
+//! they disagree is reported, along with the seed that reproduces it.
+
+use std::fmt::Debug;
+
+use crate::gen::Rng;
+
+/// Generate an input from each seed in `seeds` and compare `solution` with
+/// `reference` on it, panicking on the first disagreement.
+pub fn check<O>(
+    seeds: impl IntoIterator<Item = u64>,
+    generate: impl Fn(&mut Rng) -> String,
+    solution: impl Fn(&str) -> O,
+    reference: impl Fn(&str) -> O,
+) where
+    O: PartialEq + Debug,
+{
+    for seed in seeds {
+        let input = generate(&mut Rng::new(seed));
+        let expected = reference(&input);
+        let actual = solution(&input);
+        assert!(
+            actual == expected,
+            "seed {seed}: the solution gave {actual:?} but the reference gave {expected:?} for\n{input}",
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+
+    fn digits(rng: &mut crate::gen::Rng) -> String {
+        rng.below(1000).to_string()
+    }
+
+    fn digit_sum(s: &str) -> u32 {
+        s.chars().filter_map(|c| c.to_digit(10)).sum()
+    }
+
+    #[test]
+    fn test_agree() {
+        check(0..100, digits, digit_sum, |s| {
+            s.parse::<u32>()
+                .unwrap()
+                .to_string()
+                .bytes()
+                .map(|b| (b - b'0') as u32)
+                .sum()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "the solution gave 1 but the reference gave")]
+    fn test_disagree() {
+        check(0..100, digits, |s| s.len().min(1), |s| s.len());
+    }
+}
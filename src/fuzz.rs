@@ -0,0 +1,149 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Deterministic input mutations for checking that parsers fail with an
+//! error instead of panicking.
+
+use std::panic;
+use std::panic::AssertUnwindSafe;
+
+use crate::gen::Rng;
+
+/// Bytes spliced into inputs: digits, separators and grid cells that are
+/// valid somewhere, and a few that are valid nowhere.
+const SPLICE: &[&str] = &[
+    "0",
+    "9",
+    "-",
+    ",",
+    " ",
+    "\n",
+    "\n\n",
+    "#",
+    ".",
+    "x",
+    "=",
+    ":",
+    "[",
+    "]",
+    "é",
+    "99999999999999999999",
+];
+
+/// `rounds` variations of `input`: truncations, byte replacements,
+/// insertions, and dropped or repeated lines, plus a few degenerate inputs.
+pub fn mutations(input: &str, rounds: usize) -> Vec<String> {
+    let mut out = vec![
+        String::new(),
+        "\n".to_owned(),
+        " ".to_owned(),
+        input.trim_end().to_owned(),
+    ];
+    let boundaries = input
+        .char_indices()
+        .map(|(idx, _)| idx)
+        .chain([input.len()])
+        .collect::<Vec<_>>();
+    let lines = input.split_inclusive('\n').collect::<Vec<_>>();
+    // seeded from the input so every run tries the same mutations
+    let mut rng = Rng::new(input.len() as u64);
+    for round in 0..rounds {
+        let at = boundaries[rng.below(boundaries.len())];
+        let next = boundaries
+            .iter()
+            .copied()
+            .find(|&idx| idx > at)
+            .unwrap_or(at);
+        let splice = SPLICE[rng.below(SPLICE.len())];
+        let mutated = match round % 5 {
+            0 => input[..at].to_owned(),
+            1 => format!("{}{splice}{}", &input[..at], &input[next..]),
+            2 => format!("{}{splice}{}", &input[..at], &input[at..]),
+            3 => {
+                let drop = rng.below(lines.len());
+                lines
+                    .iter()
+                    .enumerate()
+                    .filter(|&(idx, _)| idx != drop)
+                    .map(|(_, line)| *line)
+                    .collect()
+            }
+            _ => {
+                let repeat = rng.below(lines.len());
+                lines
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(idx, line)| {
+                        std::iter::repeat_n(*line, 1 + usize::from(idx == repeat))
+                    })
+                    .collect()
+            }
+        };
+        out.push(mutated);
+    }
+    out
+}
+
+/// Run `parse` over [`mutations`] of `input`, collecting every mutation that
+/// made it panic.
+pub fn panicking_mutations<F: Fn(&str)>(input: &str, rounds: usize, parse: F) -> Vec<String> {
+    mutations(input, rounds)
+        .into_iter()
+        .filter(|mutated| panic::catch_unwind(AssertUnwindSafe(|| parse(mutated))).is_err())
+        .collect()
+}
+
+/// Panic with the first failure if any mutation of the named `inputs` makes
+/// `parse` panic.
+pub fn check(inputs: &[(&str, &str)], parse: impl Fn(&str)) {
+    for (name, input) in inputs {
+        let failures = panicking_mutations(input, 200, &parse);
+        assert!(
+            failures.is_empty(),
+            "{} mutations of {name} panicked, first:\n{:?}",
+            failures.len(),
+            failures[0],
+        );
+    }
+}
+
+/// Generate a test asserting that parsing each type never panics on
+/// mutations of the named inputs.
+#[macro_export]
+macro_rules! fuzz_parser {
+    (bytes $($t:ty),+ ; [$($input:ident),* $(,)?]) => {
+        #[test]
+        fn test_parser_does_not_panic() {
+            let inputs = [$((stringify!($input), include_str!(concat!("inputs/", stringify!($input))))),*];
+            $($crate::fuzz::check(&inputs, |s| {
+                let _ = <$t as ::core::convert::TryFrom<&[u8]>>::try_from(s.as_bytes());
+            });)+
+        }
+    };
+    ($($t:ty),+ ; [$($input:ident),* $(,)?]) => {
+        #[test]
+        fn test_parser_does_not_panic() {
+            let inputs = [$((stringify!($input), include_str!(concat!("inputs/", stringify!($input))))),*];
+            $($crate::fuzz::check(&inputs, |s| {
+                let _ = <$t as ::core::convert::TryFrom<&str>>::try_from(s);
+            });)+
+        }
+    };
+}
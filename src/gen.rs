@@ -0,0 +1,146 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Random puzzle inputs for stress tests and benchmarks.
+//!
+//! A day that supports generation exposes a [`Generator`]: given a seeded
+//! [`Rng`] and a `scale`, it writes an input that its parser accepts and its
+//! solvers can answer. `scale` is the amount of input relative to a live
+//! input, so `scale = 10` is roughly ten times the lines, cells or bits.
+//!
+//! `#[macros::generator(year = …, day = …)]` on the function registers it
+//! through [`inventory`], and [`find`] looks it up, like the solvers in
+//! [`crate::solution`].
+
+use std::ops::RangeInclusive;
+
+/// Write a puzzle input `scale` times the size of a live one.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// The generator of one day.
+#[derive(Debug)]
+pub struct Registered {
+    pub year: u16,
+    pub day: u8,
+    pub generate: Generator,
+}
+
+inventory::collect!(Registered);
+
+/// The generator for `day` of `year`, if it has one.
+pub fn find(year: u16, day: u8) -> Option<Generator> {
+    inventory::iter::<Registered>
+        .into_iter()
+        .find(|g| (g.year, g.day) == (year, day))
+        .map(|g| g.generate)
+}
+
+/// The side of a square grid with `scale` times the cells of one that is
+/// `live` cells on a side.
+pub fn side(live: usize, scale: usize) -> usize {
+    (live as f64 * (scale as f64).sqrt()).round() as usize
+}
+
+/// A small xorshift generator; the same seed always gives the same input.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    const MIX: u64 = 0x9e37_79b9_7f4a_7c15;
+
+    pub const fn new(seed: u64) -> Self {
+        // xorshift never leaves the all-zero state
+        match seed ^ Self::MIX {
+            0 => Self(Self::MIX),
+            state => Self(state),
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A value in `0..n`, or 0 when `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n.max(1) as u64) as usize
+    }
+
+    /// A value in `range`.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (lo, hi) = range.into_inner();
+        assert!(lo <= hi, "empty range {lo}..={hi}");
+        let offset = match hi.abs_diff(lo).checked_add(1) {
+            Some(span) => self.next_u64() % span,
+            None => self.next_u64(),
+        };
+        lo.wrapping_add(offset as i64)
+    }
+
+    /// True `percent` times in a hundred.
+    pub fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        let a = (0..8).map({
+            let mut rng = Rng::new(7);
+            move |_| rng.next_u64()
+        });
+        let b = (0..8).map({
+            let mut rng = Rng::new(7);
+            move |_| rng.next_u64()
+        });
+        assert!(a.eq(b));
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+        assert_ne!(Rng::new(Rng::MIX).next_u64(), 0);
+    }
+
+    #[test]
+    fn test_side() {
+        assert_eq!(side(100, 1), 100);
+        assert_eq!(side(100, 100), 1000);
+        assert_eq!(side(41, 10), 130);
+    }
+
+    #[test]
+    fn test_range() {
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            assert!((-3..=4).contains(&rng.range(-3..=4)));
+            assert!(rng.below(5) < 5);
+        }
+        assert_eq!(rng.range(9..=9), 9);
+        assert_eq!(rng.below(0), 0);
+        let full = rng.range(i64::MIN..=i64::MAX);
+        assert!((i64::MIN..=i64::MAX).contains(&full));
+    }
+}
@@ -18,8 +18,31 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+// Lets derives that expand to `::aoc::…` paths be tested in this crate.
+#[cfg(test)]
+extern crate self as aoc;
+
+pub mod answer;
+pub mod cycle;
+pub mod differential;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+pub mod gen;
+pub mod lines;
 pub mod matrix;
 pub mod nom;
+pub mod ocr;
+pub mod pack;
+pub mod params;
+#[cfg(feature = "render")]
+pub mod render;
+pub mod scan;
+pub mod sections;
+pub mod solution;
+#[cfg(feature = "proptest")]
+pub mod strategy;
+
+pub use answer::Answer;
 
 pub fn chardigit(c: u8) -> u8 {
     const ZERO: u8 = b'0';
@@ -27,16 +50,13 @@ pub fn chardigit(c: u8) -> u8 {
     c - ZERO
 }
 
-pub fn expect_empty_line<S: AsRef<str>>(s: S) -> anyhow::Result<()> {
-    let s = s.as_ref();
-    if s.is_empty() {
-        Ok(())
-    } else {
-        Err(anyhow::anyhow!("expected an empty line; got {}", s))
-    }
+/// The value of a decimal digit character, for grid parsers that must not
+/// panic on bad input.
+pub fn digit(c: char) -> Option<u8> {
+    c.to_digit(10).map(|d| d as u8)
 }
 
-pub fn parse_and_solve<'a, I, O>(s: &'a str, f: fn(I) -> O) -> Result<O, I::Error>
+pub fn parse_and_solve<'a, I, O>(s: &'a str, f: impl FnOnce(I) -> O) -> Result<O, I::Error>
 where
     I: TryFrom<&'a str>,
     O: PartialEq,
@@ -44,23 +64,248 @@ where
     s.try_into().map(f)
 }
 
+/// Like [`parse_and_solve`], for inputs that parse from (and may borrow)
+/// raw bytes.
+pub fn parse_bytes_and_solve<'a, I, O>(s: &'a [u8], f: impl FnOnce(I) -> O) -> Result<O, I::Error>
+where
+    I: TryFrom<&'a [u8]>,
+    O: PartialEq,
+{
+    s.try_into().map(f)
+}
+
+/// Like [`parse_and_solve`], returning an [`Answer`] that can be printed,
+/// stored, or compared with a stored one whatever type `f` returns.
+pub fn parse_and_answer<'a, I, O>(s: &'a str, f: impl FnOnce(I) -> O) -> Result<Answer, I::Error>
+where
+    I: TryFrom<&'a str>,
+    O: Into<Answer>,
+{
+    s.try_into().map(|input| f(input).into())
+}
+
+/// Parse `s` as the input type of `f` without solving; used by
+/// [`tests_for_problem!`] cases that expect the parse to fail.
+#[doc(hidden)]
+pub fn parse_for<S, I, O>(s: S, _: impl FnOnce(I) -> O) -> Result<I, I::Error>
+where
+    I: TryFrom<S>,
+{
+    s.try_into()
+}
+
+std::thread_local! {
+    /// Set by [`run_with_timeout`] once the solver on this thread is out of time.
+    static TIMED_OUT: std::cell::RefCell<Option<std::sync::Arc<std::sync::atomic::AtomicBool>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Whether the solver running on this thread has outlived the timeout that
+/// [`run_with_timeout`] gave it. Always `false` outside of it.
+pub fn timed_out() -> bool {
+    TIMED_OUT.with(|flag| {
+        flag.borrow()
+            .as_ref()
+            .is_some_and(|flag| flag.load(std::sync::atomic::Ordering::Relaxed))
+    })
+}
+
+/// Unwind the solver thread if it has [`timed_out`]; long searches call this
+/// in their loop so that a timed-out test stops using a core.
+pub fn abort_if_timed_out() {
+    if timed_out() {
+        panic!("timed out");
+    }
+}
+
+/// Run `f` on another thread, panicking if it has not finished after `secs`
+/// seconds. A panic inside `f` is re-raised on the calling thread.
+///
+/// A thread can not be killed, so on timeout `f` is only told to stop: it
+/// ends early if it polls [`timed_out`] or [`abort_if_timed_out`], and
+/// otherwise keeps running in the background until it finishes or the test
+/// binary exits.
+#[doc(hidden)]
+pub fn run_with_timeout<O>(secs: f64, f: impl FnOnce() -> O + Send + 'static) -> O
+where
+    O: Send + 'static,
+{
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+    use std::sync::mpsc;
+    use std::sync::mpsc::RecvTimeoutError;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    let flag = Arc::new(AtomicBool::new(false));
+    let solver_flag = Arc::clone(&flag);
+    let (tx, rx) = mpsc::channel();
+    let handle = std::thread::spawn(move || {
+        TIMED_OUT.with(|flag| *flag.borrow_mut() = Some(solver_flag));
+        let _ = tx.send(f());
+    });
+    match rx.recv_timeout(Duration::from_secs_f64(secs)) {
+        Ok(v) => v,
+        Err(RecvTimeoutError::Timeout) => {
+            flag.store(true, Ordering::Relaxed);
+            panic!("timed out after {secs}s")
+        }
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Ok(()) => unreachable!("the solver thread exits only after sending"),
+            Err(panic) => std::panic::resume_unwind(panic),
+        },
+    }
+}
+
+/// Generate one `#[test]` per case, reading `inputs/<case>` next to the
+/// calling module:
+///
+/// ```ignore
+/// tests_for_problem!(super::solve, {
+///     example => 5934,
+///     #[ignore]
+///     #[timeout(30)]
+///     live => 380758,
+///     #[input(example)]
+///     example_18_days(18) => 26,
+///     truncated => Err(ParseError::Truncated),
+/// });
+/// ```
+///
+/// Per-case attributes:
+/// - `#[timeout(secs)]` fails the test if parsing and solving take longer.
+/// - `#[input(name)]` reads `inputs/name` instead of `inputs/<case>`.
+/// - `#[params]` reads the solver's [`params::Params`] from the input's
+///   `.params` file; `#[params(name)]` reads `inputs/name.params`.
+/// - Anything else, like `#[ignore]`, is copied onto the test.
+///
+/// `case(a, b)` calls the solver as `solve(input, a, b)`, or as
+/// `solve(input, params, a, b)` when it takes parameters. `=> Err(pattern)`
+/// asserts that the input is rejected by the parser with a matching error.
+/// Prefix the solver with `bytes` for inputs parsed from `&[u8]`, and with
+/// `params` for solvers taking a parameter set, which is left at its
+/// defaults for cases without `#[params]`.
 #[macro_export]
 macro_rules! tests_for_problem {
-    ($solve:expr, {
-        $(
-            $test_case:ident => $expected:expr,
-        )*
-    }) => {
+    // Per-case attributes accumulate until the case itself is reached.
+    (@case $mode:tt $solve:tt $attrs:tt $timeout:tt $file:tt $params:tt
+        #[timeout($secs:expr)] $($rest:tt)*) => {
+        $crate::tests_for_problem!(@case $mode $solve $attrs [$secs] $file $params $($rest)*);
+    };
+    (@case $mode:tt $solve:tt $attrs:tt $timeout:tt $file:tt $params:tt
+        #[input($input:ident)] $($rest:tt)*) => {
+        $crate::tests_for_problem!(@case $mode $solve $attrs $timeout [$input] $params $($rest)*);
+    };
+    (@case $mode:tt $solve:tt $attrs:tt $timeout:tt $file:tt $params:tt
+        #[params] $($rest:tt)*) => {
+        $crate::tests_for_problem!(@case $mode $solve $attrs $timeout $file [@] $($rest)*);
+    };
+    (@case $mode:tt $solve:tt $attrs:tt $timeout:tt $file:tt $params:tt
+        #[params($name:ident)] $($rest:tt)*) => {
+        $crate::tests_for_problem!(@case $mode $solve $attrs $timeout $file [$name] $($rest)*);
+    };
+    (@case $mode:tt $solve:tt [$($attr:tt)*] $timeout:tt $file:tt $params:tt
+        #[$meta:meta] $($rest:tt)*) => {
+        $crate::tests_for_problem!(@case $mode $solve [$($attr)* #[$meta]] $timeout $file $params $($rest)*);
+    };
+    (@case $mode:tt [$solve:expr, $p0:tt] [$($attr:tt)*] $timeout:tt $file:tt $params:tt
+        $name:ident $(($($arg:expr),+ $(,)?))? => Err($err:pat) $(, $($rest:tt)*)?) => {
         paste::paste! {
-            $(
-                #[test]
-                fn [<test_ $test_case>]() {
-                    let input = include_str!(concat!("inputs/", stringify!($test_case)));
-                    let answer = ::aoc::parse_and_solve(input, $solve).unwrap();
-                    assert_eq!(answer, $expected);
-                }
-            )*
+            #[test]
+            $($attr)*
+            fn [<test_ $name>]() {
+                let input = $crate::tests_for_problem!(@input $mode $file $name);
+                let solver = $crate::tests_for_problem!(@solver [$solve] $params $file $name; $($($arg),+)?);
+                let parsed = $crate::tests_for_problem!(@timeout $timeout {
+                    $crate::parse_for(input, solver).map(|_| ())
+                });
+                assert!(
+                    matches!(parsed, Err($err)),
+                    "expected Err({}); got {:?}",
+                    stringify!($err),
+                    parsed,
+                );
+            }
         }
+        $crate::tests_for_problem!(@case $mode [$solve, $p0] [] [] [] $p0 $($($rest)*)?);
+    };
+    (@case $mode:tt [$solve:expr, $p0:tt] [$($attr:tt)*] $timeout:tt $file:tt $params:tt
+        $name:ident $(($($arg:expr),+ $(,)?))? => $expected:expr $(, $($rest:tt)*)?) => {
+        paste::paste! {
+            #[test]
+            $($attr)*
+            fn [<test_ $name>]() {
+                let input = $crate::tests_for_problem!(@input $mode $file $name);
+                let solver = $crate::tests_for_problem!(@solver [$solve] $params $file $name; $($($arg),+)?);
+                let answer = $crate::tests_for_problem!(@timeout $timeout {
+                    $crate::tests_for_problem!(@solve $mode)(input, solver).unwrap()
+                });
+                assert_eq!(answer, $expected);
+            }
+        }
+        $crate::tests_for_problem!(@case $mode [$solve, $p0] [] [] [] $p0 $($($rest)*)?);
+    };
+    (@case $mode:tt $solve:tt [] [] [] $params:tt) => {};
+
+    (@input str [] $name:ident) => {
+        include_str!(concat!("inputs/", stringify!($name)))
+    };
+    (@input str [$file:ident] $name:ident) => {
+        include_str!(concat!("inputs/", stringify!($file)))
+    };
+    (@input bytes [] $name:ident) => {
+        &include_bytes!(concat!("inputs/", stringify!($name)))[..]
+    };
+    (@input bytes [$file:ident] $name:ident) => {
+        &include_bytes!(concat!("inputs/", stringify!($file)))[..]
+    };
+    (@solve str) => {
+        $crate::parse_and_solve
+    };
+    (@solve bytes) => {
+        $crate::parse_bytes_and_solve
+    };
+    (@solver [$solve:expr] [] $file:tt $name:ident;) => {
+        $solve
+    };
+    (@solver [$solve:expr] [] $file:tt $name:ident; $($arg:expr),+) => {
+        move |input| ($solve)(input, $($arg),+)
+    };
+    (@solver [$solve:expr] $params:tt $file:tt $name:ident; $($arg:expr),*) => {{
+        let text = $crate::tests_for_problem!(@params $params $file $name);
+        let params = $crate::params::parse(text).expect("invalid .params file");
+        move |input| ($solve)(input, params $(, $arg)*)
+    }};
+    (@params [default] $file:tt $name:ident) => {
+        ""
+    };
+    (@params [@] [] $name:ident) => {
+        include_str!(concat!("inputs/", stringify!($name), ".params"))
+    };
+    (@params [@] [$file:ident] $name:ident) => {
+        include_str!(concat!("inputs/", stringify!($file), ".params"))
+    };
+    (@params [$params:ident] $file:tt $name:ident) => {
+        include_str!(concat!("inputs/", stringify!($params), ".params"))
+    };
+    (@timeout [] $body:block) => {
+        $body
+    };
+    (@timeout [$secs:expr] $body:block) => {
+        $crate::run_with_timeout(($secs) as f64, move || $body)
+    };
+
+    (bytes params $solve:expr, { $($cases:tt)* }) => {
+        $crate::tests_for_problem!(@case bytes [$solve, [default]] [] [] [] [default] $($cases)*);
+    };
+    (bytes $solve:expr, { $($cases:tt)* }) => {
+        $crate::tests_for_problem!(@case bytes [$solve, []] [] [] [] [] $($cases)*);
+    };
+    (params $solve:expr, { $($cases:tt)* }) => {
+        $crate::tests_for_problem!(@case str [$solve, [default]] [] [] [] [default] $($cases)*);
+    };
+    ($solve:expr, { $($cases:tt)* }) => {
+        $crate::tests_for_problem!(@case str [$solve, []] [] [] [] [] $($cases)*);
     };
 }
 
@@ -68,16 +313,45 @@ macro_rules! tests_for_problem {
 macro_rules! derive_FromStr_for_nom {
     ($t:ty, $f:path) => {
         impl ::core::str::FromStr for $t {
-            type Err = ::nom::error::Error<String>;
+            type Err = $crate::nom::Error;
 
             #[inline]
             fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
                 match ::nom::Finish::finish(($f)(s)) {
                     Ok((_, v)) => Ok(v),
-                    Err(::nom::error::Error { input, code }) => Err(::nom::error::Error {
-                        input: input.to_owned(),
-                        code,
-                    }),
+                    Err(e) => Err($crate::nom::Locate::locate(e, s)),
+                }
+            }
+        }
+    };
+}
+
+/// Implement `TryFrom<&'a [u8]>` for a type that borrows from its input,
+/// using a byte-oriented nom parser.
+#[macro_export]
+macro_rules! derive_TryFrom_bytes_for_nom {
+    ($t:ident<$lt:lifetime>, $f:path) => {
+        impl<$lt> ::core::convert::TryFrom<&$lt [u8]> for $t<$lt> {
+            type Error = $crate::nom::Error;
+
+            #[inline]
+            fn try_from(s: &$lt [u8]) -> ::core::result::Result<Self, Self::Error> {
+                match ::nom::Finish::finish(($f)(s)) {
+                    Ok((_, v)) => Ok(v),
+                    Err(e) => Err($crate::nom::Locate::locate(e, s)),
+                }
+            }
+        }
+    };
+    ($t:ty, $f:path) => {
+        impl ::core::convert::TryFrom<&[u8]> for $t {
+            type Error = $crate::nom::Error;
+
+            #[inline]
+            fn try_from(s: &[u8]) -> ::core::result::Result<Self, Self::Error> {
+                match ::nom::Finish::finish(($f)(s)) {
+                    Ok((_, v)) => Ok(v),
+                    Err(e) => Err($crate::nom::Locate::locate(e, s)),
                 }
             }
         }
@@ -96,3 +370,30 @@ macro_rules! ordered {
         }
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_timeout_stops_a_polling_solver() {
+        let (tx, rx) = mpsc::channel::<()>();
+        let result = std::panic::catch_unwind(|| {
+            super::run_with_timeout(0.01, move || {
+                // Dropped when the solver thread unwinds.
+                let _tx = tx;
+                loop {
+                    super::abort_if_timed_out();
+                    std::thread::yield_now();
+                }
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(
+            rx.recv_timeout(Duration::from_secs(5)),
+            Err(mpsc::RecvTimeoutError::Disconnected)
+        );
+        assert!(!super::timed_out());
+    }
+}
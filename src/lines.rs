@@ -0,0 +1,247 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Runtime support for `#[derive(macros::FromLines)]`: splitting an input
+//! into records, each numbered by the line it starts on.
+//!
+//! By default every line is a record. With a separator, such as the commas
+//! of `3,4,3,1,2`, records are split on it instead and trimmed, so a
+//! trailing newline is not part of the last one.
+//!
+//! A record that fails to parse is reported as a
+//! [`LineError`](crate::nom::LineError) holding its line; with `at_line`,
+//! errors that track their own position, like [`crate::nom::Error`], adjust
+//! it through [`AtLine`](crate::nom::AtLine) instead.
+//!
+//! `#[derive(macros::Display)]` writes the records back with [`write`].
+
+use std::fmt;
+use std::fmt::Display;
+
+/// How an input is split into records.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Format {
+    /// Split on this rather than on line endings.
+    pub sep: Option<&'static str>,
+    /// Drop records that are empty or only whitespace.
+    pub skip_blank: bool,
+    /// Drop records that start with this, ignoring leading whitespace.
+    pub comment: Option<&'static str>,
+}
+
+/// The records of an input with their 1-based line numbers, in order.
+#[derive(Debug, Clone)]
+pub struct Records<'a> {
+    input: &'a str,
+    format: Format,
+    /// Where the next record starts, or `None` once the input is used up.
+    pos: Option<usize>,
+    /// The line that `pos` is on.
+    line: usize,
+}
+
+impl<'a> Records<'a> {
+    pub fn new(input: &'a str, format: Format) -> Self {
+        let pos = match format.sep {
+            None if input.is_empty() => None,
+            _ => Some(0),
+        };
+        Self {
+            input,
+            format,
+            pos,
+            line: 1,
+        }
+    }
+
+    /// The next record, whether or not it would be skipped.
+    fn next_raw(&mut self) -> Option<(usize, &'a str)> {
+        let pos = self.pos?;
+        let rest = &self.input[pos..];
+        let (record, next) = match self.format.sep {
+            Some(sep) => match rest.find(sep) {
+                Some(end) => (&rest[..end], Some(pos + end + sep.len())),
+                None => (rest, None),
+            },
+            None => match rest.find('\n') {
+                Some(end) if pos + end + 1 == self.input.len() => (&rest[..end], None),
+                Some(end) => (&rest[..end], Some(pos + end + 1)),
+                None => (rest, None),
+            },
+        };
+
+        let (record, line) = match self.format.sep {
+            Some(_) => {
+                let leading = record.len() - record.trim_start().len();
+                let line = self.line + record[..leading].matches('\n').count();
+                (record.trim(), line)
+            }
+            None => (record.strip_suffix('\r').unwrap_or(record), self.line),
+        };
+        if let Some(next) = next {
+            self.line += self.input[pos..next].matches('\n').count();
+        }
+        self.pos = next;
+        Some((line, record))
+    }
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (line, record) = self.next_raw()?;
+            let blank = self.format.skip_blank && record.trim().is_empty();
+            let comment = self
+                .format
+                .comment
+                .is_some_and(|c| record.trim_start().starts_with(c));
+            if !blank && !comment {
+                return Some((line, record));
+            }
+        }
+    }
+}
+
+/// Write `records` the way [`Records`] reads them: one per line, or joined
+/// by the separator on a single line.
+pub fn write<'a>(
+    f: &mut fmt::Formatter<'_>,
+    sep: Option<&str>,
+    records: impl IntoIterator<Item = &'a dyn Display>,
+) -> fmt::Result {
+    let mut records = records.into_iter().peekable();
+    match sep {
+        None => records.try_for_each(|record| writeln!(f, "{record}")),
+        Some(_) if records.peek().is_none() => Ok(()),
+        Some(sep) => {
+            while let Some(record) = records.next() {
+                write!(f, "{record}")?;
+                if records.peek().is_some() {
+                    f.write_str(sep)?;
+                }
+            }
+            writeln!(f)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(s: &str, format: Format) -> Vec<(usize, &str)> {
+        Records::new(s, format).collect()
+    }
+
+    #[test]
+    fn test_lines() {
+        let format = Format::default();
+        assert_eq!(records("a\nb\r\n\nc\n", format), [
+            (1, "a"),
+            (2, "b"),
+            (3, ""),
+            (4, "c")
+        ]);
+        assert_eq!(records("", format), []);
+        assert_eq!(records("a", format), [(1, "a")]);
+    }
+
+    #[test]
+    fn test_separator() {
+        let format = Format {
+            sep: Some(","),
+            ..Format::default()
+        };
+        assert_eq!(records("3,4, 5\n", format), [(1, "3"), (1, "4"), (1, "5")]);
+        assert_eq!(records("1,\n2,3", format), [(1, "1"), (2, "2"), (2, "3")]);
+        assert_eq!(records("", format), [(1, "")]);
+    }
+
+    #[test]
+    fn test_skip() {
+        let format = Format {
+            skip_blank: true,
+            comment: Some("#"),
+            ..Format::default()
+        };
+        assert_eq!(records("# header\n1\n\n  # note\n2\n", format), [
+            (2, "1"),
+            (5, "2")
+        ]);
+    }
+
+    /// The derive itself, which needs the `macros` crate.
+    #[cfg(feature = "macros")]
+    mod derive {
+        use crate::nom::LineError;
+
+        #[derive(Debug, PartialEq, macros::FromLines)]
+        #[from_lines(u8, sep = ",")]
+        struct Fish(Vec<u8>);
+
+        #[derive(Debug, PartialEq, macros::FromLines, macros::Display)]
+        #[from_lines(u32, skip_blank, comment = "#")]
+        struct Report {
+            #[from_lines(header)]
+            name: String,
+            values: Vec<u32>,
+        }
+
+        #[test]
+        fn test_separator() {
+            assert_eq!("3,4,3\n".parse(), Ok(Fish(vec![3, 4, 3])));
+            let err = "3,\n4,x".parse::<Fish>().unwrap_err();
+            assert_eq!(err.line, 2);
+            assert_eq!(err.to_string(), "line 2: invalid digit found in string");
+        }
+
+        #[derive(Debug, PartialEq, macros::FromLines, macros::Display)]
+        #[from_lines(u8, sep = ",")]
+        struct Timers {
+            #[from_lines(header)]
+            day: u32,
+            fish: Vec<u8>,
+        }
+
+        #[test]
+        fn test_display() {
+            let timers = "18, 3,4\n".parse::<Timers>().unwrap();
+            assert_eq!(timers.to_string(), "18,3,4\n");
+            let report = "depths\n# from the sonar\n199\n\n200\n".parse::<Report>();
+            assert_eq!(report.unwrap().to_string(), "depths\n199\n200\n");
+        }
+
+        #[test]
+        fn test_header() {
+            let report = "depths\n# from the sonar\n199\n\n200\n".parse::<Report>();
+            assert_eq!(
+                report,
+                Ok(Report {
+                    name: "depths".to_owned(),
+                    values: vec![199, 200],
+                })
+            );
+            let err = "depths\n1\n\n-2\n".parse::<Report>().unwrap_err();
+            assert!(matches!(err, LineError { line: 4, .. }));
+        }
+    }
+}
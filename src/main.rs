@@ -1,30 +1,38 @@
-#![allow(clippy::write_with_newline)]
 // Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
 //
-// Permission is hereby granted, free of charge, to any person obtaining a copy of
-// this software and associated documentation files (the "Software"), to deal in
-// the Software without restriction, including without limitation the rights to
-// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
-// the Software, and to permit persons to whom the Software is furnished to do so,
-// subject to the following conditions:
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
 //
-// The above copyright notice and this permission notice shall be included in all
-// copies or substantial portions of the Software.
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
 //
 // THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
-// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
-// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
-// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
-// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
-// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
 #![cfg_attr(not(test), allow(unused))]
 
-#[cfg(any(test, feature = "y2021"))]
+#[cfg(feature = "y2021")]
 mod y2021;
 
-#[cfg(any(test, feature = "y2021"))]
+#[cfg(feature = "y2022")]
 mod y2022;
 
+use std::str::FromStr;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::bail;
+use aoc::gen::Rng;
+use aoc::solution::Solution;
+use aoc::Answer;
 use clap::Args;
 use clap::Parser;
 use clap::Subcommand;
@@ -37,70 +45,198 @@ struct Cli {
 
 #[derive(Debug, Subcommand)]
 enum Command {
-    Benchmark(InnerCommand),
+    /// Solve with registered solutions and print the answers.
     Run(InnerCommand),
+    /// Print a random puzzle input.
+    Gen(GenCommand),
+}
+
+#[derive(Debug, Args)]
+struct GenCommand {
+    year: u16,
+    day: u8,
+    /// The size of the input relative to a live one.
+    #[arg(long, default_value_t = 1)]
+    scale: usize,
+    /// The same seed always gives the same input.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
 }
 
+/// Which registered solutions to run: all of them, a year, a day or a single
+/// part, e.g. `2022 12 1` or `y2022 d12 p1`.
 #[derive(Debug, Args)]
 struct InnerCommand {
-    #[command(subcommand)]
-    year: Option<Year>,
+    /// Override one of the solution's constants, e.g. `--param steps=1000`.
+    /// May be repeated.
+    #[arg(
+        long = "param",
+        value_name = "KEY=VALUE",
+        value_parser = aoc::params::parse_pair
+    )]
+    params: Vec<(String, String)>,
+    /// The file in the day's `inputs/` directory to solve.
+    #[arg(long, default_value = "live")]
+    input: String,
+    #[arg(value_parser = prefixed::<u16>('y'))]
+    year: Option<u16>,
+    #[arg(value_parser = prefixed::<u8>('d'))]
+    day: Option<u8>,
+    #[arg(value_parser = prefixed::<u8>('p'))]
+    part: Option<u8>,
 }
 
-#[derive(Debug, Subcommand)]
-enum Year {
-    Y2021(InnerYear),
-    Y2022(InnerYear),
+/// A number, optionally written with a one-letter prefix like `d12`.
+fn prefixed<T>(prefix: char) -> impl Fn(&str) -> Result<T, T::Err> + Clone
+where
+    T: FromStr,
+{
+    move |s| s.strip_prefix(prefix).unwrap_or(s).parse()
 }
 
-#[derive(Debug, Args)]
-struct InnerYear {
-    #[command(subcommand)]
-    day: Option<Day>,
+impl InnerCommand {
+    fn solutions(&self) -> anyhow::Result<Vec<&'static Solution>> {
+        let selected = aoc::solution::all()
+            .into_iter()
+            .filter(|s| self.year.is_none_or(|year| s.year == year))
+            .filter(|s| self.day.is_none_or(|day| s.day == day))
+            .filter(|s| self.part.is_none_or(|part| s.part == part))
+            .collect::<Vec<_>>();
+        if selected.is_empty() {
+            bail!("no solution is registered for {}", self.describe());
+        }
+        Ok(selected)
+    }
+
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        parts.extend(self.year.map(|year| format!("year {year}")));
+        parts.extend(self.day.map(|day| format!("day {day}")));
+        parts.extend(self.part.map(|part| format!("part {part}")));
+        match parts.is_empty() {
+            true => "any puzzle".to_owned(),
+            false => parts.join(" "),
+        }
+    }
 }
 
-#[derive(Debug, Subcommand)]
-enum Day {
-    D01(InnerDay),
-    D02(InnerDay),
-    D03(InnerDay),
-    D04(InnerDay),
-    D05(InnerDay),
-    D06(InnerDay),
-    D07(InnerDay),
-    D08(InnerDay),
-    D09(InnerDay),
-    D10(InnerDay),
-    D11(InnerDay),
-    D12(InnerDay),
-    D13(InnerDay),
-    D14(InnerDay),
-    D15(InnerDay),
-    D16(InnerDay),
-    D17(InnerDay),
-    D18(InnerDay),
-    D19(InnerDay),
-    D20(InnerDay),
-    D21(InnerDay),
-    D22(InnerDay),
-    D23(InnerDay),
-    D24(InnerDay),
-    D25(InnerDay),
+fn label(solution: &Solution) -> String {
+    format!(
+        "{} day {:02} part {}",
+        solution.year, solution.day, solution.part
+    )
 }
 
-#[derive(Debug, Args)]
-struct InnerDay {
-    #[command(subcommand)]
-    part: Option<Part>,
+fn print_answer(label: &str, answer: &Answer, elapsed: Duration) {
+    match answer {
+        Answer::Grid(_) => print!("{label} ({elapsed:.2?}):\n{answer}"),
+        _ => println!("{label} ({elapsed:.2?}): {answer}"),
+    }
 }
 
-#[derive(Debug, Subcommand)]
-enum Part {
-    P1,
-    P2,
+/// Run each selected solution, reporting failures without stopping.
+fn run(cmd: &InnerCommand) -> anyhow::Result<()> {
+    let mut failed = 0;
+    for solution in cmd.solutions()? {
+        let start = Instant::now();
+        match solution.run_input(&cmd.input, &cmd.params) {
+            Ok(answer) => print_answer(&label(solution), &answer, start.elapsed()),
+            Err(err) => {
+                eprintln!("{}: {err:#}", label(solution));
+                failed += 1;
+            }
+        }
+    }
+    if failed > 0 {
+        bail!("{failed} solution(s) failed");
+    }
+    Ok(())
+}
+
+fn gen(cmd: &GenCommand) -> anyhow::Result<()> {
+    let Some(generator) = aoc::gen::find(cmd.year, cmd.day) else {
+        bail!(
+            "there is no input generator for {} day {}",
+            cmd.year,
+            cmd.day
+        );
+    };
+    print!("{}", generator(&mut Rng::new(cmd.seed), cmd.scale));
+    Ok(())
 }
 
-fn main() {
+fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
-    eprintln!("{cli:#?}");
+    match &cli.subcmd {
+        Command::Run(cmd) => run(cmd),
+        Command::Gen(cmd) => gen(cmd),
+    }
+}
+
+#[cfg(all(test, any(feature = "y2021", feature = "y2022")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "y2022")]
+    fn test_registry() {
+        let all = aoc::solution::all();
+        assert!(all
+            .windows(2)
+            .all(|w| (w[0].year, w[0].day, w[0].part) < (w[1].year, w[1].day, w[1].part)));
+        let solution = aoc::solution::find(2022, 12, 1).unwrap();
+        assert!(solution.file.ends_with("src/y2022/d12/part1.rs"));
+        assert_eq!(solution.run_input("example", &[]).unwrap(), Answer::Int(31));
+    }
+
+    #[test]
+    #[cfg(feature = "y2021")]
+    fn test_generators() {
+        let generate = aoc::gen::find(2021, 6).unwrap();
+        let input = generate(&mut Rng::new(1), 1);
+        assert!(aoc::solution::find(2021, 6, 1)
+            .unwrap()
+            .run(&input, &[])
+            .is_ok());
+        assert!(aoc::gen::find(2021, 1).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "y2021")]
+    fn test_params() {
+        let solution = aoc::solution::find(2021, 6, 1).unwrap();
+        let params = [("days".to_owned(), "18".to_owned())];
+        assert_eq!(
+            solution.run_input("example", &params).unwrap(),
+            Answer::Int(26)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "y2022")]
+    fn test_input_params() {
+        let solution = aoc::solution::find(2022, 9, 1).unwrap();
+        let params = [("knots".to_owned(), "10".to_owned())];
+        assert_eq!(
+            solution.run_input("example2", &params).unwrap(),
+            Answer::Int(36)
+        );
+        assert_eq!(solution.input_params("ten_knots").unwrap(), [(
+            "knots".to_owned(),
+            "10".to_owned()
+        )]);
+        assert!(solution.input_params("example").unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "y2021")]
+    fn test_select() {
+        let cli = Cli::try_parse_from(["aoc", "run", "y2021", "d06"]).unwrap();
+        let Command::Run(cmd) = cli.subcmd else {
+            panic!("expected run");
+        };
+        let parts = cmd.solutions().unwrap();
+        assert_eq!(parts.iter().map(|s| s.part).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(cmd.describe(), "year 2021 day 6");
+    }
 }
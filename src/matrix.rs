@@ -18,6 +18,9 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Write;
 use std::ops::Index;
 use std::ops::IndexMut;
 use std::ops::Range;
@@ -33,8 +36,91 @@ pub use self::iter_rel_mut::IterRelMut;
 
 pub struct MatrixCoordinate;
 
+/// A matrix position where `x` is the row and `y` is the column.
+///
+/// Prefer [`RowCol`] when the axis names matter; the two convert freely.
 pub type Position = Point2D<usize, MatrixCoordinate>;
 
+/// An explicit `(row, col)` matrix coordinate.
+///
+/// Rows grow downward from the top of the matrix and columns grow rightward
+/// from the left. Use [`RowCol::to_xy_down`] or [`RowCol::to_xy_up`] to get
+/// Cartesian `(x, y)` coordinates.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RowCol {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl RowCol {
+    pub const fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    pub const fn from_position(pos: Position) -> Self {
+        Self::new(pos.x, pos.y)
+    }
+
+    pub const fn to_position(self) -> Position {
+        point2(self.row, self.col)
+    }
+
+    /// Cartesian `(x, y)` with the origin at the top-left and y growing down.
+    pub const fn to_xy_down(self) -> (usize, usize) {
+        (self.col, self.row)
+    }
+
+    pub const fn from_xy_down(x: usize, y: usize) -> Self {
+        Self::new(y, x)
+    }
+
+    /// Cartesian `(x, y)` with the origin at the bottom-left and y growing up.
+    ///
+    /// `depth` is the number of rows in the matrix; `None` if the row does
+    /// not fit in it.
+    pub const fn to_xy_up(self, depth: usize) -> Option<(usize, usize)> {
+        match flip(self.row, depth) {
+            Some(y) => Some((self.col, y)),
+            None => None,
+        }
+    }
+
+    /// The inverse of [`RowCol::to_xy_up`]; `None` if `y` does not fit in
+    /// `depth` rows.
+    pub const fn from_xy_up(x: usize, y: usize, depth: usize) -> Option<Self> {
+        match flip(y, depth) {
+            Some(row) => Some(Self::new(row, x)),
+            None => None,
+        }
+    }
+}
+
+/// `n` counted from the other end of `0..depth`.
+const fn flip(n: usize, depth: usize) -> Option<usize> {
+    match depth.checked_sub(n) {
+        Some(rest) if rest > 0 => Some(rest - 1),
+        _ => None,
+    }
+}
+
+impl From<Position> for RowCol {
+    fn from(pos: Position) -> Self {
+        Self::from_position(pos)
+    }
+}
+
+impl From<RowCol> for Position {
+    fn from(rc: RowCol) -> Self {
+        rc.to_position()
+    }
+}
+
+impl From<(usize, usize)> for RowCol {
+    fn from((row, col): (usize, usize)) -> Self {
+        Self::new(row, col)
+    }
+}
+
 #[macro_export]
 macro_rules! Position {
     ($idx:expr, $width:expr) => {{
@@ -74,6 +160,25 @@ impl RelativePosition {
         Self::BottomRight,
     ];
 
+    /// The change in row when stepping in this direction.
+    pub const fn drow(&self) -> isize {
+        match self {
+            Self::TopLeft | Self::TopCenter | Self::TopRight => -1,
+            Self::MiddleLeft | Self::MiddleCenter | Self::MiddleRight => 0,
+            Self::BottomLeft | Self::BottomCenter | Self::BottomRight => 1,
+        }
+    }
+
+    /// The change in column when stepping in this direction.
+    pub const fn dcol(&self) -> isize {
+        match self {
+            Self::TopLeft | Self::MiddleLeft | Self::BottomLeft => -1,
+            Self::TopCenter | Self::MiddleCenter | Self::BottomCenter => 0,
+            Self::TopRight | Self::MiddleRight | Self::BottomRight => 1,
+        }
+    }
+
+    #[deprecated(note = "the tuple is `(row, col)`, not `(x, y)`; use `drow` and `dcol`")]
     pub const fn delta(&self) -> (isize, isize) {
         match self {
             RelativePosition::TopLeft => (-1, -1),
@@ -95,13 +200,184 @@ pub struct Matrix<Tile> {
     tiles: Box<[Tile]>,
 }
 
+/// Why a set of rows could not form a [`Matrix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeError {
+    /// There were no rows, or the first row was empty.
+    Empty,
+    /// `row` (0-based, but 1-based in the message) had `len` tiles where
+    /// every earlier row had `width`.
+    Ragged {
+        row: usize,
+        len: usize,
+        width: usize,
+    },
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the grid has no tiles"),
+            Self::Ragged { row, len, width } => {
+                write!(f, "row {} has {len} tiles; expected {width}", row + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+/// Why a text grid could not be parsed into a [`Matrix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    Shape(ShapeError),
+    /// The character at `row`, `col` (both 0-based, but 1-based in the
+    /// message) is not a known tile.
+    Cell {
+        row: usize,
+        col: usize,
+        found: char,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Shape(e) => e.fmt(f),
+            Self::Cell { row, col, found } => {
+                write!(
+                    f,
+                    "unexpected {found:?} at row {}, column {}",
+                    row + 1,
+                    col + 1
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl From<ShapeError> for GridError {
+    fn from(value: ShapeError) -> Self {
+        Self::Shape(value)
+    }
+}
+
+/// A character that is not one of a [`GridTile`]'s tiles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileError {
+    pub found: char,
+}
+
+impl fmt::Display for TileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unexpected {:?}", self.found)
+    }
+}
+
+impl std::error::Error for TileError {}
+
+/// A tile that is written as a single character. `#[derive(macros::FromGrid)]`
+/// implements it for enums with a `#[tile('…')]` on each variant.
+pub trait GridTile: Sized {
+    fn from_char(c: char) -> Option<Self>;
+
+    fn to_char(&self) -> char;
+}
+
+/// Where marker characters (the `S` and `E` of a heightmap) appeared in a
+/// grid, from [`Matrix::try_from_marked_grid`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Markers(BTreeMap<char, Vec<Position>>);
+
+impl Markers {
+    /// Every position marked with `marker`, in reading order.
+    pub fn all(&self, marker: char) -> &[Position] {
+        self.0.get(&marker).map_or(&[], Vec::as_slice)
+    }
+}
+
 impl<Tile> Matrix<Tile> {
+    /// Build a matrix from rows, checking that there is at least one tile and
+    /// that every row is as wide as the first. Prefer this to `collect()`
+    /// when the rows come from puzzle input.
+    pub fn try_from_rows<I, R>(rows: I) -> Result<Self, ShapeError>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = Tile>,
+    {
+        let mut width = 0;
+        let mut tiles = Vec::new();
+        for (row, line) in rows.into_iter().enumerate() {
+            let before = tiles.len();
+            tiles.extend(line);
+            let len = tiles.len() - before;
+            if row == 0 {
+                width = len;
+            }
+            if width == 0 {
+                return Err(ShapeError::Empty);
+            }
+            if len != width {
+                return Err(ShapeError::Ragged { row, len, width });
+            }
+        }
+        if tiles.is_empty() {
+            return Err(ShapeError::Empty);
+        }
+        Ok(Self {
+            width,
+            tiles: tiles.into_boxed_slice(),
+        })
+    }
+
+    /// Parse a grid with one tile per character and one row per line.
+    /// `tile` returns `None` for characters that are not tiles.
+    pub fn try_from_grid(
+        s: &str,
+        tile: impl FnMut(char) -> Option<Tile>,
+    ) -> Result<Self, GridError> {
+        Self::try_from_marked_grid(s, &[], tile).map(|(matrix, _)| matrix)
+    }
+
+    /// Like [`Matrix::try_from_grid`], also recording where each of the
+    /// `markers` characters appears. `tile` still decides which tile a
+    /// marker stands for.
+    pub fn try_from_marked_grid(
+        s: &str,
+        markers: &[char],
+        mut tile: impl FnMut(char) -> Option<Tile>,
+    ) -> Result<(Self, Markers), GridError> {
+        let mut found_markers = Markers::default();
+        let rows = s
+            .lines()
+            .enumerate()
+            .map(|(row, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(col, found)| {
+                        if markers.contains(&found) {
+                            found_markers
+                                .0
+                                .entry(found)
+                                .or_default()
+                                .push(point2(row, col));
+                        }
+                        tile(found).ok_or(GridError::Cell { row, col, found })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((Self::try_from_rows(rows)?, found_markers))
+    }
+
     pub fn new(width: usize, tiles: impl IntoIterator<Item = Tile>) -> Self {
         let this = Self {
             width,
             tiles: tiles.into_iter().collect(),
         };
-        assert!(this.tiles.len() % this.width == 0);
+        assert!(this.tiles.len().is_multiple_of(this.width));
         this
     }
 
@@ -121,14 +397,26 @@ impl<Tile> Matrix<Tile> {
         self.tiles.len() / self.width
     }
 
-    pub const fn range_x(&self) -> Range<usize> {
+    /// Valid row indices, `0..depth()`.
+    pub const fn rows(&self) -> Range<usize> {
         0..self.depth()
     }
 
-    pub const fn range_y(&self) -> Range<usize> {
+    /// Valid column indices, `0..width()`.
+    pub const fn cols(&self) -> Range<usize> {
         0..self.width
     }
 
+    #[deprecated(note = "`x` is the row axis here; use `rows`")]
+    pub const fn range_x(&self) -> Range<usize> {
+        self.rows()
+    }
+
+    #[deprecated(note = "`y` is the column axis here; use `cols`")]
+    pub const fn range_y(&self) -> Range<usize> {
+        self.cols()
+    }
+
     pub const fn idx_to_pos(&self, idx: usize) -> Position {
         debug_assert!(idx < self.tiles.len());
         Position!(idx, self.width)
@@ -140,20 +428,38 @@ impl<Tile> Matrix<Tile> {
         v
     }
 
+    pub const fn idx_to_rowcol(&self, idx: usize) -> RowCol {
+        RowCol::from_position(self.idx_to_pos(idx))
+    }
+
+    pub const fn rowcol_to_idx(&self, rc: RowCol) -> usize {
+        self.pos_to_idx(rc.to_position())
+    }
+
+    pub const fn contains(&self, rc: RowCol) -> bool {
+        rc.row < self.depth() && rc.col < self.width()
+    }
+
     pub const fn relative_pos(&self, pos: Position, rel: RelativePosition) -> Option<Position> {
         if self.is_empty() {
             return None;
         }
-        let (dx, dy) = rel.delta();
-        let x = pos.x as isize + dx;
-        let y = pos.y as isize + dy;
-        if 0 <= x && x < self.depth() as isize && 0 <= y && y < self.width() as isize {
-            Some(point2(x as usize, y as usize))
+        let row = pos.x as isize + rel.drow();
+        let col = pos.y as isize + rel.dcol();
+        if 0 <= row && row < self.depth() as isize && 0 <= col && col < self.width() as isize {
+            Some(point2(row as usize, col as usize))
         } else {
             None
         }
     }
 
+    pub const fn relative_rowcol(&self, rc: RowCol, rel: RelativePosition) -> Option<RowCol> {
+        match self.relative_pos(rc.to_position(), rel) {
+            Some(pos) => Some(RowCol::from_position(pos)),
+            None => None,
+        }
+    }
+
     pub const fn get(&self, pos: Position) -> Option<&Tile> {
         if pos.x < self.depth() && pos.y < self.width() {
             Some(&self.tiles[self.pos_to_idx(pos)])
@@ -249,7 +555,7 @@ impl<Tile> Matrix<Tile> {
         pos: Position,
         reliter: I,
     ) -> IterRel<'_, Tile, I::IntoIter> {
-        IterRel::new(self, pos, reliter.into_iter())
+        IterRel::new(self, pos, reliter)
     }
 
     pub fn iter_rel_mut<I: IntoIterator<Item = RelativePosition>>(
@@ -272,6 +578,32 @@ impl<Tile> Matrix<Tile> {
     }
 }
 
+impl<Tile: GridTile> Matrix<Tile> {
+    /// Parse a grid of [`GridTile`] characters.
+    pub fn from_grid(s: &str) -> Result<Self, GridError> {
+        Self::try_from_grid(s, Tile::from_char)
+    }
+
+    /// Like [`Matrix::from_grid`], also recording where each of the
+    /// `markers` characters appears.
+    pub fn from_marked_grid(s: &str, markers: &[char]) -> Result<(Self, Markers), GridError> {
+        Self::try_from_marked_grid(s, markers, Tile::from_char)
+    }
+}
+
+/// One line per row, each ending with a newline.
+impl<Tile: GridTile> fmt::Display for Matrix<Tile> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.tiles.chunks(self.width.max(1)) {
+            for tile in row {
+                f.write_char(tile.to_char())?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
 impl<Tile> Index<Position> for Matrix<Tile> {
     type Output = Tile;
 
@@ -286,6 +618,20 @@ impl<Tile> IndexMut<Position> for Matrix<Tile> {
     }
 }
 
+impl<Tile> Index<RowCol> for Matrix<Tile> {
+    type Output = Tile;
+
+    fn index(&self, rc: RowCol) -> &Self::Output {
+        &self[rc.to_position()]
+    }
+}
+
+impl<Tile> IndexMut<RowCol> for Matrix<Tile> {
+    fn index_mut(&mut self, rc: RowCol) -> &mut Self::Output {
+        &mut self[rc.to_position()]
+    }
+}
+
 impl<Tile> Index<usize> for Matrix<Tile> {
     type Output = Tile;
 
@@ -362,6 +708,84 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
+mod par_iter {
+    use rayon::prelude::*;
+
+    use super::Matrix;
+    use super::Position;
+
+    impl<Tile: Sync> Matrix<Tile> {
+        pub fn par_iter(&self) -> impl IndexedParallelIterator<Item = (Position, &Tile)> + '_ {
+            let width = self.width;
+            self.tiles
+                .par_iter()
+                .enumerate()
+                .map(move |(idx, v)| (Position!(idx, width), v))
+        }
+
+        pub fn par_rows(
+            &self,
+        ) -> impl IndexedParallelIterator<Item = impl Iterator<Item = (Position, &Tile)> + Send + '_> + '_
+        {
+            let width = self.width;
+            self.tiles
+                .par_chunks(width.max(1))
+                .enumerate()
+                .map(move |(row, tiles)| {
+                    tiles
+                        .iter()
+                        .enumerate()
+                        .map(move |(col, v)| (Position::new(row, col), v))
+                })
+        }
+
+        /// Like [`Matrix::map`] but `func` also sees the position and the
+        /// matrix is borrowed, so `func` can look at neighbouring tiles.
+        pub fn par_map_pos<O, F>(&self, func: F) -> Matrix<O>
+        where
+            O: Send,
+            F: Fn(Position, &Tile) -> O + Sync + Send,
+        {
+            Matrix {
+                width: self.width,
+                tiles: self
+                    .par_iter()
+                    .map(|(pos, tile)| func(pos, tile))
+                    .collect::<Vec<_>>()
+                    .into_boxed_slice(),
+            }
+        }
+    }
+
+    impl<Tile: Send> Matrix<Tile> {
+        pub fn par_iter_mut(
+            &mut self,
+        ) -> impl IndexedParallelIterator<Item = (Position, &mut Tile)> + '_ {
+            let width = self.width;
+            self.tiles
+                .par_iter_mut()
+                .enumerate()
+                .map(move |(idx, v)| (Position!(idx, width), v))
+        }
+
+        pub fn par_map<O, F>(self, func: F) -> Matrix<O>
+        where
+            O: Send,
+            F: Fn(Tile) -> O + Sync + Send,
+        {
+            Matrix {
+                width: self.width,
+                tiles: Vec::from(self.tiles)
+                    .into_par_iter()
+                    .map(func)
+                    .collect::<Vec<_>>()
+                    .into_boxed_slice(),
+            }
+        }
+    }
+}
+
 mod iter_rel {
     use super::Matrix;
     use super::Position;
@@ -624,7 +1048,7 @@ mod into_iter {
     fn into_uninit_slice<T>(boxed: Box<[T]>) -> Box<[MaybeUninit<T>]> {
         let len = boxed.len();
         let ptr = Box::into_raw(boxed) as *mut MaybeUninit<T>;
-        unsafe { Box::from_raw(std::slice::from_raw_parts_mut(ptr, len)) }
+        unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)) }
     }
 
     unsafe fn from_uninit_slice<T>(slice: &[MaybeUninit<T>]) -> &[T] {
@@ -858,9 +1282,12 @@ mod tests {
     use euclid::point2;
     use itertools::Itertools;
 
+    use super::GridError;
     use super::Matrix;
     use super::Position;
     use super::RelativePosition;
+    use super::RowCol;
+    use super::ShapeError;
 
     fn collect_tiles<'a, V: Copy + 'a>(
         iter: impl Iterator<Item = (Position, &'a V)> + 'a,
@@ -991,6 +1418,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_range() {
         let matrix = matrix();
 
@@ -1002,4 +1430,187 @@ mod tests {
         assert_eq!(bounds.start_bound(), Bound::Included(&0));
         assert_eq!(bounds.end_bound(), Bound::Excluded(&3));
     }
+
+    #[test]
+    fn test_rows_cols() {
+        let matrix = matrix();
+        assert_eq!(matrix.rows(), 0..5);
+        assert_eq!(matrix.cols(), 0..3);
+        assert!(matrix.contains(RowCol::new(4, 2)));
+        assert!(!matrix.contains(RowCol::new(2, 4)));
+    }
+
+    #[test]
+    fn test_try_from_rows() {
+        let matrix = Matrix::try_from_rows(["ab".chars(), "cd".chars()]).unwrap();
+        assert_eq!(matrix.rows(), 0..2);
+        assert_eq!(matrix[RowCol::new(1, 0)], 'c');
+
+        let ragged = Matrix::try_from_rows(["ab".chars(), "cde".chars()]);
+        assert_eq!(
+            ragged,
+            Err(ShapeError::Ragged {
+                row: 1,
+                len: 3,
+                width: 2
+            })
+        );
+        let empty = Matrix::<char>::try_from_rows(Vec::<Vec<char>>::new());
+        assert_eq!(empty, Err(ShapeError::Empty));
+        let empty = Matrix::try_from_rows(["".chars()]);
+        assert_eq!(empty, Err(ShapeError::Empty));
+    }
+
+    #[test]
+    fn test_try_from_grid() {
+        let digit = |c: char| c.to_digit(10);
+        let matrix = Matrix::try_from_grid("12\n34\n", digit).unwrap();
+        assert_eq!(matrix[RowCol::new(1, 1)], 4);
+        assert_eq!(
+            Matrix::try_from_grid("12\n3x\n", digit),
+            Err(GridError::Cell {
+                row: 1,
+                col: 1,
+                found: 'x'
+            })
+        );
+        let err = Matrix::try_from_grid("12\n3\n", digit).unwrap_err();
+        assert_eq!(err.to_string(), "row 2 has 1 tiles; expected 2");
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Cell {
+        Open,
+        Wall,
+    }
+
+    impl super::GridTile for Cell {
+        fn from_char(c: char) -> Option<Self> {
+            match c {
+                '.' | 'S' | 'E' => Some(Self::Open),
+                '#' => Some(Self::Wall),
+                _ => None,
+            }
+        }
+
+        fn to_char(&self) -> char {
+            match self {
+                Self::Open => '.',
+                Self::Wall => '#',
+            }
+        }
+    }
+
+    #[test]
+    fn test_marked_grid() {
+        let (matrix, markers) =
+            Matrix::<Cell>::from_marked_grid("S.#\n#.E\n", &['S', 'E', 'X']).unwrap();
+        assert_eq!(matrix[RowCol::new(1, 0)], Cell::Wall);
+        assert_eq!(markers.all('S'), [point2(0, 0)]);
+        assert_eq!(markers.all('E'), [point2(1, 2)]);
+        assert_eq!(markers.all('X'), []);
+        assert_eq!(matrix.to_string(), "..#\n#..\n");
+        assert_eq!(
+            Matrix::<Cell>::from_grid("..\n.?\n"),
+            Err(GridError::Cell {
+                row: 1,
+                col: 1,
+                found: '?'
+            })
+        );
+    }
+
+    #[test]
+    fn test_rowcol() {
+        let matrix = matrix();
+
+        let rc = matrix.idx_to_rowcol(5);
+        assert_eq!(rc, RowCol::new(1, 2));
+        assert_eq!(matrix[rc], 5);
+        assert_eq!(matrix.rowcol_to_idx(rc), 5);
+        assert_eq!(Position::from(rc), point2(1, 2));
+        assert_eq!(RowCol::from(point2(1, 2)), rc);
+
+        assert_eq!(
+            matrix.relative_rowcol(rc, RelativePosition::BottomLeft),
+            Some(RowCol::new(2, 1))
+        );
+        assert_eq!(
+            matrix.relative_rowcol(rc, RelativePosition::MiddleRight),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cartesian() {
+        let rc = RowCol::new(1, 2);
+        assert_eq!(rc.to_xy_down(), (2, 1));
+        assert_eq!(RowCol::from_xy_down(2, 1), rc);
+        assert_eq!(rc.to_xy_up(5), Some((2, 3)));
+        assert_eq!(RowCol::from_xy_up(2, 3, 5), Some(rc));
+        assert_eq!(rc.to_xy_up(1), None);
+        assert_eq!(RowCol::from_xy_up(2, 5, 5), None);
+    }
+
+    #[test]
+    fn test_relative_delta() {
+        for rel in RelativePosition::ALL {
+            #[allow(deprecated)]
+            let (drow, dcol) = rel.delta();
+            assert_eq!((rel.drow(), rel.dcol()), (drow, dcol));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_iter() {
+        use rayon::prelude::*;
+
+        let mut matrix = matrix();
+
+        let seq = matrix.iter().collect_vec();
+        let par = matrix.par_iter().collect::<Vec<_>>();
+        assert_eq!(seq, par);
+
+        let rows = matrix
+            .par_rows()
+            .map(|row| row.collect_vec())
+            .collect::<Vec<_>>();
+        for (row, par) in rows.into_iter().enumerate() {
+            assert_eq!(matrix.iter_row(row).collect_vec(), par);
+        }
+
+        matrix
+            .par_iter_mut()
+            .for_each(|(pos, v)| *v += (pos.x * 10 + pos.y) as u8);
+        let mut expected = self::matrix();
+        for (pos, v) in expected.iter_mut() {
+            *v += (pos.x * 10 + pos.y) as u8;
+        }
+        assert_eq!(matrix, expected);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_map() {
+        let matrix = matrix();
+
+        let neighbours = |pos: Position| -> usize {
+            matrix
+                .iter_rel(pos, RelativePosition::ALL)
+                .map(|(_, &v)| v as usize)
+                .sum()
+        };
+        let par = matrix.par_map_pos(|pos, _| neighbours(pos));
+        let seq = Matrix::new(
+            matrix.width(),
+            matrix.iter().map(|(pos, _)| neighbours(pos)),
+        );
+        assert_eq!(par, seq);
+
+        assert_eq!(
+            matrix.clone().par_map(|v| v as u32 * 3),
+            matrix.map(|v| v as u32 * 3)
+        );
+    }
 }
@@ -0,0 +1,245 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Byte-slice counterparts of the `aoc::nom` combinators.
+//!
+//! These work on `&[u8]` so parsed values can borrow from the input without
+//! UTF-8 validation up front; pair them with
+//! [`crate::derive_TryFrom_bytes_for_nom!`].
+
+use std::ops::RangeInclusive;
+use std::str::Utf8Error;
+
+use nom::character::complete::line_ending;
+use nom::character::complete::not_line_ending;
+use nom::combinator::map;
+use nom::combinator::map_res;
+use nom::error::ErrorKind;
+use nom::error::FromExternalError;
+use nom::error::ParseError;
+use nom::multi::separated_list1;
+use nom::sequence::pair;
+use nom::sequence::preceded;
+use nom::sequence::separated_pair;
+use nom::sequence::tuple;
+use nom::Parser;
+
+use super::IResult;
+use super::Integer;
+use super::TagError;
+
+/// Parse a decimal integer from the start of `s` without going through
+/// `str::parse`. Returns the value and the number of bytes consumed, or
+/// `None` if there are no digits or the value overflows `T`.
+#[inline]
+pub fn atoi<T: Integer>(s: &[u8]) -> Option<(T, usize)> {
+    scan(s).ok()
+}
+
+#[inline]
+fn scan<T: Integer>(s: &[u8]) -> Result<(T, usize), ErrorKind> {
+    let (negative, start) = match s.first() {
+        Some(b'-') if T::SIGNED => (true, 1),
+        Some(b'+') if T::SIGNED => (false, 1),
+        _ => (false, 0),
+    };
+    let mut value = T::ZERO;
+    let mut end = start;
+    while let Some(&b) = s.get(end) {
+        if !b.is_ascii_digit() {
+            break;
+        }
+        value = value
+            .push_digit(b - b'0', negative)
+            .ok_or(ErrorKind::TooLarge)?;
+        end += 1;
+    }
+    if end > start {
+        Ok((value, end))
+    } else {
+        Err(ErrorKind::Digit)
+    }
+}
+
+/// A decimal integer of any primitive type, parsed with [`atoi`].
+pub fn int<'a, T, E>(s: &'a [u8]) -> IResult<&'a [u8], T, E>
+where
+    T: Integer,
+    E: ParseError<&'a [u8]>,
+{
+    match scan(s) {
+        Ok((value, len)) => Ok((&s[len..], value)),
+        Err(kind) => Err(nom::Err::Error(E::from_error_kind(s, kind))),
+    }
+}
+
+/// Match the literal `tag` against the bytes of the input.
+pub fn tag<'a, E>(tag: &'static str) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], &'a [u8], E>
+where
+    E: TagError<&'a [u8]>,
+{
+    move |input: &'a [u8]| {
+        if input.starts_with(tag.as_bytes()) {
+            let (matched, rest) = input.split_at(tag.len());
+            Ok((rest, matched))
+        } else {
+            Err(nom::Err::Error(E::from_tag(input, tag)))
+        }
+    }
+}
+
+/// The rest of the current line as a borrowed `&str`.
+pub fn line_str<'a, E>(s: &'a [u8]) -> IResult<&'a [u8], &'a str, E>
+where
+    E: ParseError<&'a [u8]> + FromExternalError<&'a [u8], Utf8Error>,
+{
+    map_res(not_line_ending, std::str::from_utf8)(s)
+}
+
+/// One or more integers separated by `sep`.
+pub fn separated_ints<'a, T, E>(
+    sep: &'static str,
+) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], Vec<T>, E>
+where
+    T: Integer,
+    E: TagError<&'a [u8]>,
+{
+    separated_list1(tag(sep), int)
+}
+
+/// Two integers joined by `sep` as an inclusive range.
+pub fn range_inclusive<'a, T, E>(
+    sep: &'static str,
+) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], RangeInclusive<T>, E>
+where
+    T: Integer,
+    E: TagError<&'a [u8]>,
+{
+    map(separated_pair(int, tag(sep), int), |(start, end)| {
+        start..=end
+    })
+}
+
+/// A pair of integers joined by `sep`.
+pub fn coord2<'a, T, E>(sep: &'static str) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], (T, T), E>
+where
+    T: Integer,
+    E: TagError<&'a [u8]>,
+{
+    separated_pair(int, tag(sep), int)
+}
+
+/// A triple of integers joined by `sep`.
+#[allow(clippy::type_complexity)]
+pub fn coord3<'a, T, E>(
+    sep: &'static str,
+) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], (T, T, T), E>
+where
+    T: Integer,
+    E: TagError<&'a [u8]>,
+{
+    tuple((int, preceded(tag(sep), int), preceded(tag(sep), int)))
+}
+
+/// Blocks of input separated by a single blank line.
+pub fn sections<'a, O, E, F>(section: F) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], Vec<O>, E>
+where
+    E: ParseError<&'a [u8]>,
+    F: Parser<&'a [u8], O, E>,
+{
+    separated_list1(pair(line_ending, line_ending), section)
+}
+
+/// `value` preceded by a literal `label`.
+pub fn labelled<'a, O, E, F>(
+    label: &'static str,
+    value: F,
+) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], O, E>
+where
+    E: TagError<&'a [u8]>,
+    F: Parser<&'a [u8], O, E>,
+{
+    preceded(tag(label), value)
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::Finish;
+
+    use super::*;
+    use crate::nom::Locate;
+    use crate::nom::VerboseError;
+
+    type R<'a, O> = IResult<&'a [u8], O, VerboseError<&'a [u8]>>;
+
+    #[test]
+    fn test_atoi() {
+        assert_eq!(atoi::<u32>(b"1234x"), Some((1234, 4)));
+        assert_eq!(atoi::<i8>(b"-128"), Some((-128, 4)));
+        assert_eq!(atoi::<i8>(b"128"), None);
+        assert_eq!(atoi::<u8>(b"-1"), None);
+        assert_eq!(atoi::<i64>(b"+"), None);
+        assert_eq!(atoi::<usize>(b""), None);
+    }
+
+    #[test]
+    fn test_int() {
+        let r: R<u8> = int(b"300");
+        let err = r.finish().unwrap_err().locate(&b"300"[..]);
+        assert_eq!(err.expected(), "a number that fits its type");
+        let r: R<i32> = int(b"x");
+        assert!(r.is_err());
+        let r: R<i32> = int(b"-17 ");
+        assert_eq!(r, Ok((&b" "[..], -17)));
+    }
+
+    #[test]
+    fn test_combinators() {
+        let r: R<Vec<u16>> = separated_ints(",")(b"3,4,3,1,2");
+        assert_eq!(r, Ok((&b""[..], vec![3, 4, 3, 1, 2])));
+        let r: R<(RangeInclusive<u8>, RangeInclusive<u8>)> =
+            separated_pair(range_inclusive("-"), tag(","), range_inclusive("-"))(b"2-4,6-8");
+        assert_eq!(r, Ok((&b""[..], (2..=4, 6..=8))));
+        let r: R<(i32, i32, i32)> = labelled("at ", coord3(","))(b"at 1,-2,3");
+        assert_eq!(r, Ok((&b""[..], (1, -2, 3))));
+        let r: R<(usize, usize)> = coord2(" -> ")(b"0 -> 9");
+        assert_eq!(r, Ok((&b""[..], (0, 9))));
+        let r: R<Vec<Vec<u32>>> = sections(separated_list1(line_ending, int))(b"1\n2\n\n3");
+        assert_eq!(r, Ok((&b""[..], vec![vec![1, 2], vec![3]])));
+    }
+
+    #[test]
+    fn test_line_str() {
+        let r: R<&str> = line_str("a/b.c\nd".as_bytes());
+        assert_eq!(r, Ok((&b"\nd"[..], "a/b.c")));
+        let r: R<&str> = line_str(b"\xff\n");
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_locate() {
+        let input = &b"1,2\n3;4"[..];
+        let r: R<((u8, u8), (u8, u8))> =
+            pair(coord2(","), preceded(line_ending, coord2(",")))(input);
+        let err = r.finish().unwrap_err().locate(input);
+        assert_eq!((err.line(), err.column()), (2, 2));
+        assert!(err.to_string().contains("2 | 3;4"));
+    }
+}
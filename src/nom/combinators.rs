@@ -0,0 +1,236 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::num::ParseIntError;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use nom::character::complete::digit1;
+use nom::character::complete::line_ending;
+use nom::character::complete::one_of;
+use nom::combinator::map;
+use nom::combinator::map_res;
+use nom::combinator::opt;
+use nom::combinator::recognize;
+use nom::error::FromExternalError;
+use nom::error::ParseError;
+use nom::multi::separated_list1;
+use nom::sequence::pair;
+use nom::sequence::preceded;
+use nom::sequence::separated_pair;
+use nom::sequence::tuple;
+use nom::Parser;
+
+use super::tag;
+use super::IResult;
+use super::TagError;
+
+/// Primitive integers that [`int`] knows how to parse.
+pub trait Integer: FromStr<Err = ParseIntError> + Copy {
+    /// Whether a leading `-` or `+` is accepted.
+    const SIGNED: bool;
+
+    const ZERO: Self;
+
+    /// `self * 10 ± digit`, or `None` on overflow. Negative numbers are
+    /// accumulated downwards so that `MIN` is reachable.
+    fn push_digit(self, digit: u8, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($signed:literal: $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const SIGNED: bool = $signed;
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn push_digit(self, digit: u8, negative: bool) -> Option<Self> {
+                    let shifted = self.checked_mul(10)?;
+                    if negative {
+                        shifted.checked_sub(digit as Self)
+                    } else {
+                        shifted.checked_add(digit as Self)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(true: i8, i16, i32, i64, i128, isize);
+impl_integer!(false: u8, u16, u32, u64, u128, usize);
+
+/// A decimal integer of any primitive type; signed types take an optional
+/// sign. Out-of-range values fail with the [`ParseIntError`].
+pub fn int<'a, T, E>(s: &'a str) -> IResult<&'a str, T, E>
+where
+    T: Integer,
+    E: ParseError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+{
+    let sign = |s| {
+        if T::SIGNED {
+            opt(one_of("+-"))(s)
+        } else {
+            Ok((s, None))
+        }
+    };
+    map_res(recognize(pair(sign, digit1)), str::parse)(s)
+}
+
+/// One or more integers separated by `sep`, e.g. `3,4,3,1,2` with `","`.
+pub fn separated_ints<'a, T, E>(
+    sep: &'static str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>, E>
+where
+    T: Integer,
+    E: TagError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+{
+    separated_list1(tag(sep), int)
+}
+
+/// Two integers joined by `sep` as an inclusive range, e.g. `2-4` with `"-"`
+/// or `10..12` with `".."`.
+pub fn range_inclusive<'a, T, E>(
+    sep: &'static str,
+) -> impl FnMut(&'a str) -> IResult<&'a str, RangeInclusive<T>, E>
+where
+    T: Integer,
+    E: TagError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+{
+    map(separated_pair(int, tag(sep), int), |(start, end)| {
+        start..=end
+    })
+}
+
+/// A pair of integers joined by `sep`, e.g. `0,9` with `","`.
+pub fn coord2<'a, T, E>(sep: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, (T, T), E>
+where
+    T: Integer,
+    E: TagError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+{
+    separated_pair(int, tag(sep), int)
+}
+
+/// A triple of integers joined by `sep`, e.g. `1,-2,3` with `","`.
+pub fn coord3<'a, T, E>(sep: &'static str) -> impl FnMut(&'a str) -> IResult<&'a str, (T, T, T), E>
+where
+    T: Integer,
+    E: TagError<&'a str> + FromExternalError<&'a str, ParseIntError>,
+{
+    tuple((int, preceded(tag(sep), int), preceded(tag(sep), int)))
+}
+
+/// Blocks of input separated by a single blank line. The `section` parser
+/// must leave the line ending after its last line unconsumed.
+pub fn sections<'a, O, E, F>(section: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>, E>
+where
+    E: TagError<&'a str>,
+    F: Parser<&'a str, O, E>,
+{
+    separated_list1(pair(line_ending, line_ending), section)
+}
+
+/// `value` preceded by a literal `label`, e.g. `x=` in `x=10..12` or
+/// `Time: ` in `Time: 7 15 30`.
+pub fn labelled<'a, O, E, F>(
+    label: &'static str,
+    value: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O, E>
+where
+    E: TagError<&'a str>,
+    F: Parser<&'a str, O, E>,
+{
+    preceded(tag(label), value)
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::character::complete::alpha1;
+    use nom::Finish;
+
+    use super::*;
+    use crate::nom::Locate;
+    use crate::nom::VerboseError;
+
+    type R<'a, O> = IResult<&'a str, O, VerboseError<&'a str>>;
+
+    #[test]
+    fn test_int() {
+        assert_eq!(int::<i32, ()>("-12x"), Ok(("x", -12)));
+        assert_eq!(int::<i64, ()>("+7"), Ok(("", 7)));
+        assert_eq!(int::<u8, ()>("255"), Ok(("", 255)));
+        assert!(int::<u8, ()>("256").is_err());
+        assert!(int::<u32, ()>("-1").is_err());
+        assert!(int::<usize, ()>("").is_err());
+    }
+
+    #[test]
+    fn test_separated_ints() {
+        let r: R<Vec<u8>> = separated_ints(",")("3,4,3,1,2\n");
+        assert_eq!(r, Ok(("\n", vec![3, 4, 3, 1, 2])));
+        let r: R<Vec<i16>> = separated_ints(", ")("-1, 2, x");
+        assert_eq!(r, Ok((", x", vec![-1, 2])));
+    }
+
+    #[test]
+    fn test_range_inclusive() {
+        let r: R<RangeInclusive<u32>> = range_inclusive("-")("2-4,6-8");
+        assert_eq!(r, Ok((",6-8", 2..=4)));
+        let r: R<RangeInclusive<isize>> = range_inclusive("..")("-10..-5");
+        assert_eq!(r, Ok(("", -10..=-5)));
+    }
+
+    #[test]
+    fn test_coords() {
+        let r: R<(usize, usize)> = coord2(",")("0,9 -> 5,9");
+        assert_eq!(r, Ok((" -> 5,9", (0, 9))));
+        let r: R<(i32, i32, i32)> = coord3(",")("1,-2,3");
+        assert_eq!(r, Ok(("", (1, -2, 3))));
+        let r: R<(i32, i32, i32)> = coord3(",")("1,-2");
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_sections() {
+        let mut parser = sections(separated_list1(line_ending, int::<u32, _>));
+        let r: R<Vec<Vec<u32>>> = parser("1\n2\n\n3\n\n4\n5\n");
+        assert_eq!(r, Ok(("\n", vec![vec![1, 2], vec![3], vec![4, 5]])));
+    }
+
+    #[test]
+    fn test_labelled() {
+        let r: R<(RangeInclusive<i8>, RangeInclusive<i8>)> = separated_pair(
+            labelled("x=", range_inclusive("..")),
+            tag(", "),
+            labelled("y=", range_inclusive("..")),
+        )("x=20..30, y=-10..-5");
+        assert_eq!(r, Ok(("", (20..=30, -10..=-5))));
+
+        let r: R<&str> = labelled("name: ", alpha1)("name: value");
+        assert_eq!(r, Ok(("", "value")));
+
+        let err = labelled::<i32, VerboseError<&str>, _>("x=", int)("y=1")
+            .finish()
+            .unwrap_err()
+            .locate("y=1");
+        assert_eq!(err.expected(), "`x=`");
+    }
+}
@@ -0,0 +1,437 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::convert::Infallible;
+use std::fmt;
+use std::fmt::Display;
+
+use nom::error::ContextError;
+use nom::error::ErrorKind;
+use nom::error::FromExternalError;
+use nom::error::ParseError;
+use nom::AsBytes;
+
+/// A parse failure pinned to a line and column of the original input.
+///
+/// Renders like a compiler diagnostic:
+///
+/// ```text
+/// error: expected a line ending
+///  --> 2:21
+///   |
+/// 2 |   Starting items: 79; 98
+///   |                     ^
+///   = while parsing starting items
+///   = while parsing monkey
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Error {
+    line: usize,
+    column: usize,
+    source_line: String,
+    expected: String,
+    context: Vec<String>,
+}
+
+impl Error {
+    /// `remaining` is the suffix of `input` where parsing failed.
+    pub fn new(input: &str, remaining: &str, expected: impl Into<String>) -> Self {
+        Self::from_bytes(input.as_bytes(), remaining.as_bytes(), expected)
+    }
+
+    /// Like [`Error::new`] for byte input; the offending line is decoded
+    /// lossily for display.
+    pub fn from_bytes(input: &[u8], remaining: &[u8], expected: impl Into<String>) -> Self {
+        let offset = input.len().saturating_sub(remaining.len());
+        let before = &input[..offset];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |idx| idx + 1);
+        let line_end = input[offset..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(input.len(), |idx| offset + idx);
+        Self {
+            line: before.iter().filter(|&&b| b == b'\n').count() + 1,
+            column: String::from_utf8_lossy(&input[line_start..offset])
+                .chars()
+                .count()
+                + 1,
+            source_line: String::from_utf8_lossy(&input[line_start..line_end])
+                .trim_end_matches('\r')
+                .to_owned(),
+            expected: expected.into(),
+            context: Vec::new(),
+        }
+    }
+
+    /// 1-based line of the failure.
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// 1-based column (in characters) of the failure.
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// The `context(...)` labels that were being parsed, innermost first.
+    pub fn context(&self) -> &[String] {
+        &self.context
+    }
+
+    /// Push a context label; labels are added from the innermost outward.
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context.push(context.into());
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let line = self.line.to_string();
+        let pad = " ".repeat(line.len());
+        let caret = " ".repeat(self.column - 1);
+        writeln!(f, "error: expected {}", self.expected)?;
+        writeln!(f, "{pad}--> {}:{}", self.line, self.column)?;
+        writeln!(f, "{pad} |")?;
+        writeln!(f, "{line} | {}", self.source_line)?;
+        write!(f, "{pad} | {caret}^")?;
+        for context in self.context.iter() {
+            write!(f, "\n{pad} = while parsing {context}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Infallible> for Error {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
+
+/// A nom error that remembers everything it passed through, so it can become
+/// a located [`Error`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerboseError<I> {
+    errors: Vec<(I, VerboseErrorKind)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerboseErrorKind {
+    Nom(ErrorKind),
+    Char(char),
+    Tag(&'static str),
+    Context(&'static str),
+    External(String),
+}
+
+impl<I> ParseError<I> for VerboseError<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Self {
+            errors: vec![(input, VerboseErrorKind::Nom(kind))],
+        }
+    }
+
+    fn append(input: I, kind: ErrorKind, mut other: Self) -> Self {
+        other.errors.push((input, VerboseErrorKind::Nom(kind)));
+        other
+    }
+
+    fn from_char(input: I, c: char) -> Self {
+        Self {
+            errors: vec![(input, VerboseErrorKind::Char(c))],
+        }
+    }
+}
+
+impl<I> ContextError<I> for VerboseError<I> {
+    fn add_context(input: I, ctx: &'static str, mut other: Self) -> Self {
+        other.errors.push((input, VerboseErrorKind::Context(ctx)));
+        other
+    }
+}
+
+impl<I, E: Display> FromExternalError<I, E> for VerboseError<I> {
+    fn from_external_error(input: I, _: ErrorKind, e: E) -> Self {
+        Self {
+            errors: vec![(input, VerboseErrorKind::External(e.to_string()))],
+        }
+    }
+}
+
+/// Errors that can say which literal was expected.
+///
+/// [`super::tag`] uses this; error types that can't hold the literal fall
+/// back to [`ErrorKind::Tag`].
+pub trait TagError<I>: ParseError<I> {
+    fn from_tag(input: I, _tag: &'static str) -> Self {
+        Self::from_error_kind(input, ErrorKind::Tag)
+    }
+}
+
+impl<I> TagError<I> for nom::error::Error<I> {}
+
+impl<I> TagError<I> for nom::error::VerboseError<I> {}
+
+impl<I> TagError<I> for VerboseError<I> {
+    fn from_tag(input: I, tag: &'static str) -> Self {
+        Self {
+            errors: vec![(input, VerboseErrorKind::Tag(tag))],
+        }
+    }
+}
+
+fn describe(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Tag => "a tag".to_owned(),
+        ErrorKind::Digit => "a digit".to_owned(),
+        ErrorKind::HexDigit => "a hex digit".to_owned(),
+        ErrorKind::Alpha => "a letter".to_owned(),
+        ErrorKind::AlphaNumeric => "a letter or digit".to_owned(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace".to_owned(),
+        ErrorKind::CrLf => "a line ending".to_owned(),
+        ErrorKind::Eof => "end of input".to_owned(),
+        ErrorKind::OneOf => "one of the allowed characters".to_owned(),
+        ErrorKind::Alt => "one of the alternatives".to_owned(),
+        ErrorKind::TooLarge => "a number that fits its type".to_owned(),
+        kind => format!("valid input ({})", kind.description()),
+    }
+}
+
+/// Turn a nom error into a located [`Error`] against the full `input`, which
+/// may be a `&str` or a `&[u8]`.
+pub trait Locate<I> {
+    fn locate(self, input: I) -> Error;
+}
+
+impl<I: AsBytes> Locate<I> for nom::error::Error<I> {
+    fn locate(self, input: I) -> Error {
+        Error::from_bytes(input.as_bytes(), self.input.as_bytes(), describe(self.code))
+    }
+}
+
+impl<I: AsBytes + Copy> Locate<I> for nom::error::VerboseError<I> {
+    fn locate(self, input: I) -> Error {
+        use nom::error::VerboseErrorKind as Kind;
+
+        let (at, expected) = self
+            .errors
+            .iter()
+            .find_map(|(at, kind)| match kind {
+                Kind::Char(c) => Some((*at, format!("{c:?}"))),
+                Kind::Nom(kind) => Some((*at, describe(*kind))),
+                Kind::Context(_) => None,
+            })
+            .unwrap_or((input, "valid input".to_owned()));
+        self.errors
+            .iter()
+            .filter_map(|(_, kind)| match kind {
+                Kind::Context(ctx) => Some(*ctx),
+                _ => None,
+            })
+            .fold(
+                Error::from_bytes(input.as_bytes(), at.as_bytes(), expected),
+                Error::with_context,
+            )
+    }
+}
+
+impl<I: AsBytes + Copy> Locate<I> for VerboseError<I> {
+    fn locate(self, input: I) -> Error {
+        let (at, expected) = self
+            .errors
+            .iter()
+            .find_map(|(at, kind)| match kind {
+                VerboseErrorKind::Nom(kind) => Some((*at, describe(*kind))),
+                VerboseErrorKind::Char(c) => Some((*at, format!("{c:?}"))),
+                VerboseErrorKind::Tag(tag) => Some((*at, format!("`{tag}`"))),
+                VerboseErrorKind::External(msg) => Some((*at, msg.clone())),
+                VerboseErrorKind::Context(_) => None,
+            })
+            .unwrap_or((input, "valid input".to_owned()));
+        self.errors
+            .iter()
+            .filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(ctx) => Some(*ctx),
+                _ => None,
+            })
+            .fold(
+                Error::from_bytes(input.as_bytes(), at.as_bytes(), expected),
+                Error::with_context,
+            )
+    }
+}
+
+/// Errors that can record which line of a larger input they came from.
+///
+/// `#[derive(FromLines)]` parses one record at a time and wraps each error in
+/// a [`LineError`]. With `#[from_lines(T, at_line)]` it calls this instead,
+/// with the 1-based line number, so an error that knows its position, like
+/// [`Error`], can point into the whole input and stay the same type.
+pub trait AtLine {
+    type Located;
+
+    fn at_line(self, line: usize) -> Self::Located;
+}
+
+impl AtLine for Error {
+    type Located = Self;
+
+    fn at_line(mut self, line: usize) -> Self {
+        self.line += line - 1;
+        self
+    }
+}
+
+impl AtLine for anyhow::Error {
+    type Located = Self;
+
+    fn at_line(self, line: usize) -> Self {
+        self.context(format!("on line {line}"))
+    }
+}
+
+impl AtLine for Infallible {
+    type Located = Self;
+
+    fn at_line(self, _: usize) -> Self {
+        self
+    }
+}
+
+/// An error that has no position of its own, with the line it was found on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError<E> {
+    pub line: usize,
+    pub error: E,
+}
+
+impl<E: Display> Display for LineError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+/// Lets a header that cannot fail, like a `String`, share the records' error.
+impl<E> From<Infallible> for LineError<E> {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for LineError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+macro_rules! at_line_wrapped {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl AtLine for $t {
+                type Located = LineError<Self>;
+
+                #[inline]
+                fn at_line(self, line: usize) -> LineError<Self> {
+                    LineError { line, error: self }
+                }
+            }
+        )*
+    };
+}
+
+at_line_wrapped!(
+    std::num::ParseIntError,
+    std::num::ParseFloatError,
+    std::str::ParseBoolError,
+    std::char::ParseCharError,
+);
+
+#[cfg(test)]
+mod tests {
+    use nom::bytes::complete::tag;
+    use nom::character::complete::line_ending;
+    use nom::error::context;
+    use nom::sequence::terminated;
+    use nom::Finish;
+
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let input = "abc\ndef\nghi";
+        let err = Error::new(input, &input[6..], "x");
+        assert_eq!((err.line(), err.column()), (2, 3));
+        assert_eq!(err.source_line, "def");
+
+        let err = Error::new(input, "", "x");
+        assert_eq!((err.line(), err.column()), (3, 4));
+    }
+
+    #[test]
+    fn test_locate_verbose() {
+        let input = "one\ntwo\n";
+        let mut parser = context(
+            "words",
+            terminated(
+                terminated(tag("one"), line_ending),
+                context("second", crate::nom::tag::<VerboseError<&str>>("three")),
+            ),
+        );
+        let err = parser(input).finish().unwrap_err().locate(input);
+        assert_eq!((err.line(), err.column()), (2, 1));
+        assert_eq!(err.expected(), "`three`");
+        assert_eq!(err.context(), ["second", "words"]);
+        assert_eq!(
+            err.to_string(),
+            "\
+error: expected `three`
+ --> 2:1
+  |
+2 | two
+  | ^
+  = while parsing second
+  = while parsing words"
+        );
+    }
+
+    #[test]
+    fn test_at_line() {
+        let input = "12x";
+        let err = Error::new(input, &input[2..], "a digit").at_line(10);
+        assert_eq!((err.line(), err.column()), (10, 3));
+        assert!(err.to_string().contains("10 | 12x"));
+
+        let err = anyhow::anyhow!("bad").at_line(4);
+        assert_eq!(format!("{err:#}"), "on line 4: bad");
+
+        let err = "x".parse::<u8>().unwrap_err().at_line(7);
+        assert_eq!(err.line, 7);
+        assert_eq!(err.to_string(), "line 7: invalid digit found in string");
+    }
+}
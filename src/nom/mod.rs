@@ -33,13 +33,50 @@ use nom::combinator::eof;
 use nom::error::ParseError;
 use nom::AsChar;
 use nom::Compare;
-use nom::IResult;
 use nom::InputIter;
 use nom::InputLength;
 use nom::InputTake;
 use nom::Slice;
 
-pub fn eol<I, E>(input: I) -> IResult<I, I, E>
+pub use self::combinators::coord2;
+pub use self::combinators::coord3;
+pub use self::combinators::int;
+pub use self::combinators::labelled;
+pub use self::combinators::range_inclusive;
+pub use self::combinators::sections;
+pub use self::combinators::separated_ints;
+pub use self::combinators::Integer;
+pub use self::error::AtLine;
+pub use self::error::Error;
+pub use self::error::LineError;
+pub use self::error::Locate;
+pub use self::error::TagError;
+pub use self::error::VerboseError;
+pub use self::error::VerboseErrorKind;
+
+pub mod bytes;
+mod combinators;
+mod error;
+
+/// [`nom::IResult`] defaulting to [`VerboseError`] so failures can be located.
+pub type IResult<I, O, E = VerboseError<I>> = nom::IResult<I, O, E>;
+
+/// Like [`nom::bytes::complete::tag`], but the error remembers the literal.
+pub fn tag<'a, E>(tag: &'static str) -> impl Fn(&'a str) -> IResult<&'a str, &'a str, E>
+where
+    E: TagError<&'a str>,
+{
+    move |input: &'a str| {
+        if input.starts_with(tag) {
+            let (matched, rest) = input.split_at(tag.len());
+            Ok((rest, matched))
+        } else {
+            Err(nom::Err::Error(E::from_tag(input, tag)))
+        }
+    }
+}
+
+pub fn eol<I, E>(input: I) -> nom::IResult<I, I, E>
 where
     I: Clone
         + Compare<&'static str>
@@ -54,7 +91,7 @@ where
     alt((line_ending, eof))(input)
 }
 
-pub fn isize<I, E>(input: I) -> IResult<I, isize, E>
+pub fn isize<I, E>(input: I) -> nom::IResult<I, isize, E>
 where
     I: InputIter
         + Slice<RangeFrom<usize>>
@@ -72,7 +109,7 @@ where
     }
 }
 
-pub fn usize<I, E>(input: I) -> IResult<I, usize, E>
+pub fn usize<I, E>(input: I) -> nom::IResult<I, usize, E>
 where
     I: InputIter + Slice<RangeFrom<usize>> + InputLength,
     <I as InputIter>::Item: AsChar,
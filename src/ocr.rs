@@ -0,0 +1,347 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Read the block letters that some puzzles draw instead of printing an
+//! answer.
+//!
+//! Two fonts are known: the common 4×6 font (letters 5 columns apart) and the
+//! larger 6×10 font (letters 8 columns apart). The font is picked from the
+//! height of the image.
+
+use anyhow::bail;
+
+use crate::matrix::Matrix;
+use crate::matrix::Position;
+use crate::Answer;
+
+struct Font {
+    width: usize,
+    height: usize,
+    stride: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+const FONT_6: Font = Font {
+    width: 4,
+    height: 6,
+    stride: 5,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+const FONT_10: Font = Font {
+    width: 6,
+    height: 10,
+    stride: 8,
+    glyphs: &[
+        ('A', &[
+            "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+            "#....#", "#....#",
+        ]),
+        ('B', &[
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+            "#....#", "#####.",
+        ]),
+        ('C', &[
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#....#", ".####.",
+        ]),
+        ('E', &[
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ]),
+        ('F', &[
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ]),
+        ('G', &[
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+            "#...##", ".###.#",
+        ]),
+        ('H', &[
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+            "#....#", "#....#",
+        ]),
+        ('J', &[
+            "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+            "#...#.", ".###..",
+        ]),
+        ('K', &[
+            "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+            "#...#.", "#....#",
+        ]),
+        ('L', &[
+            "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ]),
+        ('N', &[
+            "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+            "#...##", "#....#",
+        ]),
+        ('P', &[
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ]),
+        ('R', &[
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+            "#....#", "#....#",
+        ]),
+        ('X', &[
+            "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+            "#....#", "#....#",
+        ]),
+        ('Z', &[
+            "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+            "#.....", "######",
+        ]),
+    ],
+};
+
+impl Font {
+    fn for_height(height: usize) -> anyhow::Result<&'static Self> {
+        match height {
+            6 => Ok(&FONT_6),
+            10 => Ok(&FONT_10),
+            _ => bail!("no font is {height} rows tall; expected 6 or 10 rows"),
+        }
+    }
+
+    fn lookup(&self, glyph: &[String]) -> Option<char> {
+        self.glyphs
+            .iter()
+            .find_map(|&(c, g)| (g == glyph).then_some(c))
+    }
+
+    fn glyph(&self, c: char) -> Option<&'static [&'static str]> {
+        self.glyphs
+            .iter()
+            .find_map(|&(g, glyph)| (g == c).then_some(glyph))
+    }
+}
+
+/// The letters that the `height`-row font can draw.
+pub fn alphabet(height: usize) -> anyhow::Result<String> {
+    let font = Font::for_height(height)?;
+    Ok(font.glyphs.iter().map(|&(c, _)| c).collect())
+}
+
+/// Draw `text` in the `height`-row font, the inverse of [`recognize`]. Each
+/// letter is followed by its blank columns.
+pub fn draw(text: &str, height: usize) -> anyhow::Result<Matrix<bool>> {
+    let font = Font::for_height(height)?;
+    let width = text.chars().count() * font.stride;
+    if width == 0 {
+        bail!("no letters to draw");
+    }
+    let mut matrix = Matrix::new(width, vec![false; width * font.height]);
+    for (n, c) in text.chars().enumerate() {
+        let Some(glyph) = font.glyph(c) else {
+            bail!("the {height}-row font has no glyph for {c:?}");
+        };
+        for (row, line) in glyph.iter().enumerate() {
+            for (col, pixel) in line.chars().enumerate() {
+                matrix[Position::new(row, n * font.stride + col)] = pixel == '#';
+            }
+        }
+    }
+    Ok(matrix)
+}
+
+/// Read the letters drawn by `true` tiles.
+pub fn recognize(matrix: &Matrix<bool>) -> anyhow::Result<String> {
+    let font = Font::for_height(matrix.depth())?;
+    // the last letter is often missing its trailing blank column
+    let letters = matrix.width().div_ceil(font.stride);
+    let mut out = String::with_capacity(letters);
+    for n in 0..letters {
+        let left = n * font.stride;
+        let glyph = (0..font.height)
+            .map(|row| {
+                (left..left + font.width)
+                    .map(|col| match matrix.get(Position::new(row, col)) {
+                        Some(true) => '#',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        match font.lookup(&glyph) {
+            Some(c) => out.push(c),
+            None => bail!(
+                "unknown glyph at letter {n} (column {left}):\n{}",
+                glyph.join("\n")
+            ),
+        }
+    }
+    Ok(out)
+}
+
+/// Read the letters drawn with `#` in `s`; every other character is blank.
+pub fn recognize_str(s: &str) -> anyhow::Result<String> {
+    let width = s.lines().map(|line| line.chars().count()).max();
+    let matrix: Matrix<bool> = s
+        .lines()
+        .map(|line| {
+            let width = width.unwrap_or_default();
+            let mut row = line.chars().map(|c| c == '#').collect::<Vec<_>>();
+            row.resize(width, false);
+            row
+        })
+        .collect();
+    if matrix.is_empty() {
+        bail!("no glyphs to recognise");
+    }
+    recognize(&matrix)
+}
+
+/// The letters drawn in `s` as an answer, failing with the unknown glyph
+/// when the alphabet lacks one.
+pub fn recognize_answer(s: &str) -> anyhow::Result<Answer> {
+    recognize_str(s).map(Answer::Str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::alphabet;
+    use super::draw;
+    use super::recognize;
+    use super::recognize_answer;
+    use super::recognize_str;
+    use super::FONT_10;
+    use super::FONT_6;
+
+    #[test]
+    fn test_font_sizes() {
+        for font in [&FONT_6, &FONT_10] {
+            for &(c, glyph) in font.glyphs {
+                assert_eq!(glyph.len(), font.height, "{c}");
+                for row in glyph {
+                    assert_eq!(row.len(), font.width, "{c}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_small() {
+        let art = "\
+###..#....####.####.####.#.....##..####.
+#..#.#....#.......#.#....#....#..#.#....
+#..#.#....###....#..###..#....#....###..
+###..#....#.....#...#....#....#.##.#....
+#.#..#....#....#....#....#....#..#.#....
+#..#.####.####.####.#....####..###.####.
+";
+        assert_eq!(recognize_str(art).unwrap(), "RLEZFLGE");
+    }
+
+    #[test]
+    fn test_small_without_trailing_column() {
+        let art = "\
+.##..####
+#..#.#...
+#..#.###.
+####.#...
+#..#.#...
+#..#.####
+";
+        assert_eq!(recognize_str(art).unwrap(), "AE");
+    }
+
+    #[test]
+    fn test_large() {
+        let art = "\
+#....#..######
+#....#.......#
+#....#.......#
+#....#......#.
+######.....#..
+#....#....#...
+#....#...#....
+#....#..#.....
+#....#..#.....
+#....#..######
+";
+        assert_eq!(recognize_str(art).unwrap(), "HZ");
+    }
+
+    #[test]
+    fn test_unknown_glyph() {
+        let art = "\
+####.####
+#..#.#...
+#..#.###.
+#..#.#...
+#..#.#...
+####.#...
+";
+        let err = recognize_str(art).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown glyph at letter 0 (column 0):\n####\n#..#\n#..#\n#..#\n#..#\n####"
+        );
+        assert_eq!(
+            recognize_answer(art).unwrap_err().to_string(),
+            err.to_string()
+        );
+        assert_eq!(
+            recognize_answer(&art.replace("####.", ".##..")).unwrap(),
+            crate::Answer::Str("OF".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_draw() {
+        for height in [6, 10] {
+            let letters = alphabet(height).unwrap();
+            let matrix = draw(&letters, height).unwrap();
+            assert_eq!(matrix.depth(), height);
+            assert_eq!(recognize(&matrix).unwrap(), letters);
+        }
+        let err = draw("AD", 6).unwrap_err();
+        assert_eq!(err.to_string(), "the 6-row font has no glyph for 'D'");
+    }
+
+    #[test]
+    fn test_unknown_height() {
+        let err = recognize_str("#\n#\n#\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "no font is 3 rows tall; expected 6 or 10 rows"
+        );
+    }
+}
@@ -0,0 +1,352 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Runtime support for `#[derive(macros::Pack)]`: flat integer keys for
+//! search states, so that visited sets and memo tables hash one word
+//! instead of a whole struct.
+//!
+//! ```ignore
+//! #[derive(macros::Pack)]
+//! #[bits(u32)]
+//! struct Player {
+//!     #[bits(10)]
+//!     score: usize,
+//!     #[bits(4)]
+//!     pos: u8,
+//!     turn: bool,
+//! }
+//! ```
+//!
+//! Fields are packed from the lowest bits up, in declaration order. A field
+//! takes `#[bits(n)]` bits if given and all of its type's [`Pack::BITS`]
+//! otherwise; enums put the variant index below the fields of the variant.
+//! The key type defaults to `u64` and is chosen with `#[bits(u128)]` on the
+//! type. Layouts that need more bits than the key has, or a `#[bits(n)]`
+//! wider than the field's type, fail to compile; values that do not fit in
+//! their `#[bits(n)]` panic rather than collide with another key.
+
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// An unsigned integer that can hold a packed value.
+pub trait Key: Copy + Eq + Ord + Hash + Debug {
+    const BITS: u32;
+
+    /// The low [`Key::BITS`] bits of `bits`.
+    fn from_bits(bits: u128) -> Self;
+
+    fn into_bits(self) -> u128;
+}
+
+macro_rules! impl_key {
+    ($($ty:ty),*) => {$(
+        impl Key for $ty {
+            const BITS: u32 = <$ty>::BITS;
+
+            fn from_bits(bits: u128) -> Self {
+                bits as $ty
+            }
+
+            fn into_bits(self) -> u128 {
+                self as u128
+            }
+        }
+    )*};
+}
+
+impl_key!(u8, u16, u32, u64, u128, usize);
+
+/// A value that round-trips through the low [`Pack::BITS`] bits of a
+/// [`Pack::Key`].
+pub trait Pack: Sized {
+    type Key: Key;
+
+    /// How many bits `pack` may set.
+    const BITS: u32;
+
+    /// Whether the top bit is a sign to extend when a field narrows the
+    /// value with `#[bits(n)]`.
+    const SIGNED: bool = false;
+
+    fn pack(&self) -> Self::Key;
+
+    /// The value `key` was packed from. Panics on keys that no value packs
+    /// to.
+    fn unpack(key: Self::Key) -> Self;
+}
+
+macro_rules! impl_unsigned {
+    ($($ty:ty),*) => {$(
+        impl Pack for $ty {
+            type Key = $ty;
+
+            const BITS: u32 = <$ty>::BITS;
+
+            fn pack(&self) -> $ty {
+                *self
+            }
+
+            fn unpack(key: $ty) -> Self {
+                key
+            }
+        }
+    )*};
+}
+
+impl_unsigned!(u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_signed {
+    ($($ty:ty => $key:ty),*) => {$(
+        impl Pack for $ty {
+            type Key = $key;
+
+            const BITS: u32 = <$ty>::BITS;
+            const SIGNED: bool = true;
+
+            fn pack(&self) -> $key {
+                *self as $key
+            }
+
+            fn unpack(key: $key) -> Self {
+                key as $ty
+            }
+        }
+    )*};
+}
+
+impl_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+impl Pack for bool {
+    type Key = u8;
+
+    const BITS: u32 = 1;
+
+    fn pack(&self) -> u8 {
+        *self as u8
+    }
+
+    fn unpack(key: u8) -> Self {
+        key & 1 == 1
+    }
+}
+
+/// `None` is all zeroes; `Some` sets the lowest bit and packs the value
+/// above it.
+impl<T: Pack> Pack for Option<T> {
+    type Key = u128;
+
+    const BITS: u32 = {
+        assert!(T::BITS < 128, "`Option` needs one bit more than its value");
+        T::BITS + 1
+    };
+
+    fn pack(&self) -> u128 {
+        match self {
+            None => 0,
+            Some(value) => 1 | put(value, T::BITS, 1),
+        }
+    }
+
+    fn unpack(key: u128) -> Self {
+        (key & 1 == 1).then(|| take(key, T::BITS, 1))
+    }
+}
+
+/// Elements are packed from the lowest bits up.
+impl<T: Pack, const N: usize> Pack for [T; N] {
+    type Key = u128;
+
+    const BITS: u32 = {
+        assert!(
+            T::BITS as usize * N <= 128,
+            "the array needs more than 128 bits"
+        );
+        T::BITS * N as u32
+    };
+
+    fn pack(&self) -> u128 {
+        let _ = Self::BITS;
+        (0..).zip(self).fold(0, |bits, (i, value)| {
+            bits | put(value, T::BITS, i * T::BITS)
+        })
+    }
+
+    fn unpack(key: u128) -> Self {
+        let _ = Self::BITS;
+        std::array::from_fn(|i| take(key, T::BITS, i as u32 * T::BITS))
+    }
+}
+
+/// A mask of the low `width` bits.
+pub const fn mask(width: u32) -> u128 {
+    if width >= u128::BITS {
+        !0
+    } else {
+        (1 << width) - 1
+    }
+}
+
+/// The larger of two widths, for the payload of a derived enum.
+#[doc(hidden)]
+pub const fn max(a: u32, b: u32) -> u32 {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/// The bits of `value` narrowed to `width` and shifted up to `offset`.
+#[doc(hidden)]
+pub fn put<T: Pack>(value: &T, width: u32, offset: u32) -> u128 {
+    let bits = value.pack().into_bits();
+    assert!(
+        extend::<T>(bits & mask(width), width) == bits,
+        "a `{}` does not fit in {width} bits",
+        std::any::type_name::<T>(),
+    );
+    (bits & mask(width)).checked_shl(offset).unwrap_or(0)
+}
+
+/// The value packed at `offset` in `width` bits by [`put`].
+#[doc(hidden)]
+pub fn take<T: Pack>(bits: u128, width: u32, offset: u32) -> T {
+    let bits = bits.checked_shr(offset).unwrap_or(0) & mask(width);
+    T::unpack(T::Key::from_bits(extend::<T>(bits, width)))
+}
+
+/// Sign-extend a `width`-bit field of a signed `T` to all of `T::BITS`.
+fn extend<T: Pack>(bits: u128, width: u32) -> u128 {
+    if T::SIGNED && width > 0 && width < T::BITS && bits >> (width - 1) & 1 == 1 {
+        (bits | !mask(width)) & mask(T::BITS)
+    } else {
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip<T: Pack + PartialEq + Debug>(value: T) {
+        assert_eq!(T::unpack(value.pack()), value);
+    }
+
+    #[test]
+    fn test_primitives() {
+        roundtrip(200u8);
+        roundtrip(-3i32);
+        roundtrip(true);
+        roundtrip(Some(-1i8));
+        roundtrip(None::<u16>);
+        roundtrip([1u8, 2, 3]);
+        assert_eq!(Some(3u8).pack(), 0b111);
+        assert_eq!([1u8, 2].pack(), 0x0201);
+    }
+
+    #[test]
+    fn test_fields() {
+        let bits = put(&-2i32, 4, 3) | put(&5u8, 3, 0);
+        assert_eq!(bits, 0b1110 << 3 | 0b101);
+        assert_eq!(take::<i32>(bits, 4, 3), -2);
+        assert_eq!(take::<u8>(bits, 3, 0), 5);
+        assert_eq!(put(&1u8, 1, 128), 0);
+    }
+
+    #[test]
+    #[should_panic = "a `u8` does not fit in 3 bits"]
+    fn test_overflow() {
+        put(&8u8, 3, 0);
+    }
+
+    /// The derive itself, which needs the `macros` crate.
+    #[cfg(feature = "macros")]
+    mod derive {
+        use super::roundtrip;
+        use crate::pack::Pack;
+
+        #[derive(Debug, Clone, Copy, PartialEq, macros::Pack)]
+        enum Amphipod {
+            Amber,
+            Bronze,
+            Copper,
+        }
+
+        #[derive(Debug, Clone, Copy, PartialEq, macros::Pack)]
+        #[bits(u8)]
+        enum Tile {
+            Wall,
+            Vacant,
+            Amphipod(Amphipod),
+        }
+
+        #[derive(Debug, PartialEq, macros::Pack)]
+        #[bits(u32)]
+        struct Player {
+            #[bits(10)]
+            score: usize,
+            #[bits(4)]
+            pos: u8,
+            turn: bool,
+            #[bits(5)]
+            dx: i16,
+        }
+
+        #[derive(Debug, PartialEq, macros::Pack)]
+        #[bits(u128)]
+        struct Burrow<const N: usize>([Tile; N], Option<Amphipod>);
+
+        #[test]
+        fn test_enum() {
+            assert_eq!(Amphipod::BITS, 2);
+            assert_eq!(Tile::BITS, 4);
+            assert_eq!(Tile::Amphipod(Amphipod::Copper).pack(), 0b10_10);
+            for tile in [Tile::Wall, Tile::Vacant, Tile::Amphipod(Amphipod::Bronze)] {
+                roundtrip(tile);
+            }
+        }
+
+        #[test]
+        #[should_panic = "3 is not a packed `Tile`"]
+        fn test_bad_key() {
+            Tile::unpack(3);
+        }
+
+        #[test]
+        fn test_struct() {
+            assert_eq!(Player::BITS, 20);
+            let player = Player {
+                score: 1000,
+                pos: 9,
+                turn: true,
+                dx: -16,
+            };
+            assert_eq!(player.pack(), 0b10000 << 15 | 1 << 14 | 9 << 10 | 1000);
+            roundtrip(player);
+        }
+
+        #[test]
+        fn test_generic() {
+            assert_eq!(Burrow::<7>::BITS, 31);
+            roundtrip(Burrow([Tile::Vacant; 7], Some(Amphipod::Amber)));
+            roundtrip(Burrow([Tile::Amphipod(Amphipod::Copper), Tile::Wall], None));
+        }
+    }
+}
@@ -0,0 +1,259 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! Per-input constants for puzzles whose example and live runs differ (the
+//! number of steps, the row to inspect, the grid size…).
+//!
+//! A solution declares its constants with [`params!`] and takes the
+//! generated struct as a second argument. Values come from an input's
+//! `inputs/<name>.params` file in tests and from `--param key=value` on the
+//! command line; anything left unset keeps the declared default.
+//!
+//! ```
+//! aoc::params! {
+//!     pub struct Params {
+//!         /// Days to simulate.
+//!         days: usize = 80,
+//!     }
+//! }
+//!
+//! let params: Params = aoc::params::parse("days=18\n").unwrap();
+//! assert_eq!(params.days, 18);
+//! assert_eq!(Params::default().days, 80);
+//! ```
+
+use std::fmt;
+
+/// A set of named constants that can be overridden one `key=value` pair at
+/// a time. Implement it with [`params!`].
+pub trait Params: Default {
+    /// The keys this set accepts, in declaration order.
+    const KEYS: &'static [&'static str];
+
+    /// Override the constant named `key` with `value`.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ParamError>;
+}
+
+/// Why a parameter could not be applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    /// The text is not of the form `key=value`.
+    Syntax(String),
+    /// The solution has no constant with this name.
+    Unknown {
+        key: String,
+        known: &'static [&'static str],
+    },
+    /// The value does not parse as the constant's type.
+    Value {
+        key: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax(s) => write!(f, "expected key=value; got {s:?}"),
+            Self::Unknown { key, known: [] } => {
+                write!(f, "unknown parameter {key:?}; this solution takes none")
+            }
+            Self::Unknown { key, known } => {
+                write!(
+                    f,
+                    "unknown parameter {key:?}; expected one of {}",
+                    known.join(", ")
+                )
+            }
+            Self::Value { key, value, reason } => {
+                write!(f, "invalid value {value:?} for {key}: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// Split a single `key=value` pair, trimming whitespace around both halves.
+/// This is the value parser behind `--param`.
+pub fn parse_pair(s: &str) -> Result<(String, String), ParamError> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_owned(), value.trim().to_owned()))
+        }
+        _ => Err(ParamError::Syntax(s.to_owned())),
+    }
+}
+
+/// Start from the defaults and apply each pair in order.
+pub fn from_pairs<P, K, V>(pairs: impl IntoIterator<Item = (K, V)>) -> Result<P, ParamError>
+where
+    P: Params,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut params = P::default();
+    for (key, value) in pairs {
+        params.set(key.as_ref(), value.as_ref())?;
+    }
+    Ok(params)
+}
+
+/// Parse a `.params` metadata file: one `key=value` pair per line, with
+/// blank lines and `#` comments ignored.
+pub fn parse<P: Params>(s: &str) -> Result<P, ParamError> {
+    from_pairs(parse_pairs(s)?)
+}
+
+/// The pairs of a `.params` metadata file, for a runner that applies them
+/// to a solution it only knows by name.
+pub fn parse_pairs(s: &str) -> Result<Vec<(String, String)>, ParamError> {
+    s.lines()
+        .map(|line| line.split_once('#').map_or(line, |(line, _)| line).trim())
+        .filter(|line| !line.is_empty())
+        .map(parse_pair)
+        .collect()
+}
+
+/// The parameters of a solution that has no tunable constants. Any key is
+/// rejected.
+impl Params for () {
+    const KEYS: &'static [&'static str] = &[];
+
+    fn set(&mut self, key: &str, _: &str) -> Result<(), ParamError> {
+        Err(ParamError::Unknown {
+            key: key.to_owned(),
+            known: Self::KEYS,
+        })
+    }
+}
+
+/// Declare a struct of named constants with their defaults and implement
+/// [`Params`] for it. Each field is set from its [`FromStr`] form.
+///
+/// [`FromStr`]: std::str::FromStr
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($(#[$field_meta:meta])* $field:ident: $ty:ty = $default:expr),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        $vis struct $name {
+            $($(#[$field_meta])* pub $field: $ty,)*
+        }
+
+        impl ::core::default::Default for $name {
+            fn default() -> Self {
+                Self {
+                    $($field: $default,)*
+                }
+            }
+        }
+
+        impl $crate::params::Params for $name {
+            const KEYS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            fn set(&mut self, key: &str, value: &str) -> Result<(), $crate::params::ParamError> {
+                match key {
+                    $(stringify!($field) => {
+                        self.$field = value.parse::<$ty>().map_err(|e| {
+                            $crate::params::ParamError::Value {
+                                key: key.to_owned(),
+                                value: value.to_owned(),
+                                reason: e.to_string(),
+                            }
+                        })?;
+                    })*
+                    _ => {
+                        return Err($crate::params::ParamError::Unknown {
+                            key: key.to_owned(),
+                            known: Self::KEYS,
+                        });
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::params! {
+        struct Sample {
+            steps: usize = 10,
+            row: i64 = -3,
+        }
+    }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(Sample::default(), Sample { steps: 10, row: -3 });
+        assert_eq!(Sample::KEYS, &["steps", "row"]);
+    }
+
+    #[test]
+    fn test_parse() {
+        let params: Sample = parse("# live input\nsteps = 1000\n\nrow=2000000 # target\n").unwrap();
+        assert_eq!(params, Sample {
+            steps: 1000,
+            row: 2000000
+        });
+        let params: Sample = parse("").unwrap();
+        assert_eq!(params, Sample::default());
+    }
+
+    #[test]
+    fn test_parse_pairs() {
+        let pairs = parse_pairs("steps = 6 # example\n\nsize=11\n").unwrap();
+        assert_eq!(pairs, [
+            ("steps".to_string(), "6".to_string()),
+            ("size".to_string(), "11".to_string()),
+        ]);
+        assert_eq!(parse_pairs("size"), Err(ParamError::Syntax("size".into())));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse_pair("steps"), Err(ParamError::Syntax("steps".into())));
+        assert_eq!(parse_pair("=4"), Err(ParamError::Syntax("=4".into())));
+
+        let err = parse::<Sample>("size=4").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"unknown parameter "size"; expected one of steps, row"#
+        );
+
+        let err = from_pairs::<Sample, _, _>([("steps", "-1")]).unwrap_err();
+        assert!(matches!(err, ParamError::Value { ref key, .. } if key == "steps"));
+
+        let err = from_pairs::<(), _, _>([("steps", "1")]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            r#"unknown parameter "steps"; this solution takes none"#
+        );
+    }
+}
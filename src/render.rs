@@ -0,0 +1,260 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Turn a [`Matrix`] into pixels.
+//!
+//! Each tile becomes one pixel (or a `scale`×`scale` block of pixels) whose
+//! colour is picked by a palette closure. Frames can be written as PPM or PNG
+//! images, and a sequence of frames can be written as an animated GIF.
+
+use std::collections::HashMap;
+use std::io::Write;
+
+use anyhow::bail;
+use anyhow::Context;
+
+use crate::matrix::Matrix;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0x00, 0x00, 0x00];
+pub const WHITE: Rgb = [0xff, 0xff, 0xff];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Box<[Rgb]>,
+}
+
+impl Frame {
+    pub fn new<Tile>(matrix: &Matrix<Tile>, mut palette: impl FnMut(&Tile) -> Rgb) -> Self {
+        Self {
+            width: matrix.width(),
+            height: matrix.depth(),
+            pixels: matrix.iter().map(|(_, tile)| palette(tile)).collect(),
+        }
+    }
+
+    /// Render with `on` for `true` tiles and `off` for `false` tiles.
+    pub fn from_bools(matrix: &Matrix<bool>, on: Rgb, off: Rgb) -> Self {
+        Self::new(matrix, |&b| if b { on } else { off })
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    /// Blow every pixel up into a `factor`×`factor` block.
+    pub fn scale(self, factor: usize) -> Self {
+        assert!(factor > 0);
+        if factor == 1 {
+            return self;
+        }
+        let width = self.width * factor;
+        let height = self.height * factor;
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            for _ in 0..factor {
+                for &px in row {
+                    pixels.extend(std::iter::repeat_n(px, factor));
+                }
+            }
+        }
+        Self {
+            width,
+            height,
+            pixels: pixels.into_boxed_slice(),
+        }
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+
+    /// Write a binary (P6) portable pixmap.
+    pub fn write_ppm<W: Write>(&self, mut w: W) -> std::io::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        w.write_all(&self.bytes())
+    }
+
+    pub fn write_png<W: Write>(&self, w: W) -> anyhow::Result<()> {
+        let width = u32::try_from(self.width).context("frame too wide for PNG")?;
+        let height = u32::try_from(self.height).context("frame too tall for PNG")?;
+        let mut encoder = png::Encoder::new(w, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.bytes())?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn to_gif(&self) -> anyhow::Result<gif::Frame<'static>> {
+        let mut palette = HashMap::new();
+        let mut indices = Vec::with_capacity(self.pixels.len());
+        for px in self.pixels.iter() {
+            let next = palette.len();
+            let idx = *palette.entry(*px).or_insert(next);
+            if idx > u8::MAX as usize {
+                bail!("frame has more than 256 colours; GIF frames are limited to 256");
+            }
+            indices.push(idx as u8);
+        }
+        let mut colours = vec![BLACK; palette.len()];
+        for (px, idx) in palette {
+            colours[idx] = px;
+        }
+        let colours = colours.into_iter().flatten().collect::<Vec<_>>();
+        Ok(gif::Frame::from_palette_pixels(
+            self.width as u16,
+            self.height as u16,
+            &indices,
+            &colours,
+            None,
+        ))
+    }
+}
+
+/// Write `frames` as a looping animated GIF, showing each frame for `delay`
+/// hundredths of a second.
+///
+/// Every frame must have the same dimensions and use at most 256 colours.
+pub fn write_gif<W: Write>(
+    w: W,
+    frames: impl IntoIterator<Item = Frame>,
+    delay: u16,
+) -> anyhow::Result<()> {
+    let mut frames = frames.into_iter().peekable();
+    let (width, height) = match frames.peek() {
+        Some(frame) => (frame.width, frame.height),
+        None => bail!("cannot write a GIF with no frames"),
+    };
+    let width = u16::try_from(width).context("frame too wide for GIF")?;
+    let height = u16::try_from(height).context("frame too tall for GIF")?;
+
+    let mut encoder = gif::Encoder::new(w, width, height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    for (n, frame) in frames.enumerate() {
+        if (frame.width, frame.height) != (width as usize, height as usize) {
+            bail!(
+                "frame {n} is {}x{}; expected {width}x{height}",
+                frame.width,
+                frame.height
+            );
+        }
+        let mut frame = frame.to_gif()?;
+        frame.delay = delay;
+        encoder.write_frame(&frame)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Frame;
+    use super::BLACK;
+    use super::WHITE;
+    use crate::matrix::Matrix;
+
+    const RED: super::Rgb = [0xff, 0x00, 0x00];
+
+    fn matrix() -> Matrix<bool> {
+        [[true, false, true], [false, true, false]]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut buf = Vec::new();
+        Frame::from_bools(&matrix(), WHITE, BLACK)
+            .write_ppm(&mut buf)
+            .unwrap();
+        let (header, data) = buf.split_at(11);
+        assert_eq!(header, b"P6\n3 2\n255\n");
+        assert_eq!(data.len(), 3 * 2 * 3);
+        assert_eq!(&data[..6], &[0xff, 0xff, 0xff, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_scale() {
+        let frame = Frame::from_bools(&matrix(), WHITE, BLACK).scale(2);
+        assert_eq!((frame.width(), frame.height()), (6, 4));
+        assert_eq!(frame.pixel(0, 0), WHITE);
+        assert_eq!(frame.pixel(1, 1), WHITE);
+        assert_eq!(frame.pixel(2, 1), BLACK);
+        assert_eq!(frame.pixel(2, 2), WHITE);
+    }
+
+    #[test]
+    fn test_png_roundtrip() {
+        let frame = Frame::from_bools(&matrix(), RED, BLACK);
+        let mut buf = Vec::new();
+        frame.write_png(&mut buf).unwrap();
+
+        let decoder = png::Decoder::new(buf.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut data).unwrap();
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(&data[..info.buffer_size()], frame.bytes().as_slice());
+    }
+
+    #[test]
+    fn test_gif_roundtrip() {
+        let first = Frame::from_bools(&matrix(), WHITE, BLACK);
+        let second = Frame::from_bools(&matrix().map(|b| !b), RED, BLACK);
+        let mut buf = Vec::new();
+        super::write_gif(&mut buf, [first.clone(), second.clone()], 10).unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(buf.as_slice()).unwrap();
+        for expected in [first, second] {
+            let frame = decoder.read_next_frame().unwrap().unwrap();
+            assert_eq!(frame.delay, 10);
+            let rgb = frame
+                .buffer
+                .chunks(4)
+                .flat_map(|px| &px[..3])
+                .copied()
+                .collect::<Vec<_>>();
+            assert_eq!(rgb, expected.bytes());
+        }
+        assert!(decoder.read_next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_gif_mismatched_frames() {
+        let first = Frame::from_bools(&matrix(), WHITE, BLACK);
+        let second = first.clone().scale(2);
+        let err = super::write_gif(Vec::new(), [first, second], 10).unwrap_err();
+        assert_eq!(err.to_string(), "frame 1 is 6x4; expected 3x2");
+    }
+}
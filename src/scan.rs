@@ -0,0 +1,218 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Runtime support for `#[derive(macros::Scan)]`.
+//!
+//! The derive turns a pattern such as `"move {count} from {from} to {to}"`
+//! into code that walks the input with a [`Scanner`]: literals must match
+//! exactly, and each field takes the text up to the next literal and parses
+//! it with its own `FromStr`. When a field fails to parse, the next
+//! occurrence of the following literal is tried, so `"{lo}-{hi}"` still
+//! reads `-3--1`.
+//!
+//! On failure the scanner reports the furthest point any pattern reached,
+//! as a located [`Error`], listing every literal or field that could have
+//! continued from there.
+
+use std::any::Any;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::nom::Error;
+
+#[derive(Debug)]
+pub struct Scanner<'a> {
+    input: &'a str,
+    offset: usize,
+    expected: Vec<String>,
+    context: Vec<String>,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            offset: 0,
+            expected: Vec::new(),
+            context: Vec::new(),
+        }
+    }
+
+    /// Byte offset of `at`, which must be a slice of the input.
+    fn offset_of(&self, at: &str) -> usize {
+        at.as_ptr() as usize - self.input.as_ptr() as usize
+    }
+
+    /// Remember a failure unless something already got further.
+    fn fail_at(&mut self, offset: usize, expected: String, context: Vec<String>) {
+        if offset > self.offset || self.expected.is_empty() {
+            self.offset = offset;
+            self.expected = vec![expected];
+            self.context = context;
+        } else if offset == self.offset && !self.expected.contains(&expected) {
+            self.expected.push(expected);
+        }
+    }
+
+    fn fail(&mut self, at: &str, expected: String) {
+        self.fail_at(self.offset_of(at), expected, Vec::new());
+    }
+
+    /// The rest of `at` after `literal`.
+    pub fn literal<'s>(&mut self, at: &'s str, literal: &'static str) -> Option<&'s str> {
+        let rest = at.strip_prefix(literal);
+        if rest.is_none() {
+            self.fail(at, format!("`{literal}`"));
+        }
+        rest
+    }
+
+    /// Whether `at` is the end of the input.
+    pub fn end(&mut self, at: &str) -> bool {
+        if !at.is_empty() {
+            self.fail(at, "the end of the input".to_owned());
+        }
+        at.is_empty()
+    }
+
+    /// Every place the field before `literal` could end, earliest first.
+    pub fn splits(&mut self, at: &str, literal: &'static str) -> Vec<usize> {
+        let splits = at
+            .match_indices(literal)
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        if splits.is_empty() {
+            self.fail(&at[at.len()..], format!("`{literal}`"));
+        }
+        splits
+    }
+
+    /// `at` without the `literal` that ends the pattern.
+    pub fn suffix<'s>(&mut self, at: &'s str, literal: &'static str) -> Option<&'s str> {
+        let rest = at.strip_suffix(literal);
+        if rest.is_none() {
+            self.fail(&at[at.len()..], format!("`{literal}`"));
+        }
+        rest
+    }
+
+    /// Parse the whole of `text` as the field `name`.
+    ///
+    /// A located [`Error`] from the field's own parser is moved to where
+    /// the field sits in the input.
+    pub fn field<T>(&mut self, text: &str, name: &'static str) -> Option<T>
+    where
+        T: FromStr,
+        T::Err: Display + 'static,
+    {
+        let err = match text.parse() {
+            Ok(value) => return Some(value),
+            Err(err) => err,
+        };
+        let start = self.offset_of(text);
+        match (&err as &dyn Any).downcast_ref::<Error>() {
+            Some(inner) if inner.line() == 1 => {
+                let column = text
+                    .char_indices()
+                    .map(|(idx, _)| idx)
+                    .chain([text.len()])
+                    .nth(inner.column() - 1)
+                    .unwrap_or(text.len());
+                let mut context = inner.context().to_vec();
+                context.push(format!("`{name}`"));
+                self.fail_at(start + column, inner.expected().to_owned(), context);
+            }
+            _ => self.fail(text, format!("`{name}` ({err})")),
+        }
+        None
+    }
+
+    /// The error for the furthest failure.
+    pub fn finish(self) -> Error {
+        let expected = if self.expected.is_empty() {
+            "valid input".to_owned()
+        } else {
+            self.expected.join(" or ")
+        };
+        self.context.into_iter().fold(
+            Error::new(self.input, &self.input[self.offset..], expected),
+            Error::with_context,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scanner;
+
+    #[test]
+    fn test_furthest_failure() {
+        let input = "move x from 1 to 2";
+        let mut scanner = Scanner::new(input);
+        assert_eq!(scanner.literal(input, "copy "), None);
+        let rest = scanner.literal(input, "move ").unwrap();
+        let splits = scanner.splits(rest, " from ");
+        assert_eq!(splits, [1]);
+        assert_eq!(scanner.field::<u8>(&rest[..1], "count"), None);
+        let err = scanner.finish();
+        assert_eq!((err.line(), err.column()), (1, 6));
+        assert_eq!(err.expected(), "`count` (invalid digit found in string)");
+    }
+
+    #[test]
+    fn test_alternatives() {
+        let input = "of";
+        let mut scanner = Scanner::new(input);
+        assert_eq!(scanner.literal(input, "on"), None);
+        assert_eq!(scanner.literal(input, "off"), None);
+        assert_eq!(scanner.finish().expected(), "`on` or `off`");
+
+        let mut scanner = Scanner::new(input);
+        let rest = scanner.literal(input, "o").unwrap();
+        assert!(!scanner.end(rest));
+        assert_eq!(scanner.suffix(rest, "x"), None);
+        let err = scanner.finish();
+        assert_eq!(err.column(), 3);
+        assert_eq!(err.expected(), "`x`");
+    }
+
+    #[test]
+    fn test_nested_error() {
+        let input = "at [1,x]";
+        let mut scanner = Scanner::new(input);
+        let rest = scanner.literal(input, "at ").unwrap();
+        assert_eq!(scanner.field::<Pair>(rest, "pair"), None);
+        let err = scanner.finish();
+        assert_eq!((err.line(), err.column()), (1, 7));
+        assert_eq!(err.expected(), "a number");
+        assert_eq!(err.context(), ["`pair`"]);
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Pair;
+
+    impl std::str::FromStr for Pair {
+        type Err = crate::nom::Error;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Err(crate::nom::Error::new(s, &s[3..], "a number"))
+        }
+    }
+}
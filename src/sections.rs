@@ -0,0 +1,301 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Runtime support for `#[derive(macros::FromSections)]`: inputs made of
+//! blocks separated by blank lines, such as a list of dots followed by a
+//! list of folds.
+//!
+//! Every field reads the next section: `Vec<T>` fields parse each line as a
+//! `T`, `#[section(each)]` fields parse every remaining section as a `T`,
+//! and any other field parses the whole section with its own `FromStr`.
+//! `#[derive(macros::Display)]` writes them back with a [`SectionWriter`].
+
+use std::any::Any;
+use std::fmt;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::nom::AtLine;
+
+/// Why a sectioned input could not be read. Lines are 1-based and count
+/// from the start of the whole input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SectionError {
+    /// The input ran out before this section.
+    Missing { section: &'static str },
+    /// There is more input after the last section.
+    Unexpected { line: usize },
+    /// The section's own parser failed.
+    Parse {
+        section: &'static str,
+        line: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for SectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { section } => write!(f, "the input has no `{section}` section"),
+            Self::Unexpected { line } => write!(f, "unexpected section at line {line}"),
+            Self::Parse {
+                section,
+                line,
+                message,
+            } => write!(f, "in `{section}` at line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for SectionError {}
+
+/// Walks the sections of an input in order.
+#[derive(Debug)]
+pub struct Sections<'a> {
+    lines: std::iter::Peekable<std::iter::Enumerate<std::str::Lines<'a>>>,
+}
+
+impl<'a> Sections<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            lines: input.lines().enumerate().peekable(),
+        }
+    }
+
+    fn skip_blank(&mut self) {
+        while self.lines.next_if(|(_, line)| line.is_empty()).is_some() {}
+    }
+
+    /// The lines of the next section with their 1-based line numbers.
+    fn next_section(&mut self) -> Option<Vec<(usize, &'a str)>> {
+        self.skip_blank();
+        let mut section = Vec::new();
+        while let Some((idx, line)) = self.lines.next_if(|(_, line)| !line.is_empty()) {
+            section.push((idx + 1, line));
+        }
+        (!section.is_empty()).then_some(section)
+    }
+
+    /// Parse the next section as a whole.
+    pub fn parse<T>(&mut self, section: &'static str) -> Result<T, SectionError>
+    where
+        T: FromStr,
+        T::Err: Display + 'static,
+    {
+        let lines = self
+            .next_section()
+            .ok_or(SectionError::Missing { section })?;
+        parse(section, &lines)
+    }
+
+    /// Parse every line of the next section as a `T`.
+    pub fn lines<T, C>(&mut self, section: &'static str) -> Result<C, SectionError>
+    where
+        T: FromStr,
+        T::Err: Display + 'static,
+        C: FromIterator<T>,
+    {
+        let lines = self
+            .next_section()
+            .ok_or(SectionError::Missing { section })?;
+        lines.iter().map(|&line| parse(section, &[line])).collect()
+    }
+
+    /// Parse every remaining section as a `T`. There must be at least one.
+    pub fn each<T, C>(&mut self, section: &'static str) -> Result<C, SectionError>
+    where
+        T: FromStr,
+        T::Err: Display + 'static,
+        C: FromIterator<T>,
+    {
+        let mut sections = std::iter::from_fn(|| self.next_section()).peekable();
+        if sections.peek().is_none() {
+            return Err(SectionError::Missing { section });
+        }
+        sections.map(|lines| parse(section, &lines)).collect()
+    }
+
+    /// Check that nothing follows the last section.
+    pub fn finish(mut self) -> Result<(), SectionError> {
+        self.skip_blank();
+        match self.lines.next() {
+            Some((idx, _)) => Err(SectionError::Unexpected { line: idx + 1 }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Writes sections the way [`Sections`] reads them: each ends with a line
+/// break and is separated from the next by a blank line.
+pub struct SectionWriter<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    first: bool,
+}
+
+impl<'a, 'b> SectionWriter<'a, 'b> {
+    pub fn new(f: &'a mut fmt::Formatter<'b>) -> Self {
+        Self { f, first: true }
+    }
+
+    fn start(&mut self) -> fmt::Result {
+        if !std::mem::take(&mut self.first) {
+            writeln!(self.f)?;
+        }
+        Ok(())
+    }
+
+    /// Write `value` as a section of its own.
+    pub fn whole(&mut self, value: &dyn Display) -> fmt::Result {
+        self.start()?;
+        let text = value.to_string();
+        self.f.write_str(&text)?;
+        if !text.ends_with('\n') {
+            writeln!(self.f)?;
+        }
+        Ok(())
+    }
+
+    /// Write one section with a line per value.
+    pub fn lines<'v>(&mut self, values: impl IntoIterator<Item = &'v dyn Display>) -> fmt::Result {
+        self.start()?;
+        values
+            .into_iter()
+            .try_for_each(|value| writeln!(self.f, "{value}"))
+    }
+
+    /// Write a section per value.
+    pub fn each<'v>(&mut self, values: impl IntoIterator<Item = &'v dyn Display>) -> fmt::Result {
+        values.into_iter().try_for_each(|value| self.whole(value))
+    }
+}
+
+/// Parse the joined `lines`, moving a located [`crate::nom::Error`] to
+/// where the section starts.
+fn parse<T>(section: &'static str, lines: &[(usize, &str)]) -> Result<T, SectionError>
+where
+    T: FromStr,
+    T::Err: Display + 'static,
+{
+    let first = lines[0].0;
+    let text = lines
+        .iter()
+        .map(|(_, line)| *line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    text.parse().map_err(|err: T::Err| {
+        match (&err as &dyn Any).downcast_ref::<crate::nom::Error>() {
+            Some(inner) => {
+                let inner = inner.clone().at_line(first);
+                SectionError::Parse {
+                    section,
+                    line: inner.line(),
+                    message: inner.to_string(),
+                }
+            }
+            None => SectionError::Parse {
+                section,
+                line: first,
+                message: err.to_string(),
+            },
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+
+    use super::SectionError;
+    use super::SectionWriter;
+    use super::Sections;
+
+    #[test]
+    fn test_sections() {
+        let input = "1,2\n\n3\n4\n\n\n5 6\n\n7 8\n";
+        let mut sections = Sections::new(input);
+        assert_eq!(sections.parse::<String>("header"), Ok("1,2".to_owned()));
+        assert_eq!(sections.lines::<u8, Vec<_>>("numbers"), Ok(vec![3, 4]));
+        assert_eq!(
+            sections.each::<String, Vec<_>>("pairs"),
+            Ok(vec!["5 6".to_owned(), "7 8".to_owned()])
+        );
+        assert_eq!(sections.finish(), Ok(()));
+    }
+
+    #[test]
+    fn test_errors() {
+        let input = "1\n\n2\nx\n\n3\n";
+        let mut sections = Sections::new(input);
+        assert_eq!(sections.lines::<u8, Vec<_>>("first"), Ok(vec![1]));
+        let err = sections.lines::<u8, Vec<_>>("second").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "in `second` at line 4: invalid digit found in string"
+        );
+        assert_eq!(sections.finish(), Err(SectionError::Unexpected { line: 6 }));
+
+        let mut sections = Sections::new("1\n");
+        assert_eq!(sections.parse::<u8>("first"), Ok(1));
+        assert_eq!(
+            sections.parse::<u8>("second"),
+            Err(SectionError::Missing { section: "second" })
+        );
+    }
+
+    #[test]
+    fn test_nested_error() {
+        #[derive(Debug)]
+        struct Block;
+
+        impl std::str::FromStr for Block {
+            type Err = crate::nom::Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Err(crate::nom::Error::new(s, &s[2..], "`:`"))
+            }
+        }
+
+        let mut sections = Sections::new("a\n\nb\nc\n");
+        assert!(sections.parse::<String>("first").is_ok());
+        match sections.parse::<Block>("block").unwrap_err() {
+            SectionError::Parse { line, message, .. } => {
+                assert_eq!(line, 4);
+                assert!(message.contains("4 | c"), "{message}");
+            }
+            err => panic!("unexpected {err:?}"),
+        }
+    }
+
+    #[test]
+    fn test_writer() {
+        struct Input;
+
+        impl fmt::Display for Input {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let mut writer = SectionWriter::new(f);
+                writer.whole(&"1,2")?;
+                writer.lines([&3 as &dyn fmt::Display, &4])?;
+                writer.each([&"5 6\n" as &dyn fmt::Display, &"7 8"])
+            }
+        }
+
+        assert_eq!(Input.to_string(), "1,2\n\n3\n4\n\n5 6\n\n7 8\n");
+    }
+}
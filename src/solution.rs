@@ -0,0 +1,282 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Every solver, registered by `#[macros::solution(year = …, day = …, part
+//! = …)]` on its `solve` function.
+//!
+//! The attribute submits a [`Solution`] through [`inventory`], so a runner
+//! finds solvers with [`all`] and [`find`] instead of a hand-written list of
+//! days. Inputs are read from the `inputs/` directory next to the solver's
+//! source file, the same files its tests use.
+//!
+//! Parsing and solving are kept apart so that they can be timed separately:
+//! [`Solution::prepare`] parses and returns the rest of the work as a
+//! [`Solver`].
+//!
+//! The attribute also writes a test that the solver is registered once, from
+//! its own file, and that every one of its inputs parses through the
+//! registry, except those listed in `invalid(…)`, which must be rejected.
+
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use anyhow::Context;
+#[doc(hidden)]
+pub use inventory;
+
+use crate::params::Params;
+use crate::Answer;
+
+/// A parsed input, waiting to be solved.
+pub type Solver<'a> = Box<dyn FnOnce() -> anyhow::Result<Answer> + 'a>;
+
+/// Parse an input and apply `key=value` parameter overrides.
+pub type Prepare = for<'a> fn(&'a str, &[(String, String)]) -> anyhow::Result<Solver<'a>>;
+
+/// One registered part of one day.
+#[derive(Debug)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// The absolute path of the file defining the solver.
+    pub file: &'static str,
+    pub prepare: Prepare,
+}
+
+inventory::collect!(Solution);
+
+impl Solution {
+    /// The path of `inputs/<name>` next to the solver.
+    pub fn input_path(&self, name: &str) -> PathBuf {
+        let mut path = PathBuf::from(self.file);
+        path.pop();
+        path.push("inputs");
+        path.push(name);
+        path
+    }
+
+    /// The names of the inputs next to the solver: every file in `inputs/`
+    /// without an extension, so `.params` files are left out.
+    pub fn inputs(&self) -> Vec<String> {
+        let Ok(dir) = std::fs::read_dir(self.input_path("")) else {
+            return Vec::new();
+        };
+        let mut names = dir
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_none())
+            .filter_map(|path| Some(path.file_name()?.to_str()?.to_owned()))
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Parse and solve `input`.
+    pub fn run(&self, input: &str, params: &[(String, String)]) -> anyhow::Result<Answer> {
+        (self.prepare)(input, params)?()
+    }
+
+    /// The pairs of `inputs/<name>.params`, or none if the input has no
+    /// such file.
+    pub fn input_params(&self, name: &str) -> anyhow::Result<Vec<(String, String)>> {
+        let path = self.input_path(&format!("{name}.params"));
+        match std::fs::read_to_string(&path) {
+            Ok(text) => crate::params::parse_pairs(&text)
+                .with_context(|| format!("reading {}", path.display())),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(err).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    /// Read `inputs/<name>` and solve it with the input's own `.params`,
+    /// overridden by `params`.
+    pub fn run_input(&self, name: &str, params: &[(String, String)]) -> anyhow::Result<Answer> {
+        let path = self.input_path(name);
+        let input = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading {}", path.display()))?;
+        let mut pairs = self.input_params(name)?;
+        pairs.extend_from_slice(params);
+        self.run(&input, &pairs)
+    }
+}
+
+/// Every registered solution, ordered by year, day and part.
+pub fn all() -> Vec<&'static Solution> {
+    let mut all = inventory::iter::<Solution>.into_iter().collect::<Vec<_>>();
+    all.sort_by_key(|s| (s.year, s.day, s.part));
+    all
+}
+
+/// The solution for one part of one day, if it has been registered.
+pub fn find(year: u16, day: u8, part: u8) -> Option<&'static Solution> {
+    inventory::iter::<Solution>
+        .into_iter()
+        .find(|s| (s.year, s.day, s.part) == (year, day, part))
+}
+
+/// Check that `year`, `day` and `part` are registered once, by `file`, and
+/// that its inputs parse unless they are `invalid`; the test written by
+/// `#[macros::solution]`.
+#[doc(hidden)]
+pub fn check_registration(year: u16, day: u8, part: u8, file: &str, invalid: &[&str]) {
+    let found = inventory::iter::<Solution>
+        .into_iter()
+        .filter(|s| (s.year, s.day, s.part) == (year, day, part))
+        .collect::<Vec<_>>();
+    let [solution] = found[..] else {
+        panic!(
+            "{year} day {day} part {part} is registered {} times",
+            found.len()
+        );
+    };
+    assert_eq!(solution.file, file);
+
+    let inputs = solution.inputs();
+    for name in invalid {
+        assert!(inputs.iter().any(|i| i == name), "no input named {name:?}");
+    }
+    for name in inputs {
+        let path = solution.input_path(&name);
+        let input = std::fs::read_to_string(&path).unwrap();
+        let params = solution.input_params(&name).unwrap();
+        match (solution.prepare)(&input, &params).map(drop) {
+            Ok(_) if invalid.contains(&name.as_str()) => {
+                panic!("{name} parsed; it is listed as invalid")
+            }
+            Err(err) if !invalid.contains(&name.as_str()) => panic!("{name}: {err:#}"),
+            _ => {}
+        }
+    }
+}
+
+/// Parse `s` and build the parameters from `pairs`, leaving `f` to be
+/// called; the body of every registered [`Solution::prepare`]. Solvers
+/// without parameters take `()`, which rejects any override.
+#[doc(hidden)]
+pub fn prepare<'a, S, I, P, O>(
+    s: S,
+    pairs: &[(String, String)],
+    f: impl FnOnce(I, P) -> O + 'a,
+) -> anyhow::Result<Solver<'a>>
+where
+    I: TryFrom<S> + 'a,
+    I::Error: Display,
+    P: Params + 'a,
+    O: IntoAnswer,
+{
+    let params = crate::params::from_pairs::<P, _, _>(pairs.iter().map(|(k, v)| (k, v)))?;
+    let input = I::try_from(s).map_err(|err| anyhow!("{err}"))?;
+    Ok(Box::new(move || f(input, params).into_answer()))
+}
+
+/// What a solver may return: anything convertible to an [`Answer`], or a
+/// [`Result`] of one for solvers that can fail after the input is parsed.
+pub trait IntoAnswer {
+    fn into_answer(self) -> anyhow::Result<Answer>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for T {
+    fn into_answer(self) -> anyhow::Result<Answer> {
+        Ok(self.into())
+    }
+}
+
+impl<T: Into<Answer>, E: Into<anyhow::Error>> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> anyhow::Result<Answer> {
+        self.map(Into::into).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Number(usize);
+
+    impl TryFrom<&str> for Number {
+        type Error = std::num::ParseIntError;
+
+        fn try_from(s: &str) -> Result<Self, Self::Error> {
+            s.parse().map(Self)
+        }
+    }
+
+    fn double(input: Number) -> usize {
+        input.0 * 2
+    }
+
+    inventory::submit! {
+        Solution {
+            year: 2015,
+            day: 1,
+            part: 2,
+            file: file!(),
+            prepare: |s, pairs| prepare(s.trim(), pairs, |input, ()| double(input)),
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        let solution = find(2015, 1, 2).unwrap();
+        assert_eq!(all().first().map(|s| s.part), Some(2));
+        assert_eq!(solution.run("21\n", &[]).unwrap(), Answer::Int(42));
+        assert!(find(2015, 1, 1).is_none());
+    }
+
+    #[test]
+    fn test_errors() {
+        let solution = find(2015, 1, 2).unwrap();
+        let err = solution.run("twenty-one", &[]).unwrap_err();
+        assert_eq!(err.to_string(), "invalid digit found in string");
+        let pairs = [("steps".to_owned(), "3".to_owned())];
+        let err = solution.run("21", &pairs).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown parameter \"steps\"; this solution takes none"
+        );
+        assert!(solution
+            .run_input("missing", &[])
+            .unwrap_err()
+            .to_string()
+            .starts_with("reading "));
+    }
+
+    #[test]
+    fn test_prepare() {
+        let solution = find(2015, 1, 2).unwrap();
+        let solve = (solution.prepare)("4", &[]).unwrap();
+        assert_eq!(solve().unwrap(), Answer::Int(8));
+        assert!(solution.inputs().is_empty());
+    }
+
+    #[test]
+    fn test_fallible_solver() {
+        let double_nonzero = |input: Number, ()| match input.0 {
+            0 => Err(anyhow!("nothing to double")),
+            n => Ok(n * 2),
+        };
+        let solve = prepare("4", &[], double_nonzero).unwrap();
+        assert_eq!(solve().unwrap(), Answer::Int(8));
+        let solve = prepare("0", &[], double_nonzero).unwrap();
+        assert_eq!(solve().unwrap_err().to_string(), "nothing to double");
+    }
+}
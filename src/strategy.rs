@@ -0,0 +1,160 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+//! [`proptest`] strategies for the shared types, and [`proptest_roundtrip!`]
+//! for checking that a type's `Display` output parses back to the same
+//! value.
+//!
+//! [`proptest_roundtrip!`] is the randomised companion of
+//! `macros::test_roundtrip!`: rather than a fixed list of strings it starts
+//! from generated values, so it also catches `Display` impls that write
+//! something the parser never accepts.
+
+use std::fmt::Debug;
+use std::ops::Range;
+use std::ops::RangeInclusive;
+
+use euclid::Point2D;
+pub use proptest;
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use crate::matrix::Matrix;
+use crate::matrix::Position;
+use crate::matrix::RowCol;
+
+/// A [`Position`] with its row in `rows` and its column in `cols`.
+pub fn position(rows: Range<usize>, cols: Range<usize>) -> impl Strategy<Value = Position> {
+    (rows, cols).prop_map(|(row, col)| Position::new(row, col))
+}
+
+/// A [`RowCol`] with its row in `rows` and its column in `cols`.
+pub fn rowcol(rows: Range<usize>, cols: Range<usize>) -> impl Strategy<Value = RowCol> {
+    (rows, cols).prop_map(|(row, col)| RowCol::new(row, col))
+}
+
+/// A point in any coordinate space from strategies for each axis.
+pub fn point2d<T, U>(
+    x: impl Strategy<Value = T>,
+    y: impl Strategy<Value = T>,
+) -> impl Strategy<Value = Point2D<T, U>>
+where
+    T: Debug,
+    U: Debug,
+{
+    (x, y).prop_map(|(x, y)| Point2D::new(x, y))
+}
+
+/// A non-empty rectangular [`Matrix`] of `tile`s whose depth and width are
+/// drawn from `rows` and `cols`.
+pub fn matrix<T, S>(
+    tile: S,
+    rows: RangeInclusive<usize>,
+    cols: RangeInclusive<usize>,
+) -> impl Strategy<Value = Matrix<T>>
+where
+    T: Debug + Clone,
+    S: Strategy<Value = T> + Clone,
+{
+    assert!(
+        *rows.start() > 0 && *cols.start() > 0,
+        "a matrix has at least one tile"
+    );
+    (rows, cols).prop_flat_map(move |(rows, cols)| {
+        vec(tile.clone(), rows * cols).prop_map(move |tiles| Matrix::new(cols, tiles))
+    })
+}
+
+/// A `start..=end` range with both ends drawn from `bound`, so that
+/// `start <= end`.
+pub fn range_inclusive<T>(
+    bound: impl Strategy<Value = T> + Clone,
+) -> impl Strategy<Value = RangeInclusive<T>>
+where
+    T: Debug + Clone + PartialOrd,
+{
+    (bound.clone(), bound).prop_map(|(a, b)| if b < a { b..=a } else { a..=b })
+}
+
+/// Generate a property test per type asserting that `parse(x.to_string())`
+/// gives back `x` for values drawn from the given strategy.
+///
+/// ```ignore
+/// aoc::proptest_roundtrip! {
+///     instruction: super::Instruction = instruction(),
+///     snailfish: super::Snailfish = snailfish(4),
+/// }
+/// ```
+#[macro_export]
+macro_rules! proptest_roundtrip {
+    ($($name:ident: $ty:ty = $strategy:expr),+ $(,)?) => {
+        $crate::strategy::proptest::proptest! {
+            $(
+                #[test]
+                fn $name(value in $strategy) {
+                    let value: $ty = value;
+                    let s = value.to_string();
+                    let parsed = s.parse::<$ty>();
+                    $crate::strategy::proptest::prop_assert!(
+                        parsed.is_ok(),
+                        "{:?} did not parse: {}",
+                        s,
+                        parsed.as_ref().err().map(ToString::to_string).unwrap_or_default(),
+                    );
+                    $crate::strategy::proptest::prop_assert_eq!(parsed.ok(), Some(value), "displayed as {:?}", s);
+                }
+            )+
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_position(pos in position(2..5, 0..3)) {
+            prop_assert!((2..5).contains(&pos.x));
+            prop_assert!((0..3).contains(&pos.y));
+        }
+
+        #[test]
+        fn test_matrix(m in matrix(0u8..=9, 1..=4, 2..=3)) {
+            prop_assert!((1..=4).contains(&m.depth()));
+            prop_assert!((2..=3).contains(&m.width()));
+            prop_assert_eq!(m.len(), m.depth() * m.width());
+        }
+
+        #[test]
+        fn test_range_inclusive(r in range_inclusive(-5i8..5)) {
+            prop_assert!(r.start() <= r.end());
+        }
+
+        #[test]
+        fn test_matrix_grid_roundtrip(m in matrix(0u8..=9, 1..=6, 1..=6)) {
+            let grid = m
+                .rows()
+                .map(|row| m.iter_row(row).map(|(_, &d)| (b'0' + d) as char).collect::<String>())
+                .collect::<Vec<_>>()
+                .join("\n");
+            prop_assert_eq!(Matrix::try_from_grid(&grid, crate::digit), Ok(m));
+        }
+    }
+}
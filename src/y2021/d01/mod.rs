@@ -24,3 +24,8 @@ pub(crate) mod part2;
 #[derive(derive_more::Deref, macros::FromLines)]
 #[from_lines(usize)]
 struct Ocean(Vec<usize>);
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Ocean; [example, live]);
+}
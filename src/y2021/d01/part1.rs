@@ -88,6 +88,7 @@
 //!
 //! How many measurements are larger than the previous measurement?
 
+#[macros::solution(year = 2021, day = 1, part = 1)]
 fn solve(input: super::Ocean) -> usize {
     input.windows(2).filter(|s| s[0] < s[1]).count()
 }
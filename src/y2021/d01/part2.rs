@@ -69,6 +69,7 @@
 //! Consider sums of a three-measurement sliding window. How many sums are
 //! larger than the previous sum?
 
+#[macros::solution(year = 2021, day = 1, part = 2)]
 fn solve(input: super::Ocean) -> usize {
     input.windows(4).filter(|s| s[0] < s[3]).count()
 }
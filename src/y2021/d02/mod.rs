@@ -33,5 +33,10 @@ enum Direction {
 
 #[derive(derive_more::IntoIterator, macros::FromLines)]
 #[into_iterator(ref)]
-#[from_lines(Direction)]
+#[from_lines(Direction, at_line)]
 struct DirectionList(Vec<Direction>);
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::DirectionList; [example, live]);
+}
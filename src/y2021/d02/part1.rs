@@ -63,6 +63,7 @@
 
 use super::Direction;
 
+#[macros::solution(year = 2021, day = 2, part = 1)]
 fn solve(input: super::DirectionList) -> usize {
     let mut horizontal = 0;
     let mut depth = 0;
@@ -59,6 +59,7 @@
 
 use super::Direction;
 
+#[macros::solution(year = 2021, day = 2, part = 2)]
 fn solve(input: super::DirectionList) -> usize {
     let mut aim = 0;
     let mut horizontal = 0;
@@ -64,6 +64,8 @@ impl Not for Line {
 }
 
 impl<'a> From<&'a Line> for usize {
+    // `usize: Add<&Bit>` exists but `usize: Sum<&Bit>` does not.
+    #[allow(clippy::unnecessary_fold)]
     fn from(v: &'a Line) -> Self {
         v.into_iter().fold(0, Add::add)
     }
@@ -30,3 +30,8 @@ use line::Line;
 #[into_iterator(ref)]
 #[from_lines(Line)]
 struct Lines(Vec<Line>);
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Lines; [example, live]);
+}
@@ -76,6 +76,7 @@
 use super::bit::Bit;
 use super::line::Line;
 
+#[macros::solution(year = 2021, day = 3, part = 1)]
 fn solve(input: super::Lines) -> usize {
     let len = input[0].len();
     let mut zeros = vec![0; len];
@@ -94,6 +94,7 @@
 
 use super::line::Line;
 
+#[macros::solution(year = 2021, day = 3, part = 2)]
 fn solve(input: super::Lines) -> usize {
     let bits = input[0].len();
     let mut o2 = Lines::from_iter(&input);
@@ -23,6 +23,7 @@ use std::fmt::Debug;
 
 use aoc::matrix::Matrix;
 use aoc::matrix::Position;
+use aoc::matrix::ShapeError;
 
 #[derive(Debug, Default, Clone)]
 pub struct Tile {
@@ -49,10 +50,14 @@ pub struct Board {
 }
 
 impl Board {
-    pub fn new<I: IntoIterator<Item = V>, V: IntoIterator<Item = Tile>>(tiles: I) -> Self {
-        let matrix = tiles.into_iter().collect::<Matrix<_>>();
+    pub fn try_new<I, V>(tiles: I) -> Result<Self, ShapeError>
+    where
+        I: IntoIterator<Item = V>,
+        V: IntoIterator<Item = Tile>,
+    {
+        let matrix = Matrix::try_from_rows(tiles)?;
         let value_map = matrix.iter().map(|(pos, tile)| (tile.value, pos)).collect();
-        Self { matrix, value_map }
+        Ok(Self { matrix, value_map })
     }
 
     pub fn mark(&mut self, value: u8) -> Option<Position> {
@@ -24,11 +24,22 @@ pub(crate) mod part2;
 pub(crate) mod matrix;
 mod parser;
 
-#[derive(Debug, Clone, macros::TryFromStr)]
+#[derive(Debug, Clone, macros::FromSections, macros::TryFromStr)]
 pub struct Game {
-    pub pulls: Vec<u8>,
+    pub pulls: Pulls,
 
+    #[section(each)]
     pub boards: Vec<matrix::Board>,
 }
 
-::aoc::derive_FromStr_for_nom!(Game, parser::game);
+/// The numbers in the order they are drawn.
+#[derive(Debug, Clone)]
+pub struct Pulls(pub Vec<u8>);
+
+::aoc::derive_FromStr_for_nom!(Pulls, parser::pulls);
+::aoc::derive_FromStr_for_nom!(matrix::Board, parser::board);
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Game; [example, live]);
+}
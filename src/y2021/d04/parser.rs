@@ -18,22 +18,21 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use nom::bytes::complete::tag;
+use aoc::nom::separated_ints;
+use aoc::nom::IResult;
 use nom::character::complete::line_ending;
 use nom::character::complete::space0;
 use nom::character::complete::space1;
 use nom::character::complete::u8;
-use nom::combinator::eof;
+use nom::combinator::all_consuming;
 use nom::combinator::map;
-use nom::multi::count;
+use nom::combinator::map_res;
+use nom::combinator::verify;
 use nom::multi::separated_list1;
 use nom::sequence::preceded;
-use nom::sequence::separated_pair;
-use nom::sequence::terminated;
-use nom::IResult;
 
-fn pull_list(s: &str) -> IResult<&str, Vec<u8>> {
-    terminated(separated_list1(tag(","), u8), line_ending)(s)
+pub(super) fn pulls(s: &str) -> IResult<&str, super::Pulls> {
+    map(all_consuming(separated_ints(",")), super::Pulls)(s)
 }
 
 fn tile(s: &str) -> IResult<&str, super::matrix::Tile> {
@@ -41,26 +40,13 @@ fn tile(s: &str) -> IResult<&str, super::matrix::Tile> {
 }
 
 fn bingo_board_line(s: &str) -> IResult<&str, Vec<super::matrix::Tile>> {
-    preceded(
-        space0,
-        terminated(separated_list1(space1, tile), line_ending),
-    )(s)
+    preceded(space0, separated_list1(space1, tile))(s)
 }
 
-fn bingo_board(s: &str) -> IResult<&str, super::matrix::Board> {
-    map(count(bingo_board_line, 5), super::matrix::Board::new)(s)
-}
-
-pub(super) fn game(s: &str) -> IResult<&str, super::Game> {
-    map(
-        terminated(
-            separated_pair(
-                pull_list,
-                line_ending,
-                separated_list1(line_ending, bingo_board),
-            ),
-            eof,
-        ),
-        |(pulls, boards)| super::Game { pulls, boards },
-    )(s)
+pub(super) fn board(s: &str) -> IResult<&str, super::matrix::Board> {
+    let rows = verify(
+        separated_list1(line_ending, bingo_board_line),
+        |rows: &Vec<_>| rows.len() == 5,
+    );
+    map_res(all_consuming(rows), super::matrix::Board::try_new)(s)
 }
@@ -118,9 +118,11 @@
 //! To guarantee victory against the giant squid, figure out which board will
 //! win first. What will your final score be if you choose that board?
 
+#[macros::solution(year = 2021, day = 4, part = 1)]
 pub fn solve(mut input: super::Game) -> usize {
     input
         .pulls
+        .0
         .into_iter()
         .find_map(|pull| {
             input.boards.iter_mut().find_map(|board| {
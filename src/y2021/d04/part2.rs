@@ -37,10 +37,12 @@
 
 use super::matrix::Board;
 
+#[macros::solution(year = 2021, day = 4, part = 2)]
 pub fn solve(input: super::Game) -> usize {
     let mut bingo = Bingo(input.boards);
     input
         .pulls
+        .0
         .into_iter()
         .find_map(|pull| bingo.mark(pull))
         .unwrap()
@@ -23,7 +23,7 @@ use super::line::Line;
 
 #[derive(Debug, derive_more::IntoIterator, macros::FromLines)]
 #[into_iterator(owned, ref)]
-#[from_lines(Line)]
+#[from_lines(Line, at_line)]
 pub(super) struct SolutionBuilder(Vec<Line>);
 
 impl SolutionBuilder {
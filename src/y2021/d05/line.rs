@@ -39,7 +39,7 @@ impl Line {
     }
 
     fn is_valid(&self) -> bool {
-        self.0 != self.1 && (self.angle() % 45) == 0
+        self.0 != self.1 && self.angle().is_multiple_of(45)
     }
 
     fn incr(self) -> Line {
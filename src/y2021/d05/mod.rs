@@ -31,3 +31,8 @@ use euclid::Point2D;
 struct D05;
 
 type Coordinate = Point2D<usize, D05>;
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::builder::SolutionBuilder; [example, live]);
+}
@@ -18,7 +18,7 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use aoc::nom::usize;
+use aoc::nom::coord2;
 use euclid::point2;
 use nom::bytes::complete::tag;
 use nom::combinator::map;
@@ -29,9 +29,7 @@ use super::line::Line;
 use super::Coordinate;
 
 fn coordinate(s: &str) -> IResult<&str, Coordinate> {
-    map(separated_pair(usize, tag(","), usize), |(x, y)| {
-        point2(x, y)
-    })(s)
+    map(coord2(","), |(x, y)| point2(x, y))(s)
 }
 
 pub(super) fn line(s: &str) -> IResult<&str, Line> {
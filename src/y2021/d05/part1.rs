@@ -77,6 +77,7 @@
 
 use super::builder::SolutionBuilder;
 
+#[macros::solution(year = 2021, day = 5, part = 1)]
 fn solve(input: SolutionBuilder) -> usize {
     input.board(|l| !l.is_diagonal()).overlaps()
 }
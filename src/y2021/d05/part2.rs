@@ -51,6 +51,7 @@
 
 use super::builder::SolutionBuilder;
 
+#[macros::solution(year = 2021, day = 5, part = 2)]
 fn solve(input: SolutionBuilder) -> usize {
     input.board(|_| true).overlaps()
 }
@@ -18,24 +18,13 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use ::aoc::nom::usize;
-use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::combinator::map;
-use nom::sequence::separated_pair;
-use nom::IResult;
+use aoc::gen::Rng;
 
-fn step(s: &str) -> IResult<&str, super::Step> {
-    alt((
-        map(tag("U"), |_| super::Step::Up),
-        map(tag("D"), |_| super::Step::Down),
-        map(tag("L"), |_| super::Step::Left),
-        map(tag("R"), |_| super::Step::Right),
-    ))(s)
-}
-
-pub(super) fn instruction(s: &str) -> IResult<&str, super::Instruction> {
-    map(separated_pair(step, tag(" "), usize), |(step, distance)| {
-        super::Instruction { step, distance }
-    })(s)
+/// 300 lanternfish timers from 1 to 5 at scale 1, like a live input.
+#[macros::generator(year = 2021, day = 6)]
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let timers = (0..300 * scale)
+        .map(|_| (1 + rng.below(5)).to_string())
+        .collect::<Vec<_>>();
+    timers.join(",") + "\n"
 }
@@ -18,33 +18,16 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+pub(crate) mod gen;
 pub(crate) mod part1;
 pub(crate) mod part2;
+#[cfg(test)]
+mod reference;
 
-use std::str::FromStr;
-
-use anyhow::Context;
-use anyhow::Error;
-use anyhow::Result;
-
-#[derive(macros::FromIterator, macros::TryFromStr)]
-#[from_iterator(u8)]
+#[derive(macros::FromLines)]
+#[from_lines(u8, sep = ",")]
 pub struct Ocean(Vec<u8>);
 
-impl FromStr for Ocean {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.lines()
-            .next()
-            .context("readling input line")?
-            .split(',')
-            .map(str::parse)
-            .collect::<Result<_, _>>()
-            .context("parsing input values")
-    }
-}
-
 impl Ocean {
     pub fn count(&self, days: usize) -> usize {
         let mut lanterns = [0; 9];
@@ -63,3 +46,22 @@ impl Ocean {
         lanterns.into_iter().sum()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    fn count(ocean: super::Ocean, days: usize) -> usize {
+        ocean.count(days)
+    }
+
+    ::aoc::tests_for_problem!(count, {
+        #[input(example)]
+        example_18_days(18) => 26,
+        #[input(example)]
+        example_80_days(80) => 5934,
+    });
+}
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Ocean; [example, live]);
+}
@@ -95,14 +95,25 @@
 // Find a way to simulate lanternfish. How many lanternfish would there be after
 // 80 days?
 
-pub fn solve(input: super::Ocean) -> usize {
-    input.count(80)
+::aoc::params! {
+    pub struct Params {
+        /// Days to simulate.
+        days: usize = 80,
+    }
+}
+
+#[macros::solution(year = 2021, day = 6, part = 1)]
+pub fn solve(input: super::Ocean, params: Params) -> usize {
+    input.count(params.days)
 }
 
 #[cfg(test)]
 mod tests {
-    ::aoc::tests_for_problem!(super::solve, {
+    ::aoc::tests_for_problem!(params super::solve, {
         example => 5934,
         live => 380758,
+        #[input(example)]
+        #[params(eighteen_days)]
+        example_18_days => 26,
     });
 }
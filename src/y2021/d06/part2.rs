@@ -25,13 +25,21 @@
 // After 256 days in the example above, there would be a total of 26984457539
 // lanternfish!
 
-pub fn solve(input: super::Ocean) -> usize {
-    input.count(256)
+::aoc::params! {
+    pub struct Params {
+        /// Days to simulate.
+        days: usize = 256,
+    }
+}
+
+#[macros::solution(year = 2021, day = 6, part = 2)]
+pub fn solve(input: super::Ocean, params: Params) -> usize {
+    input.count(params.days)
 }
 
 #[cfg(test)]
 mod tests {
-    ::aoc::tests_for_problem!(super::solve, {
+    ::aoc::tests_for_problem!(params super::solve, {
         example => 26984457539,
         live => 1710623015163,
     });
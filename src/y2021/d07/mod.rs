@@ -21,31 +21,11 @@
 pub(crate) mod part1;
 pub(crate) mod part2;
 
-use std::str::FromStr;
-
-use anyhow::Context;
-use anyhow::Error;
-use anyhow::Result;
-
-#[derive(derive_more::IntoIterator, macros::FromIterator, macros::TryFromStr)]
+#[derive(derive_more::IntoIterator, macros::FromLines)]
 #[into_iterator(ref)]
-#[from_iterator(usize)]
+#[from_lines(usize, sep = ",")]
 pub struct Ocean(Vec<usize>);
 
-impl FromStr for Ocean {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.lines()
-            .next()
-            .context("reading input line")?
-            .split(',')
-            .map(str::parse)
-            .collect::<Result<_, _>>()
-            .context("parsing input values")
-    }
-}
-
 impl Ocean {
     pub fn solve<F>(&self, mut cost: F) -> usize
     where
@@ -71,9 +51,10 @@ impl Ocean {
 }
 
 fn absub(a: usize, b: usize) -> usize {
-    if b <= a {
-        a - b
-    } else {
-        b - a
-    }
+    a.abs_diff(b)
+}
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Ocean; [example, live]);
 }
@@ -68,6 +68,7 @@
 // Determine the horizontal position that the crabs can align to using the least
 // fuel possible. How much fuel must they spend to align to that position?
 
+#[macros::solution(year = 2021, day = 7, part = 1)]
 pub fn solve(input: super::Ocean) -> usize {
     input.solve(|d| d)
 }
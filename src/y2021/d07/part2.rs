@@ -49,6 +49,7 @@
 // fuel possible so they can make you an escape route! How much fuel must they
 // spend to align to that position?
 
+#[macros::solution(year = 2021, day = 7, part = 2)]
 pub fn solve(input: super::Ocean) -> usize {
     input.solve(|d| (d * (d + 1)) / 2)
 }
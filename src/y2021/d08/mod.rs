@@ -51,7 +51,9 @@ pub type Digit = BTreeSet<Segment>;
 pub struct Signal([Digit; parser::SIGNAL_DIGITS]);
 
 impl Signal {
-    pub fn consume(self) -> HashMap<Digit, usize> {
+    /// Which digit each pattern shows, or `None` if the patterns are not the
+    /// ten digits of some wiring.
+    pub fn decode(&self) -> Option<HashMap<Digit, usize>> {
         //  aaaa
         // b    c
         // b    c
@@ -71,35 +73,32 @@ impl Signal {
         // d8 = abcdefg
         // d9 = abcdfg
 
-        let remain = self.into_iter().collect::<Vec<_>>();
-        let (d1, remain) = split_iter_only_remain(remain, |d| d.len() == 2);
-        let (d4, remain) = split_iter_only_remain(remain, |d| d.len() == 4);
-        let (d7, remain) = split_iter_only_remain(remain, |d| d.len() == 3);
-        let (d8, remain) = split_iter_only_remain(remain, |d| d.len() == 7);
+        let remain = self.0.to_vec();
+        let (d1, remain) = split_iter_only_remain(remain, |d| d.len() == 2)?;
+        let (d4, remain) = split_iter_only_remain(remain, |d| d.len() == 4)?;
+        let (d7, remain) = split_iter_only_remain(remain, |d| d.len() == 3)?;
+        let (d8, remain) = split_iter_only_remain(remain, |d| d.len() == 7)?;
 
-        let (len5, len6) = split_iter(remain, |d| d.len() == 5);
+        let (len5, len6): (Vec<_>, Vec<_>) = split_iter(remain, |d| d.len() == 5);
 
-        // d2, d3, d5
-        assert_eq!(3, len5.len());
+        // d2, d3, d5 and d0, d6, d9
+        if len5.len() != 3 || len6.len() != 3 {
+            return None;
+        }
 
         // d3 is a superset of d1
-        let (d3, len5) = split_iter_only_remain(len5, |d| d.is_superset(&d1));
-        assert_eq!(2, len5.len());
-
-        // d0, d6, d9
-        assert_eq!(3, len6.len());
+        let (d3, len5) = split_iter_only_remain(len5, |d| d.is_superset(&d1))?;
 
         // d9 is a superset of d4
-        let (d9, len6) = split_iter_only_remain(len6, |d| d.is_superset(&d4));
-        assert_eq!(2, len6.len());
+        let (d9, len6) = split_iter_only_remain(len6, |d| d.is_superset(&d4))?;
 
         // d9 is a superset of d5
-        let (d5, d2) = split_iter_only(len5, |d| d.is_subset(&d9));
+        let (d5, d2) = split_iter_only(len5, |d| d.is_subset(&d9))?;
 
         // d0 is a superset of d1
-        let (d0, d6) = split_iter_only(len6, |d| d.is_superset(&d1));
+        let (d0, d6) = split_iter_only(len6, |d| d.is_superset(&d1))?;
 
-        [
+        let digits = [
             (d0, 0),
             (d1, 1),
             (d2, 2),
@@ -112,7 +111,8 @@ impl Signal {
             (d9, 9),
         ]
         .into_iter()
-        .collect()
+        .collect();
+        Some(digits)
     }
 }
 
@@ -134,18 +134,26 @@ pub struct Line {
 
 ::aoc::derive_FromStr_for_nom!(Line, parser::line);
 
+impl Line {
+    /// The output read with the wiring of the signal, or `None` if the
+    /// signal does not decode or the output shows some other pattern.
+    pub fn value(&self) -> Option<usize> {
+        let digit_map = self.signal.decode()?;
+        self.output
+            .0
+            .iter()
+            .try_fold(0, |prev, next| Some(prev * 10 + digit_map.get(next)?))
+    }
+}
+
 impl From<Line> for usize {
     fn from(value: Line) -> usize {
-        let digit_map = value.signal.consume();
-        value
-            .output
-            .into_iter()
-            .fold(0, |prev, next| (prev * 10) + digit_map.get(&next).unwrap())
+        value.value().expect("checked when parsed")
     }
 }
 
 #[derive(Debug, Clone, derive_more::IntoIterator, macros::FromLines)]
-#[from_lines(Line)]
+#[from_lines(Line, at_line)]
 pub struct Lines(Vec<Line>);
 
 fn split_iter<I, F>(iter: I, f: F) -> (I, I)
@@ -156,40 +164,44 @@ where
     iter.into_iter().partition(f)
 }
 
-fn split_iter_only_remain<I, F>(iter: I, f: F) -> (I::Item, I)
+fn split_iter_only_remain<I, F>(iter: I, f: F) -> Option<(I::Item, I)>
 where
     I: IntoIterator + Default + Extend<I::Item>,
     F: FnMut(&I::Item) -> bool,
 {
     let (left, right) = split_iter(iter, f);
-    (only(left), right)
+    Some((only(left)?, right))
 }
 
-fn split_iter_only<I, F>(iter: I, mut f: F) -> (I::Item, I::Item)
+/// The one of exactly two items that matches `f`, then the other.
+fn split_iter_only<I, F>(iter: I, mut f: F) -> Option<(I::Item, I::Item)>
 where
     I: IntoIterator,
     F: FnMut(&I::Item) -> bool,
 {
     let mut iter = iter.into_iter();
-    let first = iter.next().unwrap();
-    let second = iter.next().unwrap();
-    assert!(iter.next().is_none());
-    let first_match = (f)(&first);
-    let second_match = (f)(&second);
-    assert_ne!(first_match, second_match);
-    if first_match {
-        (first, second)
-    } else {
-        (second, first)
+    let first = iter.next()?;
+    let second = iter.next()?;
+    if iter.next().is_some() {
+        return None;
+    }
+    match ((f)(&first), (f)(&second)) {
+        (true, false) => Some((first, second)),
+        (false, true) => Some((second, first)),
+        _ => None,
     }
 }
 
-fn only<I>(iter: I) -> I::Item
+fn only<I>(iter: I) -> Option<I::Item>
 where
     I: IntoIterator,
 {
     let mut iter = iter.into_iter();
-    let res = iter.next().unwrap();
-    assert!(iter.next().is_none());
-    res
+    let res = iter.next()?;
+    iter.next().is_none().then_some(res)
+}
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Lines; [example, live]);
 }
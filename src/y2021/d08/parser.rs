@@ -18,11 +18,14 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use itertools::Itertools;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::line_ending;
 use nom::character::complete::one_of;
 use nom::combinator::eof;
+use nom::combinator::map;
+use nom::combinator::verify;
 use nom::multi::count;
 use nom::multi::many_m_n;
 use nom::sequence::terminated;
@@ -49,15 +52,13 @@ fn segment(s: &str) -> IResult<&str, super::Segment> {
 }
 
 fn digit(s: &str) -> IResult<&str, super::Digit> {
-    let (s, segments) = many_m_n(1, SEGMENTS.len(), segment)(s)?;
-    let len_before_collect = segments.len();
-    let digit = segments.into_iter().collect::<super::Digit>();
-    assert_eq!(
-        len_before_collect,
-        digit.len(),
-        "digit has duplicate segments"
-    );
-    Ok((s, digit))
+    // Duplicate segments would collapse when collected, so reject them.
+    map(
+        verify(many_m_n(1, SEGMENTS.len(), segment), |segments: &Vec<_>| {
+            segments.iter().all_unique()
+        }),
+        |segments| segments.into_iter().collect(),
+    )(s)
 }
 
 fn digits<const LEN: usize>(s: &str) -> IResult<&str, [super::Digit; LEN]> {
@@ -78,7 +79,7 @@ fn output_digits(s: &str) -> IResult<&str, super::Output> {
     Ok((s, v.into()))
 }
 
-pub(super) fn line(s: &str) -> IResult<&str, super::Line> {
+fn line_digits(s: &str) -> IResult<&str, super::Line> {
     let (s, signal) = signal_digits(s)?;
     // digits consumes the trailing space
     let (s, _) = tag("| ")(s)?;
@@ -86,10 +87,26 @@ pub(super) fn line(s: &str) -> IResult<&str, super::Line> {
     Ok((s, super::Line::new(signal, output)))
 }
 
+/// A line whose signal decodes and whose output it can read.
+pub(super) fn line(s: &str) -> IResult<&str, super::Line> {
+    verify(line_digits, |line: &super::Line| line.value().is_some())(s)
+}
+
 #[cfg(test)]
 mod test {
     #[test]
     fn test_parse_example_no_panic() {
         nom::multi::many1(super::line)(include_str!("inputs/example")).unwrap();
     }
+
+    #[test]
+    fn test_undecodable() {
+        // two patterns of the length of a one
+        let line = "ab ab abcd abd abcdefg abcde abcdf abdef abcdef abcdeg | ab ab ab ab";
+        assert!(super::line(line).is_err());
+        // an output pattern the signal does not have
+        let line =
+            "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gc";
+        assert!(super::line(line).is_err());
+    }
 }
@@ -114,6 +114,7 @@
 //
 // In the output values, how many times do digits 1, 4, 7, or 8 appear?
 
+#[macros::solution(year = 2021, day = 8, part = 1)]
 pub fn solve(input: super::Lines) -> usize {
     input
         .into_iter()
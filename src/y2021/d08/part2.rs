@@ -78,6 +78,7 @@
 // four-digit output values. What do you get if you add up all of the output
 // values?
 
+#[macros::solution(year = 2021, day = 8, part = 2)]
 pub fn solve(input: super::Lines) -> usize {
     input.into_iter().map(usize::from).sum()
 }
@@ -24,3 +24,8 @@ pub(crate) mod part2;
 pub(crate) mod ocean;
 
 use ocean::Ocean;
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Ocean; [example, live]);
+}
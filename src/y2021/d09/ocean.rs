@@ -20,9 +20,9 @@
 
 use std::collections::BTreeSet;
 use std::collections::VecDeque;
-use std::convert::Infallible;
 use std::str::FromStr;
 
+use aoc::matrix::GridError;
 use aoc::matrix::Matrix;
 use aoc::matrix::Position;
 use aoc::matrix::RelativePosition;
@@ -78,11 +78,9 @@ impl Ocean {
 }
 
 impl FromStr for Ocean {
-    type Err = Infallible;
+    type Err = GridError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(
-            s.lines().map(|l| l.bytes().map(::aoc::chardigit)).collect(),
-        ))
+        Matrix::try_from_grid(s, aoc::digit).map(Self)
     }
 }
@@ -57,6 +57,7 @@
 // Find all of the low points on your heightmap. What is the sum of the risk
 // levels of all low points on your heightmap?
 
+#[macros::solution(year = 2021, day = 9, part = 1)]
 pub fn solve(input: super::Ocean) -> usize {
     input
         .iter_low_points()
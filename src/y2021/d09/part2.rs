@@ -68,6 +68,7 @@
 // What do you get if you multiply together the sizes of the three largest
 // basins?
 
+#[macros::solution(year = 2021, day = 9, part = 2)]
 pub fn solve(input: super::Ocean) -> usize {
     let mut v = input.basins();
     v.sort_unstable();
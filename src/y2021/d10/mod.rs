@@ -21,7 +21,7 @@
 pub(crate) mod part1;
 pub(crate) mod part2;
 
-#[derive(derive_more::IntoIterator, macros::FromLines)]
+#[derive(Debug, derive_more::IntoIterator, macros::FromLines)]
 #[into_iterator(ref)]
 #[from_lines(Line)]
 pub struct Lines(Vec<Line>);
@@ -32,11 +32,23 @@ pub enum Line {
     Invalid(u8),
 }
 
+/// A character that is not one of the eight brackets.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[display(fmt = "expected a bracket; got {found:?}")]
+pub struct ParseError {
+    found: char,
+}
+
+impl std::error::Error for ParseError {}
+
 impl std::str::FromStr for Line {
-    type Err = std::convert::Infallible;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(s.bytes().collect())
+        match s.chars().find(|c| !"()[]{}<>".contains(*c)) {
+            Some(found) => Err(ParseError { found }),
+            None => Ok(s.bytes().collect()),
+        }
     }
 }
 
@@ -79,3 +91,18 @@ impl FromIterator<u8> for Line {
         Incomplete(stack)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_parse_error() {
+        let err = "()\n(x]\n".parse::<super::Lines>().unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.to_string(), "line 2: expected a bracket; got 'x'");
+    }
+}
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Lines; [example, live]);
+}
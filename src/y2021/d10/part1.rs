@@ -95,6 +95,7 @@
 // Find the first illegal character in each corrupted line of the navigation
 // subsystem. What is the total syntax error score for those errors?
 
+#[macros::solution(year = 2021, day = 10, part = 1)]
 pub fn solve(input: super::Lines) -> usize {
     use super::Line::*;
     let mut res = [0; 4];
@@ -81,6 +81,7 @@
 // Find the completion string for each incomplete line, score the completion
 // strings, and sort the scores. What is the middle score?
 
+#[macros::solution(year = 2021, day = 10, part = 2)]
 pub fn solve(input: super::Lines) -> usize {
     let mut scores = input
         .into_iter()
@@ -22,9 +22,9 @@ pub(crate) mod part1;
 pub(crate) mod part2;
 
 use std::collections::VecDeque;
-use std::convert::Infallible;
 use std::str::FromStr;
 
+use aoc::matrix::GridError;
 use aoc::matrix::Matrix;
 use aoc::matrix::Position;
 use aoc::matrix::RelativePosition;
@@ -97,14 +97,10 @@ impl Ocean {
 }
 
 impl FromStr for Ocean {
-    type Err = Infallible;
+    type Err = GridError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(
-            s.lines()
-                .map(|l| l.bytes().map(aoc::chardigit).map(Octopus::from))
-                .collect(),
-        ))
+        Matrix::try_from_grid(s, |c| aoc::digit(c).map(Octopus::from)).map(Self)
     }
 }
 
@@ -130,3 +126,8 @@ impl FlashQueue {
         self.queue.pop_front()
     }
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Ocean; [example, live]);
+}
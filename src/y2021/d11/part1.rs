@@ -339,6 +339,7 @@
 // Given the starting energy levels of the dumbo octopuses in your cavern,
 // simulate 100 steps. How many total flashes are there after 100 steps?
 
+#[macros::solution(year = 2021, day = 11, part = 1)]
 pub fn solve(mut input: super::Ocean) -> usize {
     (0..100).map(move |_| input.tick()).sum()
 }
@@ -66,10 +66,9 @@
 // simultaneously, you should be able to navigate through the cavern. What is
 // the first step during which all octopuses flash?
 
+#[macros::solution(year = 2021, day = 11, part = 2)]
 pub fn solve(mut input: super::Ocean) -> usize {
-    (1..)
-        .find_map(|tick| (input.tick() == 100).then_some(tick))
-        .unwrap()
+    (1..).find(|_| input.tick() == 100).unwrap()
 }
 
 #[cfg(test)]
@@ -22,3 +22,8 @@ pub(crate) mod part1;
 pub(crate) mod part2;
 
 pub(crate) mod ocean;
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::ocean::Ocean; [example_a, example_b, example_c, live]);
+}
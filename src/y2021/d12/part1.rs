@@ -137,6 +137,7 @@
 // How many paths through this cave system are there that visit small caves at
 // most once?
 
+#[macros::solution(year = 2021, day = 12, part = 1)]
 pub fn solve(input: super::ocean::Ocean) -> usize {
     input.paths(false).count()
 }
@@ -71,6 +71,7 @@
 //
 // Given these new rules, how many paths through this cave system are there?
 
+#[macros::solution(year = 2021, day = 12, part = 2)]
 pub fn solve(input: super::ocean::Ocean) -> usize {
     input.paths(true).count()
 }
@@ -31,10 +31,15 @@ use anyhow::anyhow;
 use anyhow::Context;
 use anyhow::Error;
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, derive_more::Display)]
-#[display(fmt = "{_0},{_1}\n")]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 struct Coordinate(usize, usize);
 
+impl Display for Coordinate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{},{}", self.0, self.1)
+    }
+}
+
 impl Coordinate {
     fn fold(&self, f: &Fold) -> Self {
         match *f {
@@ -90,15 +95,21 @@ impl FromStr for Coordinate {
     }
 }
 
-#[derive(Debug, derive_more::Display)]
+#[derive(Debug)]
 enum Fold {
-    #[display(fmt = "fold along x={_0}\n")]
     X(usize),
-
-    #[display(fmt = "fold along y={_0}\n")]
     Y(usize),
 }
 
+impl Display for Fold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fold::X(v) => writeln!(f, "fold along x={v}"),
+            Fold::Y(v) => writeln!(f, "fold along y={v}"),
+        }
+    }
+}
+
 impl FromStr for Fold {
     type Err = Error;
 
@@ -117,7 +128,7 @@ impl FromStr for Fold {
     }
 }
 
-#[derive(Debug, macros::TryFromStr)]
+#[derive(Debug, macros::FromSections, macros::TryFromStr)]
 pub struct Instructions {
     coordinates: Vec<Coordinate>,
     folds: Vec<Fold>,
@@ -136,26 +147,6 @@ impl Display for Instructions {
     }
 }
 
-impl FromStr for Instructions {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-        let mut coordinates = Vec::new();
-        loop {
-            let line = lines.next().context("reading coordinates from input")?;
-            if line.is_empty() {
-                break;
-            }
-            coordinates.push(line.parse::<Coordinate>()?);
-        }
-        Ok(Self {
-            coordinates,
-            folds: lines.map(str::parse).collect::<Result<_, _>>()?,
-        })
-    }
-}
-
 #[derive(macros::FromIterator)]
 #[from_iterator(Coordinate)]
 struct Matrix(BTreeSet<Coordinate>);
@@ -171,15 +162,16 @@ impl Display for Matrix {
         let (max_x, max_y) = self.0.iter().fold((0, 0), |p, n| {
             (std::cmp::max(p.0, n.0), std::cmp::max(p.1, n.1))
         });
+        let width = max_x + 1;
         let mut v = Vec::new();
-        v.resize((max_x + 1) * (max_y + 1), '.');
+        v.resize(width * (max_y + 1), '.');
         for c in self.0.iter() {
-            let idx = (c.1 * max_x) + c.0;
+            let idx = (c.1 * width) + c.0;
             v[idx] = '#';
         }
         for y in 0..=max_y {
             for x in 0..=max_x {
-                let idx = (y * max_x) + x;
+                let idx = (y * width) + x;
                 f.write_char(v[idx])?;
             }
             f.write_char('\n')?;
@@ -195,3 +187,8 @@ impl<'a> Add<&'a Fold> for Matrix {
         self.0.iter().map(|c| c.fold(rhs)).collect()
     }
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Instructions; [example, live]);
+}
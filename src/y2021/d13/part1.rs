@@ -155,6 +155,7 @@
 // How many dots are visible after completing just the first fold instruction on
 // your transparent paper?
 
+#[macros::solution(year = 2021, day = 13, part = 1)]
 pub fn solve(input: super::Instructions) -> usize {
     let matrix: super::Matrix = input.coordinates.into_iter().collect();
     let matrix = matrix + &input.folds[0];
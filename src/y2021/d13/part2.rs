@@ -24,16 +24,27 @@
 //
 // What code do you use to activate the infrared thermal imaging camera system?
 
-pub fn solve(input: super::Instructions) -> String {
+pub fn render(input: super::Instructions) -> String {
     let matrix: super::Matrix = input.coordinates.into_iter().collect();
     let matrix = input.folds.iter().fold(matrix, |prev, fold| prev + fold);
     matrix.to_string()
 }
 
+#[macros::solution(year = 2021, day = 13, part = 2)]
+pub fn solve(input: super::Instructions) -> anyhow::Result<aoc::Answer> {
+    aoc::ocr::recognize_answer(&render(input))
+}
+
 #[cfg(test)]
 mod tests {
-    ::aoc::tests_for_problem!(super::solve, {
-        example => include_str!("outputs/example"),
-        live => include_str!("outputs/live"),
+    ::aoc::tests_for_problem!(|input: super::super::Instructions| super::solve(input).unwrap(), {
+        live => aoc::Answer::Str("FJAHJGAH".to_owned()),
     });
+
+    mod render {
+        ::aoc::tests_for_problem!(super::super::render, {
+            example => include_str!("outputs/example"),
+            live => include_str!("outputs/live"),
+        });
+    }
 }
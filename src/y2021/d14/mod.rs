@@ -25,112 +25,75 @@ use std::collections::BTreeMap;
 use std::str::FromStr;
 
 use anyhow::anyhow;
-use anyhow::Context;
 use anyhow::Error;
+use aoc::sections::SectionError;
+use itertools::Itertools;
 
-#[derive(Debug)]
-struct InsertionRule {
-    matching_pair: (u8, u8),
-    insert: u8,
-}
-
-impl From<InsertionRule> for ((u8, u8), u8) {
-    fn from(value: InsertionRule) -> Self {
-        (value.matching_pair, value.insert)
-    }
-}
+/// Two neighbouring elements of a polymer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, derive_more::Display)]
+#[display(fmt = "{_0}{_1}")]
+struct Pair(char, char);
 
-impl FromStr for InsertionRule {
+impl FromStr for Pair {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (lhs, rhs) = s
-            .split_once(" -> ")
-            .with_context(|| format!("splitting {s} on ` -> `"))?;
-
-        let lhs = lhs.as_bytes();
-        if lhs.len() != 2 {
-            return Err(anyhow!(
-                "expected instructions to start with two bytes; got {}",
-                s
-            ));
-        }
-        let rhs = rhs.as_bytes();
-        if rhs.len() != 1 {
-            return Err(anyhow!(
-                "expected instructions to end with one byte; got {}",
-                s
-            ));
-        }
-        Ok(Self {
-            matching_pair: (lhs[0], lhs[1]),
-            insert: rhs[0],
-        })
+        s.chars()
+            .collect_tuple()
+            .map(|(a, b)| Self(a, b))
+            .ok_or_else(|| anyhow!("expected two elements; got {s:?}"))
     }
 }
 
-#[derive(Clone, macros::TryFromStr)]
-pub struct Instructions {
-    tuples: BTreeMap<(u8, u8), usize>,
-    rules: BTreeMap<(u8, u8), u8>,
+#[derive(Debug, macros::Scan)]
+#[scan("{matching_pair} -> {insert}")]
+struct InsertionRule {
+    matching_pair: Pair,
+    insert: char,
 }
 
-impl std::fmt::Debug for Instructions {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let tuples = self
-            .tuples
-            .iter()
-            .map(|(&(a, b), &count)| {
-                let b = [a, b];
-                let s = std::str::from_utf8(&b).unwrap();
-                format!("{s} -> {count}")
-            })
-            .collect::<Vec<_>>();
-        let rules = self
-            .rules
-            .iter()
-            .map(|(&(a, b), &next)| {
-                let b1 = [a, b];
-                let s1 = std::str::from_utf8(&b1).unwrap();
-                let b2 = [next];
-                let s2 = std::str::from_utf8(&b2).unwrap();
-                format!("{s1} -> {s2}")
-            })
-            .collect::<Vec<_>>();
-        f.debug_struct("Instructions")
-            .field("tuples", &tuples)
-            .field("rules", &rules)
-            .field("score", &self.score())
-            .finish()
+impl From<InsertionRule> for (Pair, char) {
+    fn from(value: InsertionRule) -> Self {
+        (value.matching_pair, value.insert)
     }
 }
 
-impl FromStr for Instructions {
-    type Err = Error;
+#[derive(Debug, Clone, macros::TryFromStr)]
+pub struct Instructions {
+    tuples: BTreeMap<Pair, usize>,
+    rules: BTreeMap<Pair, char>,
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut lines = s.lines();
-        let tuples = lines
-            .next()
-            .context("reading polymer template from input")?
-            .as_bytes()
-            .windows(2)
-            .map(|slice| (slice[0], slice[1]))
+/// The puzzle input as written: the polymer template, then the rules.
+#[derive(macros::FromSections, macros::Display)]
+struct Manual {
+    template: String,
+    rules: Vec<InsertionRule>,
+}
+
+impl From<Manual> for Instructions {
+    fn from(manual: Manual) -> Self {
+        let tuples = manual
+            .template
+            .chars()
+            .tuple_windows()
+            .map(|(a, b)| Pair(a, b))
             .fold(BTreeMap::new(), |mut map, pair| {
                 *map.entry(pair).or_default() += 1;
                 map
             });
-        lines
-            .next()
-            .context("reading empty line separator")
-            .and_then(aoc::expect_empty_line)?;
-        Ok(Self {
+        Self {
             tuples,
-            rules: lines
-                .map(str::parse::<InsertionRule>)
-                .map(|r| r.map(Into::into))
-                .collect::<Result<_, _>>()?,
-        })
+            rules: manual.rules.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl FromStr for Instructions {
+    type Err = SectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<Manual>().map(Self::from)
     }
 }
 
@@ -138,19 +101,19 @@ impl Instructions {
     pub fn step(self) -> Self {
         let mut tuples = BTreeMap::new();
         let rules = self.rules;
-        for ((a, b), count) in self.tuples {
-            if let Some(&ins) = rules.get(&(a, b)) {
-                *tuples.entry((a, ins)).or_default() += count;
-                *tuples.entry((ins, b)).or_default() += count;
+        for (pair @ Pair(a, b), count) in self.tuples {
+            if let Some(&ins) = rules.get(&pair) {
+                *tuples.entry(Pair(a, ins)).or_default() += count;
+                *tuples.entry(Pair(ins, b)).or_default() += count;
             }
         }
         Self { tuples, rules }
     }
 
     pub fn score(&self) -> usize {
-        let mut a_counts: BTreeMap<u8, usize> = BTreeMap::new();
-        let mut b_counts: BTreeMap<u8, usize> = BTreeMap::new();
-        for (&(a, b), &count) in self.tuples.iter() {
+        let mut a_counts: BTreeMap<char, usize> = BTreeMap::new();
+        let mut b_counts: BTreeMap<char, usize> = BTreeMap::new();
+        for (&Pair(a, b), &count) in self.tuples.iter() {
             *a_counts.entry(a).or_default() += count;
             *b_counts.entry(b).or_default() += count;
         }
@@ -170,3 +133,17 @@ impl Instructions {
         max - min
     }
 }
+
+#[cfg(test)]
+mod tests {
+    macros::test_roundtrip!(
+        super::Manual,
+        include_str!("inputs/example"),
+        include_str!("inputs/live")
+    );
+}
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Instructions; [example, live]);
+}
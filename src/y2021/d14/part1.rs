@@ -97,6 +97,7 @@
 // quantity of the most common element and subtract the quantity of the least
 // common element?
 
+#[macros::solution(year = 2021, day = 14, part = 1)]
 pub fn solve(input: super::Instructions) -> usize {
     (0..10).fold(input, |i, _| i.step()).score()
 }
@@ -32,6 +32,7 @@
 // quantity of the most common element and subtract the quantity of the least
 // common element?
 
+#[macros::solution(year = 2021, day = 14, part = 2)]
 pub fn solve(input: super::Instructions) -> usize {
     (0..40).fold(input, |i, _| i.step()).score()
 }
@@ -18,44 +18,31 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use aoc::nom::usize;
-use nom::character::complete::line_ending;
-use nom::combinator::all_consuming;
-use nom::combinator::map;
-use nom::multi::separated_list1;
-use nom::sequence::terminated;
-use nom::IResult;
+use aoc::gen::Rng;
 
-use super::Elf;
-use super::Elves;
-
-pub fn elves(s: &str) -> IResult<&str, Elves> {
-    map(all_consuming(separated_list1(line_ending, elf)), |mut v| {
-        v.sort();
-        Elves(v)
-    })(s)
-}
-
-fn elf(s: &str) -> IResult<&str, Elf> {
-    map(
-        terminated(separated_list1(line_ending, usize), line_ending),
-        Elf,
-    )(s)
+/// A square cave of risk levels, 100 cells on a side at scale 1.
+#[macros::generator(year = 2021, day = 15)]
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let side = aoc::gen::side(100, scale);
+    let mut out = String::with_capacity(side * (side + 1));
+    for _ in 0..side {
+        out.extend((0..side).map(|_| (b'1' + rng.below(9) as u8) as char));
+        out.push('\n');
+    }
+    out
 }
 
 #[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn test_parse_example() {
-        const TEXT: &str = include_str!("./inputs/example");
-        let _ = elves(TEXT).unwrap();
-    }
+mod tests {
+    use aoc::gen::Rng;
 
     #[test]
-    fn test_parse_live() {
-        const TEXT: &str = include_str!("./inputs/live");
-        let _ = elves(TEXT).unwrap();
+    fn test_generated_inputs_solve() {
+        for seed in 0..2 {
+            let input = super::generate(&mut Rng::new(seed), 1);
+            assert_eq!(input.lines().count(), 100);
+            aoc::parse_and_solve(input.as_str(), super::super::part1::solve).unwrap();
+            aoc::parse_and_solve(input.as_str(), super::super::part2::solve).unwrap();
+        }
     }
 }
@@ -18,17 +18,27 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+pub(crate) mod gen;
 pub(crate) mod part1;
 pub(crate) mod part2;
 
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
-use std::convert::Infallible;
 use std::fmt::Display;
 use std::ops::Mul;
 use std::str::FromStr;
 
-const AROUND_THE_BLOCK: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+use aoc::matrix::GridError;
+use aoc::matrix::Matrix;
+use aoc::matrix::RelativePosition;
+use aoc::matrix::RowCol;
+
+const AROUND_THE_BLOCK: [RelativePosition; 4] = [
+    RelativePosition::TopCenter,
+    RelativePosition::BottomCenter,
+    RelativePosition::MiddleLeft,
+    RelativePosition::MiddleRight,
+];
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 struct State {
@@ -52,26 +62,13 @@ impl Ord for State {
 }
 
 #[derive(Debug, Clone, macros::TryFromStr)]
-pub struct Cave {
-    width: usize,
-    nodes: Vec<u8>,
-}
+pub struct Cave(Matrix<u8>);
 
 impl FromStr for Cave {
-    type Err = Infallible;
+    type Err = GridError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut width = 0;
-        let mut nodes = Vec::new();
-        for line in s.lines() {
-            if width == 0 {
-                width = line.len();
-            }
-            assert_eq!(width, line.len());
-            nodes.extend(line.bytes().map(aoc::chardigit));
-        }
-        let s = Self { width, nodes };
-        Ok(s)
+        Matrix::try_from_grid(s, aoc::digit).map(Self)
     }
 }
 
@@ -83,59 +80,33 @@ impl Mul<usize> for Cave {
     type Output = Cave;
 
     fn mul(self, rhs: usize) -> Self::Output {
-        let width = self.width;
-        let depth = self.depth();
-        let mut new = Cave::new(width * rhs, depth * rhs);
-        for (idx, &v) in self.nodes.iter().enumerate() {
-            let (x, y) = self.idx_to_pos(idx);
-            for dx in 0..rhs {
-                for dy in 0..rhs {
-                    let new_idx = new.pos_to_idx((x + (dx * width), y + (dy * depth)));
-                    let new_value = clamp(v as usize + dx + dy);
-                    new.nodes[new_idx] = new_value;
+        let width = self.0.width();
+        let depth = self.0.depth();
+        let mut new = Matrix::new(width * rhs, vec![0; width * depth * rhs * rhs]);
+        for (pos, &v) in self.0.iter() {
+            let RowCol { row, col } = RowCol::from_position(pos);
+            for drow in 0..rhs {
+                for dcol in 0..rhs {
+                    let rc = RowCol::new(row + drow * depth, col + dcol * width);
+                    new[rc] = clamp(v as usize + drow + dcol);
                 }
             }
         }
-        new
+        Cave(new)
     }
 }
 
 impl Cave {
-    fn new(width: usize, depth: usize) -> Self {
-        Self {
-            width,
-            nodes: vec![0; width * depth],
-        }
-    }
-
-    fn depth(&self) -> usize {
-        self.nodes.len() / self.width
-    }
-
-    fn idx_to_pos(&self, idx: usize) -> (usize, usize) {
-        (idx % self.width, idx / self.width)
-    }
-
-    fn pos_to_idx(&self, (x, y): (usize, usize)) -> usize {
-        (y * self.width) + x
-    }
-
     fn iter_surrounding(&self, idx: usize) -> impl Iterator<Item = (usize, &u8)> {
-        let (x, y) = self.idx_to_pos(idx);
+        let rc = self.0.idx_to_rowcol(idx);
         AROUND_THE_BLOCK
-            .iter()
-            .map(move |&(dx, dy)| (x as isize + dx, y as isize + dy))
-            .filter(|(x, y)| {
-                (0..self.width as isize).contains(x) && (0..self.depth() as isize).contains(y)
-            })
-            .map(|(x, y)| self.pos_to_idx((x as usize, y as usize)))
-            .map(|idx| (idx, &self.nodes[idx]))
+            .into_iter()
+            .filter_map(move |rel| self.0.relative_rowcol(rc, rel))
+            .map(|rc| (self.0.rowcol_to_idx(rc), &self.0[rc]))
     }
 
     fn cost(&self) -> Option<usize> {
-        let mut dist = (0..self.nodes.len())
-            .map(|_| usize::MAX)
-            .collect::<Vec<_>>();
+        let mut dist = vec![usize::MAX; self.0.len()];
         dist[0] = 0;
 
         let mut heap = BinaryHeap::new();
@@ -145,7 +116,7 @@ impl Cave {
         });
 
         while let Some(State { cost, position }) = heap.pop() {
-            if position == self.nodes.len() - 1 {
+            if position == self.0.len() - 1 {
                 return Some(cost);
             }
 
@@ -171,9 +142,9 @@ impl Cave {
 
 impl Display for Cave {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for chunk in self.nodes.chunks(self.width) {
-            for b in chunk {
-                write!(f, "{b}")?;
+        for row in self.0.rows() {
+            for col in self.0.cols() {
+                write!(f, "{}", self.0[RowCol::new(row, col)])?;
             }
             writeln!(f)?;
         }
@@ -195,6 +166,11 @@ mod test {
         println!("{example}");
         println!("{example_5x}");
 
-        assert_eq!(example.nodes, example_5x.nodes);
+        assert_eq!(example.0, example_5x.0);
     }
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Cave; [example, example_5x, live]);
+}
@@ -77,6 +77,7 @@
 // What is the lowest total risk of any path from the top left to the bottom
 // right?
 
+#[macros::solution(year = 2021, day = 15, part = 1)]
 pub fn solve(input: super::Cave) -> usize {
     input.cost().unwrap()
 }
@@ -156,6 +156,7 @@
 // Using the full map, what is the lowest total risk of any path from the top
 // left to the bottom right?
 
+#[macros::solution(year = 2021, day = 15, part = 2)]
 pub fn solve(input: super::Cave) -> usize {
     (input * 5).cost().unwrap()
 }
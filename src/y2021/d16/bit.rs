@@ -23,8 +23,7 @@ use std::iter::Sum;
 use std::ops::Add;
 use std::str::FromStr;
 
-use anyhow::anyhow;
-use anyhow::Error;
+use super::ParseError;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Bit {
@@ -46,7 +45,7 @@ impl Bit {
         ]
     }
 
-    pub fn from_char(c: u8) -> Result<[Bit; 4], Error> {
+    pub fn from_char(c: u8) -> Result<[Bit; 4], ParseError> {
         use Bit::*;
         match c {
             b'0' => Ok([Zero, Zero, Zero, Zero]),
@@ -65,7 +64,7 @@ impl Bit {
             b'D' | b'd' => Ok([One, One, Zero, One]),
             b'E' | b'e' => Ok([One, One, One, Zero]),
             b'F' | b'f' => Ok([One, One, One, One]),
-            _ => Err(anyhow!("expected a hexadecimal character; got {}", c)),
+            _ => Err(ParseError::Hex(c as char)),
         }
     }
 }
@@ -148,7 +147,7 @@ impl FromIterator<u8> for BitVector {
 }
 
 impl FromStr for BitVector {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut this: Self = s
@@ -0,0 +1,124 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use aoc::gen::Rng;
+
+/// Values are kept below this so that evaluating the packet cannot overflow.
+const MAX_PRODUCT: u64 = 1 << 40;
+
+/// A transmission of nested packets, about 5,200 bits at scale 1.
+#[macros::generator(year = 2021, day = 16)]
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut bits = Vec::new();
+    packet(rng, 5200 * scale, 0, &mut bits);
+    bits.resize(bits.len().next_multiple_of(4), false);
+    let mut out = bits
+        .chunks(4)
+        .map(|nibble| {
+            let v = nibble.iter().fold(0, |v, &b| (v << 1) | b as u32);
+            char::from_digit(v, 16).unwrap().to_ascii_uppercase()
+        })
+        .collect::<String>();
+    out.push('\n');
+    out
+}
+
+fn push(out: &mut Vec<bool>, value: u64, width: usize) {
+    out.extend((0..width).rev().map(|n| (value >> n) & 1 == 1));
+}
+
+/// Write a packet of roughly `budget` bits, returning its value.
+fn packet(rng: &mut Rng, budget: usize, depth: usize, out: &mut Vec<bool>) -> u64 {
+    push(out, rng.below(8) as u64, 3);
+    if depth >= 8 || budget < 64 {
+        return literal(rng, out);
+    }
+
+    let mut typ = *rng.choose(&[0, 1, 2, 3, 5, 6, 7]);
+    let count = match typ {
+        5..=7 => 2,
+        _ => {
+            let least = budget.div_ceil(4096).min(2047);
+            least + rng.below((budget / 64).clamp(least, 2047) - least + 1)
+        }
+    };
+
+    let mut children = Vec::new();
+    let values = (0..count)
+        .map(|_| packet(rng, budget / count, depth + 1, &mut children))
+        .collect::<Vec<_>>();
+    let value = match typ {
+        1 => match values.iter().try_fold(1u64, |p, &v| p.checked_mul(v)) {
+            Some(product) if product <= MAX_PRODUCT => product,
+            _ => {
+                typ = 0;
+                values.iter().sum()
+            }
+        },
+        0 => values.iter().sum(),
+        2 => *values.iter().min().unwrap(),
+        3 => *values.iter().max().unwrap(),
+        5 => (values[0] > values[1]) as u64,
+        6 => (values[0] < values[1]) as u64,
+        _ => (values[0] == values[1]) as u64,
+    };
+
+    push(out, typ, 3);
+    if children.len() < 1 << 15 && rng.chance(50) {
+        out.push(false);
+        push(out, children.len() as u64, 15);
+    } else {
+        out.push(true);
+        push(out, count as u64, 11);
+    }
+    out.extend(children);
+    value
+}
+
+fn literal(rng: &mut Rng, out: &mut Vec<bool>) -> u64 {
+    let bits = rng.range(1..=16);
+    let value = rng.below(1 << bits) as u64;
+    push(out, 4, 3);
+    let groups = (64 - value.leading_zeros() as usize).div_ceil(4).max(1);
+    for group in (0..groups).rev() {
+        out.push(group > 0);
+        push(out, (value >> (4 * group)) & 0xf, 4);
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::gen::Rng;
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for (seed, scale) in [(0, 1), (1, 1), (2, 10)] {
+            let input = super::generate(&mut Rng::new(seed), scale);
+            let len = input.trim_end().len();
+            assert!(
+                (1000 * scale..2000 * scale).contains(&len),
+                "{len} hex digits"
+            );
+            aoc::parse_and_solve(input.as_str(), super::super::part1::solve).unwrap();
+            aoc::parse_and_solve(input.as_str(), super::super::part2::solve).unwrap();
+        }
+    }
+}
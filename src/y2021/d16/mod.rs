@@ -18,6 +18,7 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+pub(crate) mod gen;
 pub(crate) mod part1;
 pub(crate) mod part2;
 
@@ -25,11 +26,26 @@ pub(crate) mod bit;
 
 use std::str::FromStr;
 
-use anyhow::Context;
-use anyhow::Error;
 use bit::Bit;
 use bit::BitVector;
 
+/// Why a transmission could not be decoded.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum ParseError {
+    #[display(fmt = "expected a hexadecimal character; got {_0:?}")]
+    Hex(char),
+    #[display(fmt = "the transmission ends in the middle of a packet")]
+    Truncated,
+    #[display(fmt = "sub-packets need {need} bits but only {have} remain")]
+    Length { need: usize, have: usize },
+    #[display(fmt = "operator {packet_type} cannot take {count} sub-packets")]
+    Operands { packet_type: usize, count: usize },
+    #[display(fmt = "the transmission evaluates to more than {} bits", usize::BITS)]
+    Overflow,
+}
+
+impl std::error::Error for ParseError {}
+
 #[derive(Debug)]
 pub struct Version([Bit; 3]);
 
@@ -57,12 +73,13 @@ enum LengthType {
 }
 
 impl LengthType {
-    pub fn read(bv: &mut BitVector) -> Option<Self> {
+    pub fn read(bv: &mut BitVector) -> Result<Self, ParseError> {
         match bv.pop() {
             Some(Bit::Zero) => bv.take_array().map(Self::TotalLength),
             Some(Bit::One) => bv.take_array().map(Self::SubPackets),
             None => None,
         }
+        .ok_or(ParseError::Truncated)
     }
 }
 
@@ -96,77 +113,62 @@ impl Packet {
     }
 
     pub fn evaluate(&self) -> usize {
+        self.value().expect("checked when parsed")
+    }
+
+    /// What the packet evaluates to, or `None` if that does not fit in a
+    /// `usize` or an operator has the wrong number of sub-packets.
+    fn value(&self) -> Option<usize> {
         match self {
-            Self::Literal { value, .. } => value.iter().sum(),
+            Self::Literal { value, .. } => {
+                (value.len() <= usize::BITS as usize).then(|| value.iter().sum())
+            }
             Self::Operator {
                 packet_type,
                 children,
                 ..
             } => {
                 let typ = packet_type.0.iter().sum::<usize>();
-                let mut children = children.iter().map(Packet::evaluate);
-                match typ {
+                let values = children
+                    .iter()
+                    .map(Packet::value)
+                    .collect::<Option<Vec<_>>>()?;
+                match (typ, values.as_slice()) {
                     // sum
-                    0 => children.sum::<usize>(),
+                    (0, values) => values.iter().try_fold(0usize, |a, &b| a.checked_add(b)),
                     // product
-                    1 => children.product::<usize>(),
+                    (1, values) => values.iter().try_fold(1usize, |a, &b| a.checked_mul(b)),
                     // min
-                    2 => children.min().unwrap(),
+                    (2, values) => values.iter().copied().min(),
                     // max
-                    3 => children.max().unwrap(),
+                    (3, values) => values.iter().copied().max(),
                     // greater than
-                    5 => {
-                        let lhs = children.next().unwrap();
-                        let rhs = children.next().unwrap();
-                        assert!(children.next().is_none());
-                        if lhs > rhs {
-                            1
-                        } else {
-                            0
-                        }
-                    }
+                    (5, &[lhs, rhs]) => Some((lhs > rhs) as usize),
                     // less than
-                    6 => {
-                        let lhs = children.next().unwrap();
-                        let rhs = children.next().unwrap();
-                        assert!(children.next().is_none());
-                        if lhs < rhs {
-                            1
-                        } else {
-                            0
-                        }
-                    }
+                    (6, &[lhs, rhs]) => Some((lhs < rhs) as usize),
                     // equal to
-                    7 => {
-                        let lhs = children.next().unwrap();
-                        let rhs = children.next().unwrap();
-                        assert!(children.next().is_none());
-                        if lhs == rhs {
-                            1
-                        } else {
-                            0
-                        }
-                    }
-                    _ => unreachable!(),
+                    (7, &[lhs, rhs]) => Some((lhs == rhs) as usize),
+                    _ => None,
                 }
             }
         }
     }
 
-    pub fn read(bv: &mut BitVector) -> Option<Self> {
-        let version = Version::read(bv)?;
-        let packet_type = PacketType::read(bv)?;
-        if packet_type.0.iter().sum::<usize>() == 4 {
+    pub fn read(bv: &mut BitVector) -> Result<Self, ParseError> {
+        let version = Version::read(bv).ok_or(ParseError::Truncated)?;
+        let packet_type = PacketType::read(bv).ok_or(ParseError::Truncated)?;
+        let typ = packet_type.0.iter().sum::<usize>();
+        if typ == 4 {
             let mut value = Vec::new();
             loop {
-                let next = bv.pop()?;
-                let v = bv.take_array::<4>()?;
+                let next = bv.pop().ok_or(ParseError::Truncated)?;
+                let v = bv.take_array::<4>().ok_or(ParseError::Truncated)?;
                 value.extend(v);
                 if matches!(next, Bit::Zero) {
                     break;
                 }
             }
-            Some(Self::Literal {
+            Ok(Self::Literal {
                 version,
                 // packet_type,
                 value,
@@ -177,12 +179,11 @@ impl Packet {
             match length_type {
                 LengthType::TotalLength(ref v) => {
                     let need = v.iter().sum::<usize>();
-                    let len = bv.len();
-                    assert!(
-                        need <= len,
-                        "invalid state; need {need} bits but only have {len}"
-                    );
-                    let remain = len - need;
+                    let have = bv.len();
+                    if need > have {
+                        return Err(ParseError::Length { need, have });
+                    }
+                    let remain = have - need;
                     while remain < bv.len() {
                         children.push(Packet::read(bv)?);
                     }
@@ -194,7 +195,18 @@ impl Packet {
                     }
                 }
             }
-            Some(Self::Operator {
+            let count = children.len();
+            let arity_ok = match typ {
+                5..=7 => count == 2,
+                _ => count > 0,
+            };
+            if !arity_ok {
+                return Err(ParseError::Operands {
+                    packet_type: typ,
+                    count,
+                });
+            }
+            Ok(Self::Operator {
                 version,
                 packet_type,
                 // length_type,
@@ -205,10 +217,49 @@ impl Packet {
 }
 
 impl FromStr for Packet {
-    type Err = Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut bv = BitVector::from_str(s)?;
-        Packet::read(&mut bv).context("parsing packet from input")
+        let packet = Packet::read(&mut bv)?;
+        match packet.value() {
+            Some(_) => Ok(packet),
+            None => Err(ParseError::Overflow),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Packet;
+    use super::ParseError;
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("8G".parse::<Packet>().unwrap_err(), ParseError::Hex('G'));
+        assert_eq!("D2FE".parse::<Packet>().unwrap_err(), ParseError::Truncated);
+        // A `min` operator (type 2) with no sub-packets.
+        assert_eq!(
+            "0A00000".parse::<Packet>().unwrap_err(),
+            ParseError::Operands {
+                packet_type: 2,
+                count: 0
+            }
+        );
+        // The product of two literals of 2^63 each.
+        assert_eq!(
+            "260084C42108421084210842001310842108421084210800"
+                .parse::<Packet>()
+                .unwrap_err(),
+            ParseError::Overflow
+        );
     }
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Packet; [
+        example_a, example_b, example_c, example_d, example_e, example_f, example_g, example_h,
+        example_i, example_j, example_k, example_l, example_m, example_n, example_o, live,
+    ]);
+}
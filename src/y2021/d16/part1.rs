@@ -189,6 +189,7 @@
 // Decode the structure of your hexadecimal-encoded BITS transmission; what do
 // you get if you add up the version numbers in all packets?
 
+#[macros::solution(year = 2021, day = 16, part = 1, invalid(truncated))]
 pub fn solve(packet: super::Packet) -> usize {
     packet.version_sum()
 }
@@ -212,5 +213,6 @@ mod tests {
         example_n => 16,
         example_o => 20,
         live => 886,
+        truncated => Err(super::super::ParseError::Truncated),
     });
 }
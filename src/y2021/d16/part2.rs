@@ -70,6 +70,7 @@
 // What do you get if you evaluate the expression represented by your
 // hexadecimal- encoded BITS transmission?
 
+#[macros::solution(year = 2021, day = 16, part = 2, invalid(truncated))]
 pub fn solve(packet: super::Packet) -> usize {
     packet.evaluate()
 }
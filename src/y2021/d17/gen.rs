@@ -0,0 +1,43 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use aoc::gen::Rng;
+
+/// A target area to the right of and below the launcher, about the size of
+/// a live one at scale 1 and `scale` times further away otherwise.
+///
+/// Like real inputs, some horizontal velocity comes to rest over the area,
+/// which is what lets part 1 ignore the x axis.
+#[macros::generator(year = 2021, day = 17)]
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let scale = scale as i64;
+    loop {
+        let min_x = rng.range(10..=200 * scale);
+        let max_x = min_x + rng.range(5..=50 * scale);
+        let max_y = -rng.range(5..=100 * scale);
+        let min_y = max_y - rng.range(5..=50 * scale);
+        let rests = (1..=max_x)
+            .map(|dx| dx * (dx + 1) / 2)
+            .any(|x| (min_x..=max_x).contains(&x));
+        if rests {
+            return format!("target area: x={min_x}..{max_x}, y={min_y}..{max_y}\n");
+        }
+    }
+}
@@ -18,15 +18,17 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+pub(crate) mod gen;
 pub(crate) mod part1;
 pub(crate) mod part2;
+#[cfg(test)]
+mod reference;
 
 pub(crate) mod parser;
 
 use std::ops::RangeInclusive;
 
-#[derive(derive_more::Display, macros::TryFromStr)]
-#[display(fmt = "target area: x={min_x}..{max_x}, y={min_y}..{max_y}\n")]
+#[derive(macros::TryFromStr)]
 pub struct TargetArea {
     min_x: isize,
     max_x: isize,
@@ -34,6 +36,18 @@ pub struct TargetArea {
     max_y: isize,
 }
 
+impl std::fmt::Display for TargetArea {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            min_x,
+            max_x,
+            min_y,
+            max_y,
+        } = self;
+        writeln!(f, "target area: x={min_x}..{max_x}, y={min_y}..{max_y}")
+    }
+}
+
 ::aoc::derive_FromStr_for_nom!(TargetArea, parser::target_area);
 
 fn pos(dx: isize, dy: isize, t: isize) -> (isize, isize) {
@@ -90,3 +104,8 @@ mod tests {
         live => include_str!("inputs/live"),
     });
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::TargetArea; [example, live]);
+}
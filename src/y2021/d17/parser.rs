@@ -18,22 +18,23 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
-use aoc::nom::isize;
+use aoc::nom::labelled;
+use aoc::nom::range_inclusive;
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::line_ending;
 use nom::combinator::eof;
+use nom::sequence::separated_pair;
 use nom::IResult;
 
 pub(super) fn target_area(s: &str) -> IResult<&str, super::TargetArea> {
-    let (s, _) = tag("target area: x=")(s)?;
-    let (s, min_x) = isize(s)?;
-    let (s, _) = tag("..")(s)?;
-    let (s, max_x) = isize(s)?;
-    let (s, _) = tag(", y=")(s)?;
-    let (s, min_y) = isize(s)?;
-    let (s, _) = tag("..")(s)?;
-    let (s, max_y) = isize(s)?;
+    let (s, _) = tag("target area: ")(s)?;
+    let (s, (x, y)) = separated_pair(
+        labelled("x=", range_inclusive("..")),
+        tag(", "),
+        labelled("y=", range_inclusive("..")),
+    )(s)?;
+    let ((min_x, max_x), (min_y, max_y)) = (x.into_inner(), y.into_inner());
     let (s, _) = alt((line_ending, eof))(s)?;
     Ok((s, super::TargetArea {
         min_x,
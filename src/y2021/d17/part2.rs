@@ -46,6 +46,7 @@
 // How many distinct initial velocity values cause the probe to be within the
 // target area after any step?
 
+#[macros::solution(year = 2021, day = 17, part = 2)]
 pub fn solve(input: super::TargetArea) -> usize {
     input.range().count()
 }
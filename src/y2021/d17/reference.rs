@@ -0,0 +1,87 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Fire the probe at every velocity that could reach the area and follow it
+/// step by step, returning the greatest height reached by a probe that hits
+/// and how many velocities hit.
+pub fn launch(area: &super::TargetArea) -> (isize, usize) {
+    let mut best = None;
+    let mut hits = 0;
+    // a faster probe overshoots on its first step right, or on its first
+    // step down after falling back through y = 0
+    for dx in 0..=area.max_x {
+        for dy in area.min_y..=-area.min_y {
+            if let Some(height) = fire(area, dx, dy) {
+                best = best.max(Some(height));
+                hits += 1;
+            }
+        }
+    }
+    (best.unwrap_or_default(), hits)
+}
+
+fn fire(area: &super::TargetArea, mut dx: isize, mut dy: isize) -> Option<isize> {
+    let (mut x, mut y, mut height) = (0, 0, 0);
+    while x <= area.max_x && y >= area.min_y {
+        if area.range_x().contains(&x) && area.range_y().contains(&y) {
+            return Some(height);
+        }
+        x += dx;
+        y += dy;
+        height = height.max(y);
+        dx -= dx.signum();
+        dy -= 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::TargetArea;
+
+    fn solution(s: &str) -> (isize, usize) {
+        let part1 = aoc::parse_and_solve(s, super::super::part1::solve).unwrap();
+        let part2 = aoc::parse_and_solve(s, super::super::part2::solve).unwrap();
+        (part1 as isize, part2)
+    }
+
+    #[test]
+    fn test_differential() {
+        aoc::differential::check(
+            0..16,
+            |rng| super::super::gen::generate(rng, 1),
+            solution,
+            |s| super::launch(&s.parse::<TargetArea>().unwrap()),
+        );
+    }
+
+    #[test]
+    fn test_inputs() {
+        assert_eq!(solution(include_str!("inputs/example")), (45, 112));
+        assert_eq!(
+            super::launch(&include_str!("inputs/example").parse().unwrap()),
+            (45, 112)
+        );
+        assert_eq!(
+            super::launch(&include_str!("inputs/live").parse().unwrap()),
+            (5995, 3202)
+        );
+    }
+}
@@ -27,7 +27,7 @@ pub(crate) mod parser;
 
 #[derive(derive_more::IntoIterator, macros::FromLines)]
 #[into_iterator]
-#[from_lines(Snailfish)]
+#[from_lines(Snailfish, at_line)]
 pub struct Homework(Vec<Snailfish>);
 
 #[derive(Debug, Clone, PartialEq, Eq, derive_more::Display, derive_more::From)]
@@ -154,3 +154,31 @@ mod tests {
         example_e => "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]".parse().unwrap(),
     });
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Homework; [
+        example_a, example_b, example_c, example_d, example_e, live,
+    ]);
+}
+
+#[cfg(all(test, feature = "proptest"))]
+mod roundtrip {
+    use proptest::prelude::*;
+
+    use super::Node;
+    use super::Snailfish;
+
+    fn snailfish(depth: u32) -> impl Strategy<Value = Snailfish> {
+        let node = (0..20usize)
+            .prop_map(Node::Value)
+            .prop_recursive(depth, 64, 2, |node| {
+                (node.clone(), node).prop_map(|(l, r)| Node::Pair(Box::new(Snailfish(l, r))))
+            });
+        (node.clone(), node).prop_map(|(l, r)| Snailfish(l, r))
+    }
+
+    ::aoc::proptest_roundtrip! {
+        test_snailfish: Snailfish = snailfish(4),
+    }
+}
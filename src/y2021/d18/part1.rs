@@ -221,6 +221,7 @@
 // Add up all of the snailfish numbers from the homework assignment in the order
 // they appear. What is the magnitude of the final sum?
 
+#[macros::solution(year = 2021, day = 18, part = 1)]
 pub fn solve(input: super::Homework) -> usize {
     super::sum(input).magnitude()
 }
@@ -51,6 +51,7 @@
 
 use itertools::Itertools;
 
+#[macros::solution(year = 2021, day = 18, part = 2)]
 pub fn solve(input: super::Homework) -> usize {
     input
         .into_iter()
@@ -73,7 +73,7 @@ impl Coordinate {
     }
 }
 
-impl<'lhs, 'rhs> Sub<&'rhs Coordinate> for &'lhs Coordinate {
+impl<'rhs> Sub<&'rhs Coordinate> for &Coordinate {
     type Output = Coordinate;
 
     fn sub(self, rhs: &'rhs Coordinate) -> Self::Output {
@@ -81,7 +81,7 @@ impl<'lhs, 'rhs> Sub<&'rhs Coordinate> for &'lhs Coordinate {
     }
 }
 
-impl<'lhs, 'rhs> Add<&'rhs Coordinate> for &'lhs Coordinate {
+impl<'rhs> Add<&'rhs Coordinate> for &Coordinate {
     type Output = Coordinate;
 
     fn add(self, rhs: &'rhs Coordinate) -> Self::Output {
@@ -25,7 +25,6 @@ pub(crate) mod coordinate;
 pub(crate) mod parser;
 
 use std::collections::VecDeque;
-use std::fmt::Display;
 
 use coordinate::Coordinate;
 use hashbrown::HashMap;
@@ -85,9 +84,18 @@ impl<'a> From<&'a [Scanner]> for BeaconSet {
     }
 }
 
-#[derive(Debug, macros::TryFromStr)]
-struct Scanner {
+/// The `--- scanner 0 ---` line that starts a scanner's report.
+#[derive(Debug, macros::Scan)]
+#[scan("--- scanner {idx} ---")]
+struct Heading {
     idx: usize,
+}
+
+#[derive(Debug, macros::FromLines, macros::Display)]
+#[from_lines(Coordinate, at_line)]
+struct Scanner {
+    #[from_lines(header)]
+    heading: Heading,
     beacons: Vec<Coordinate>,
 }
 
@@ -97,30 +105,8 @@ impl Scanner {
     }
 }
 
-impl Display for Scanner {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "--- scanner {} ---", self.idx)?;
-        writeln!(
-            f,
-            "{}",
-            self.beacons.iter().map(ToString::to_string).join("\n")
-        )?;
-        Ok(())
-    }
-}
-
-::aoc::derive_FromStr_for_nom!(Scanner, parser::scanner);
-
-#[derive(macros::TryFromStr)]
-pub struct Report(Vec<Scanner>);
-
-impl Display for Report {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0.iter().map(ToString::to_string).join("\n"))
-    }
-}
-
-::aoc::derive_FromStr_for_nom!(Report, parser::report);
+#[derive(macros::FromSections, macros::TryFromStr, macros::Display)]
+pub struct Report(#[section(each)] Vec<Scanner>);
 
 #[cfg(test)]
 mod tests {
@@ -130,3 +116,8 @@ mod tests {
         include_str!("inputs/live")
     );
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Report; [example, live]);
+}
@@ -414,6 +414,7 @@
 //
 // Assemble the full map of beacons. How many beacons are there?
 
+#[macros::solution(year = 2021, day = 19, part = 1)]
 pub fn solve(input: super::Report) -> usize {
     let set: super::BeaconSet = input.0.as_slice().into();
     set.beacons.len()
@@ -30,6 +30,7 @@
 
 use itertools::Itertools;
 
+#[macros::solution(year = 2021, day = 19, part = 2)]
 pub fn solve(input: super::Report) -> usize {
     let set: super::BeaconSet = input.0.as_slice().into();
     set.scanners
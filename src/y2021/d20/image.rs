@@ -22,14 +22,16 @@ use std::ops::AddAssign;
 use std::ops::Index;
 use std::ops::Not;
 
+use aoc::matrix::Matrix;
+use aoc::matrix::ShapeError;
 use arrayvec::ArrayVec;
 
-#[derive(Debug, Clone, Copy, derive_more::Display, macros::Unwrap)]
+#[derive(Debug, Clone, Copy, macros::FromGrid, macros::Unwrap)]
 pub(super) enum Pixel {
-    #[display(fmt = ".")]
+    #[tile('.')]
     Dim,
 
-    #[display(fmt = "#")]
+    #[tile('#')]
     Lit,
 }
 
@@ -85,12 +87,13 @@ pub(super) struct Image {
 }
 
 impl Image {
-    pub fn new(v: Vec<Vec<Pixel>>) -> Self {
-        Image {
-            width: v[0].len(),
-            pixels: v.into_iter().flatten().collect(),
+    pub fn try_new(v: Vec<Vec<Pixel>>) -> Result<Self, ShapeError> {
+        let grid = Matrix::try_from_rows(v)?;
+        Ok(Image {
+            width: grid.width(),
+            pixels: grid.into_iter().map(|(_, pixel)| pixel).collect(),
             outer: Pixel::Dim,
-        }
+        })
     }
 
     pub fn count(&self) -> usize {
@@ -149,7 +152,7 @@ impl Image {
         (0..self.width as isize).contains(&x) && (0..self.depth() as isize).contains(&y)
     }
 
-    fn index(&self, center: usize) -> AlgorithmIndex {
+    fn index(&self, center: usize) -> AlgorithmIndex<'_> {
         let av = self
             .iter_surrounding(center)
             .collect::<ArrayVec<_, { super::ALGORITHM_BITS }>>();
@@ -158,14 +161,11 @@ impl Image {
         AlgorithmIndex(pixels)
     }
 
-    fn iter_row(&self, row: usize) -> impl Iterator<Item = &'_ Pixel> + ExactSizeIterator + '_ {
+    fn iter_row(&self, row: usize) -> impl ExactSizeIterator<Item = &'_ Pixel> + '_ {
         self.pixels.iter().skip(self.width * row).take(self.width)
     }
 
-    fn iter_surrounding(
-        &self,
-        idx: usize,
-    ) -> impl Iterator<Item = &'_ Pixel> + ExactSizeIterator + '_ {
+    fn iter_surrounding(&self, idx: usize) -> impl ExactSizeIterator<Item = &'_ Pixel> + '_ {
         let (x, y) = self.idx_to_pos(idx);
         super::ALGORITHM_DX_DY
             .iter()
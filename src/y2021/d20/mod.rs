@@ -42,7 +42,7 @@ const ALGORITHM_DX_DY: [(isize, isize); ALGORITHM_BITS] = [
     (1, 1),
 ];
 
-#[derive(macros::TryFromStr)]
+#[derive(macros::FromSections, macros::TryFromStr)]
 pub struct Input {
     algo: Algorithm,
     image: Image,
@@ -56,4 +56,10 @@ impl Input {
     }
 }
 
-::aoc::derive_FromStr_for_nom!(Input, parser::input);
+::aoc::derive_FromStr_for_nom!(Algorithm, parser::algorithm);
+::aoc::derive_FromStr_for_nom!(Image, parser::image);
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Input; [example, live]);
+}
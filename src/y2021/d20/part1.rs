@@ -160,6 +160,7 @@
 // twice, being careful to account for the infinite size of the images. How many
 // pixels are lit in the resulting image?
 
+#[macros::solution(year = 2021, day = 20, part = 1)]
 pub fn solve(input: super::Input) -> usize {
     input.fold(2)
 }
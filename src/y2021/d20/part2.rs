@@ -28,6 +28,7 @@
 // Start again with the original input image and apply the image enhancement
 // algorithm 50 times. How many pixels are lit in the resulting image?
 
+#[macros::solution(year = 2021, day = 20, part = 2)]
 pub fn solve(input: super::Input) -> usize {
     input.fold(50)
 }
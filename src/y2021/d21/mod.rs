@@ -64,9 +64,11 @@ impl FromStr for Input {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, macros::Pack)]
 struct Player {
+    #[bits(10)]
     score: usize,
+    #[bits(4)]
     pos: u8,
 }
 
@@ -89,7 +91,8 @@ impl From<u8> for Player {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, macros::Pack)]
+#[bits(u32)]
 struct State {
     p1: Player,
     p2: Player,
@@ -141,3 +144,8 @@ impl<'input> From<&'input Input> for State {
         }
     }
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Input; [example, live]);
+}
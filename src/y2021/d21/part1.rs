@@ -102,6 +102,7 @@ impl Deterministic {
     }
 }
 
+#[macros::solution(year = 2021, day = 21, part = 1)]
 pub fn solve(input: super::Input) -> usize {
     let mut game = Deterministic::default();
     let state = game.game(&input);
@@ -43,9 +43,10 @@
 // the player that wins in more universes; in how many universes does that
 // player win?
 
-use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::ops::Not;
 
+use aoc::pack::Pack;
 use itertools::iproduct;
 
 use super::Input;
@@ -88,7 +89,7 @@ impl Not for Wins {
 
 #[derive(Default)]
 struct Dirac {
-    win_cache: BTreeMap<State, Wins>,
+    win_cache: HashMap<u32, Wins>,
 }
 
 impl Dirac {
@@ -104,20 +105,21 @@ impl Dirac {
             Wins::p1()
         } else if state.p2.score >= Self::WIN {
             Wins::p2()
-        } else if let Some(&wins) = self.win_cache.get(&state) {
+        } else if let Some(&wins) = self.win_cache.get(&state.pack()) {
             wins
-        } else if let Some(&wins) = self.win_cache.get(&!state) {
+        } else if let Some(&wins) = self.win_cache.get(&(!state).pack()) {
             !wins
         } else {
             let wins = iproduct!(Self::ROLLS, Self::ROLLS, Self::ROLLS)
                 .map(|(d1, d2, d3)| d1 + d2 + d3)
                 .fold(Wins::nil(), |wins, roll| wins + self.roll(state + roll));
-            self.win_cache.insert(state, wins);
+            self.win_cache.insert(state.pack(), wins);
             wins
         }
     }
 }
 
+#[macros::solution(year = 2021, day = 21, part = 2)]
 pub fn solve(input: Input) -> usize {
     Dirac::from_input(&input).max()
 }
@@ -0,0 +1,61 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use aoc::gen::Rng;
+
+/// 20 reboot steps inside the `-50..=50` initialisation region followed by
+/// 400 much larger ones at scale 1, matching the shape of a live input.
+#[macros::generator(year = 2021, day = 22)]
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut out = String::new();
+    for n in 0..20 * scale {
+        step(rng, &mut out, n == 0, 50, 50);
+    }
+    for _ in 0..400 * scale {
+        step(rng, &mut out, false, 100_000, 50_000);
+    }
+    out
+}
+
+fn step(rng: &mut Rng, out: &mut String, on: bool, bound: i64, size: i64) {
+    let on = on || rng.chance(50);
+    out.push_str(if on { "on " } else { "off " });
+    for (n, axis) in ["x", "y", "z"].into_iter().enumerate() {
+        let lo = rng.range(-bound..=bound - 1);
+        let hi = rng.range(lo..=(lo + size).min(bound));
+        let sep = if n == 0 { "" } else { "," };
+        out.push_str(&format!("{sep}{axis}={lo}..{hi}"));
+    }
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::gen::Rng;
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        let input = super::generate(&mut Rng::new(0), 1);
+        assert_eq!(input.lines().count(), 420);
+        let part1 = aoc::parse_and_solve(input.as_str(), super::super::part1::solve).unwrap();
+        let part2 = aoc::parse_and_solve(input.as_str(), super::super::part2::solve).unwrap();
+        assert!(0 < part1 && part1 <= part2);
+    }
+}
@@ -18,6 +18,7 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+pub(crate) mod gen;
 pub(crate) mod part1;
 pub(crate) mod part2;
 
@@ -26,14 +27,12 @@ mod parser;
 use std::collections::BTreeMap;
 use std::ops::Not;
 
-#[derive(
-    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, derive_more::Display, macros::Unwrap,
-)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, macros::Scan, macros::Unwrap)]
 pub enum State {
-    #[display(fmt = "off")]
+    #[scan("off")]
     Off,
 
-    #[display(fmt = "on")]
+    #[scan("on")]
     On,
 }
 
@@ -132,31 +131,23 @@ impl Cube {
                 self.head.$elem <= rhs.head.$elem && self.tail.$elem >= rhs.tail.$elem
             };
         }
-        cmp!(x) && cmp!(y) && cmp!(x)
+        cmp!(x) && cmp!(y) && cmp!(z)
     }
 }
 
 #[derive(
-    Debug,
-    Clone,
-    Copy,
-    PartialEq,
-    Eq,
-    PartialOrd,
-    Ord,
-    derive_more::Constructor,
-    derive_more::Display,
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, derive_more::Constructor, macros::Scan,
 )]
-#[display(fmt = "{state} {cube}")]
+#[scan("{state} {cube}")]
 pub struct Instruction {
     state: State,
     cube: Cube,
 }
 
-::aoc::derive_FromStr_for_nom!(Instruction, parser::instruction);
+::aoc::derive_FromStr_for_nom!(Cube, parser::cube);
 
 #[derive(Default, derive_more::IntoIterator, macros::FromLines)]
-#[from_lines(Instruction)]
+#[from_lines(Instruction, at_line)]
 pub struct Instructions(Vec<Instruction>);
 
 impl Instructions {
@@ -186,6 +177,21 @@ impl Instructions {
 
 #[cfg(test)]
 mod tests {
+    use super::Coordinate;
+    use super::Cube;
+
+    #[test]
+    fn test_contains() {
+        let zone = Cube::new(Coordinate::new(-50, -50, -50), Coordinate::new(50, 50, 50));
+        let inside = Cube::new(Coordinate::new(-5, 0, 10), Coordinate::new(5, 10, 20));
+        assert!(zone.contains(&inside));
+        // Only the z range reaches outside the zone.
+        let above = Cube::new(Coordinate::new(-5, 0, 60), Coordinate::new(5, 10, 70));
+        assert!(!zone.contains(&above));
+        let across = Cube::new(Coordinate::new(-5, 0, 40), Coordinate::new(5, 10, 60));
+        assert!(!zone.contains(&across));
+    }
+
     macros::test_roundtrip!(
         super::Instruction,
         "on x=10..12,y=10..12,z=10..12",
@@ -216,3 +222,8 @@ mod tests {
         "on x=967..23432,y=45373..81175,z=27513..53682",
     );
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Instructions; [example_a, example_b, example_c, live]);
+}
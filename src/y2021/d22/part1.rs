@@ -151,6 +151,7 @@ const CLAMP: super::Cube = super::Cube::new(
     super::Coordinate::new(50, 50, 50),
 );
 
+#[macros::solution(year = 2021, day = 22, part = 1)]
 pub fn solve(input: super::Instructions) -> isize {
     input.reduce(Some(&CLAMP))
 }
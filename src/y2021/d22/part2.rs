@@ -94,6 +94,7 @@
 // Starting again with all cubes off, execute all reboot steps. Afterward,
 // considering all cubes, how many cubes are on?
 
+#[macros::solution(year = 2021, day = 22, part = 2)]
 pub fn solve(input: super::Instructions) -> isize {
     input.reduce(None)
 }
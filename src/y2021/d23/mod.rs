@@ -21,23 +21,28 @@
 pub(crate) mod part1;
 pub(crate) mod part2;
 
-use std::collections::BTreeMap;
 use std::collections::BinaryHeap;
+use std::collections::HashMap;
 use std::ops::Range;
 use std::str::FromStr;
 
-use anyhow::Context;
-use arrayvec::ArrayVec;
+use aoc::pack::Pack;
 use itertools::Itertools;
 
 const HALLWAY_COLUMNS: [usize; 7] = [1, 2, 4, 6, 8, 10, 11];
 const ROOM_COLUMNS: [usize; 4] = [3, 5, 7, 9];
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord, macros::FromGrid, macros::Pack,
+)]
 enum Amphipod {
+    #[tile('A')]
     Amber,
+    #[tile('B')]
     Bronze,
+    #[tile('C')]
     Copper,
+    #[tile('D')]
     Desert,
 }
 
@@ -56,63 +61,132 @@ impl Amphipod {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, macros::Unwrap)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, macros::FromGrid, macros::Unwrap)]
 enum Tile {
+    #[tile('#')]
     Wall,
+    #[tile('.')]
     Vacant,
     Amphipod(Amphipod),
+    #[tile(' ')]
     Void,
 }
 
-impl TryFrom<u8> for Tile {
-    type Error = anyhow::Error;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            b'#' => Ok(Self::Wall),
-            b'.' => Ok(Self::Vacant),
-            b'A' => Ok(Self::Amphipod(Amphipod::Amber)),
-            b'B' => Ok(Self::Amphipod(Amphipod::Bronze)),
-            b'C' => Ok(Self::Amphipod(Amphipod::Copper)),
-            b'D' => Ok(Self::Amphipod(Amphipod::Desert)),
-            b' ' => Ok(Self::Void),
-            _ => Err(anyhow::anyhow!("invalid character: {}", value)),
-        }
-    }
+/// Why a maze could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum ParseError {
+    #[display(fmt = "unexpected {_0:?} in the maze")]
+    Tile(char),
+    #[display(fmt = "line {line} is {len} tiles wide; at most {ROW_WIDTH} fit")]
+    Width { line: usize, len: usize },
+    #[display(fmt = "the maze has {_0} rows; expected {MIN_ROWS} to {MAX_ROWS}")]
+    Rows(usize),
+    #[display(fmt = "the maze is not a valid starting layout")]
+    Layout,
 }
 
+impl std::error::Error for ParseError {}
+
 const HALLWAY_ROW: usize = 1;
 const AMPHIPOD_COUNT: usize = 4;
 const ROW_WIDTH: usize = 13;
+/// The walls above and below, the hallway and at least one row of rooms.
+const MIN_ROWS: usize = 4;
+/// The deepest rooms that a [`Burrow`] holds.
+const MAX_DEPTH: usize = 4;
+const MAX_ROWS: usize = MIN_ROWS - 1 + MAX_DEPTH;
+/// The rows that part two unfolds between the first and second rows of
+/// rooms.
+const FOLDED: [&str; 2] = ["  #D#C#B#A#", "  #D#B#A#C#"];
+/// How deep the rooms are before the [`FOLDED`] rows are inserted.
+const FOLDED_DEPTH: usize = 2;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, macros::TryFromStr)]
-pub struct Maze<const ROWS: usize> {
-    tiles: [[Tile; ROW_WIDTH]; ROWS],
+pub struct Maze {
+    tiles: Vec<[Tile; ROW_WIDTH]>,
+}
+
+/// Where the amphipods of a [`Maze`] are, which is all that changes
+/// between the states of a search. Rooms are listed from the hallway down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, macros::Pack)]
+#[bits(u128)]
+struct Burrow {
+    hallway: [Option<Amphipod>; HALLWAY_COLUMNS.len()],
+    rooms: [[Option<Amphipod>; MAX_DEPTH]; AMPHIPOD_COUNT],
 }
 
-impl<const ROWS: usize> Maze<ROWS> {
+impl Maze {
+    /// How many amphipods fit in each room.
+    pub fn depth(&self) -> usize {
+        self.room_row_range().len()
+    }
+
+    /// The maze with the [`FOLDED`] rows inserted below its first row of
+    /// rooms, if it is still folded; otherwise the maze as it is.
+    pub fn unfold(self) -> Self {
+        if self.depth() != FOLDED_DEPTH {
+            return self;
+        }
+        let mut tiles = self.tiles;
+        let folded = FOLDED.map(|line| parse_row(0, line).expect("the folded rows fit"));
+        tiles.splice(HALLWAY_ROW + 2..HALLWAY_ROW + 2, folded);
+        Self { tiles }
+    }
+
+    fn burrow(&self) -> Burrow {
+        let amphipod = |row: usize, col: usize| {
+            self.tiles
+                .get(row)
+                .and_then(|tiles| tiles[col].as_amphipod_ref())
+                .copied()
+        };
+        Burrow {
+            hallway: HALLWAY_COLUMNS.map(|col| amphipod(HALLWAY_ROW, col)),
+            rooms: ROOM_COLUMNS
+                .map(|col| std::array::from_fn(|depth| amphipod(HALLWAY_ROW + 1 + depth, col))),
+        }
+    }
+
+    /// This maze with its amphipods moved to where `burrow` has them.
+    fn with_burrow(&self, burrow: &Burrow) -> Self {
+        let tile = |amphipod: Option<Amphipod>| amphipod.map_or(Tile::Vacant, Tile::Amphipod);
+        let mut new = self.to_owned();
+        for (col, amphipod) in HALLWAY_COLUMNS.into_iter().zip(burrow.hallway) {
+            new.tiles[HALLWAY_ROW][col] = tile(amphipod);
+        }
+        for (col, room) in ROOM_COLUMNS.into_iter().zip(burrow.rooms) {
+            for (row, amphipod) in self.room_row_range().zip(room) {
+                new.tiles[row][col] = tile(amphipod);
+            }
+        }
+        new
+    }
+
     pub fn shortest_path(&self) -> usize {
-        let mut dist = BTreeMap::new();
+        let mut dist = HashMap::new();
         let mut heap = BinaryHeap::new();
-        heap.push((0isize, self.to_owned()));
-        while let Some((cost, next)) = heap.pop() {
+        heap.push((0isize, self.burrow().pack()));
+        while let Some((cost, key)) = heap.pop() {
+            aoc::abort_if_timed_out();
             let ucost = (-cost) as usize;
+            let next = self.with_burrow(&Burrow::unpack(key));
             if next.is_complete() {
                 return ucost;
             }
-            if let Some(&c) = dist.get(&next) {
+            if let Some(&c) = dist.get(&key) {
                 if c < ucost {
                     continue;
                 }
             }
             for (next, next_cost) in next.moves() {
                 let next_cost = ucost + next_cost;
-                match dist.entry(next.clone()) {
-                    std::collections::btree_map::Entry::Vacant(o) => {
+                let next = next.burrow().pack();
+                match dist.entry(next) {
+                    std::collections::hash_map::Entry::Vacant(o) => {
                         o.insert(next_cost);
                         heap.push((-(next_cost as isize), next));
                     }
-                    std::collections::btree_map::Entry::Occupied(o) => {
+                    std::collections::hash_map::Entry::Occupied(o) => {
                         let prev = o.into_mut();
                         if next_cost < *prev {
                             *prev = next_cost;
@@ -126,7 +200,7 @@ impl<const ROWS: usize> Maze<ROWS> {
     }
 
     fn valid(&self) -> bool {
-        let rows = Self::room_row_range().len();
+        let rows = self.depth();
         self.tiles
             .iter()
             .flatten()
@@ -170,12 +244,13 @@ impl<const ROWS: usize> Maze<ROWS> {
         self.iter_hallway(src, dst).all(|(_, t)| t.is_vacant())
     }
 
-    const fn room_row_range() -> Range<usize> {
-        HALLWAY_ROW + 1..ROWS - 1
+    fn room_row_range(&self) -> Range<usize> {
+        HALLWAY_ROW + 1..self.tiles.len() - 1
     }
 
     fn iter_room(&self, room: usize) -> impl Iterator<Item = (usize, &Tile)> + '_ {
-        Self::room_row_range().map(move |row| (row, &self.tiles[row][room]))
+        self.room_row_range()
+            .map(move |row| (row, &self.tiles[row][room]))
     }
 
     fn room_vacancy(&self, room: usize) -> Option<usize> {
@@ -295,45 +370,45 @@ impl<const ROWS: usize> Maze<ROWS> {
     }
 }
 
-impl<const ROWS: usize> FromStr for Maze<ROWS> {
-    type Err = anyhow::Error;
+/// Line `n` (1-based) of a maze, padded with [`Tile::Void`].
+fn parse_row(n: usize, line: &str) -> Result<[Tile; ROW_WIDTH], ParseError> {
+    if line.len() > ROW_WIDTH {
+        return Err(ParseError::Width {
+            line: n,
+            len: line.len(),
+        });
+    }
+    let mut row = [Tile::Void; ROW_WIDTH];
+    for (col, b) in line.bytes().enumerate() {
+        row[col] = Tile::try_from(b).map_err(|err| ParseError::Tile(err.found))?;
+    }
+    Ok(row)
+}
+
+impl FromStr for Maze {
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let v = Self {
-            tiles: s
-                .lines()
-                .map(|line| {
-                    if line.len() <= ROW_WIDTH {
-                        Ok(line
-                            .bytes()
-                            .chain((0..ROW_WIDTH - line.len()).map(|_| b' '))
-                            .map(Tile::try_from)
-                            .collect::<Result<ArrayVec<_, ROW_WIDTH>, _>>()?
-                            .into_inner()
-                            .unwrap())
-                    } else {
-                        Err(anyhow::anyhow!("invalid input: {}", line))
-                    }
-                })
-                .collect::<Result<ArrayVec<_, ROWS>, _>>()?
-                .into_inner()
-                .ok()
-                .context("short read on input")?,
-        };
+        let found = s.lines().count();
+        if !(MIN_ROWS..=MAX_ROWS).contains(&found) {
+            return Err(ParseError::Rows(found));
+        }
+        let tiles = s
+            .lines()
+            .enumerate()
+            .map(|(row, line)| parse_row(row + 1, line))
+            .collect::<Result<_, _>>()?;
+        let v = Self { tiles };
         if v.valid() {
             Ok(v)
         } else {
-            Err(anyhow::anyhow!("maze is not valid: {:?}", v))
+            Err(ParseError::Layout)
         }
     }
 }
 
 const fn h_dist(room: usize, col: usize) -> usize {
-    if room < col {
-        col - room
-    } else {
-        room - col
-    }
+    room.abs_diff(col)
 }
 
 const fn v_dist(row: usize) -> usize {
@@ -348,3 +423,43 @@ fn trisect<V: Ord>(slice: &[V], src: V, dst: V) -> (&[V], &[V], &[V]) {
     let (a, b) = ab.split_at(mid);
     (a, b, c)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Maze;
+    use super::ParseError;
+
+    #[test]
+    fn test_width() {
+        let input = "#############\n#.............#\n###B#C#B#D###\n  #A#D#C#A#\n  #########\n";
+        assert_eq!(
+            input.parse::<Maze>(),
+            Err(ParseError::Width { line: 2, len: 15 })
+        );
+    }
+
+    #[test]
+    fn test_unfold() {
+        let folded: Maze = include_str!("inputs/part1_example").parse().unwrap();
+        let unfolded: Maze = include_str!("inputs/part2_example").parse().unwrap();
+        assert_eq!(folded.unfold(), unfolded);
+        assert_eq!(unfolded.clone().unfold(), unfolded);
+    }
+
+    #[test]
+    fn test_burrow() {
+        use aoc::pack::Pack;
+
+        let maze: Maze = include_str!("inputs/part2_example").parse().unwrap();
+        let burrow = maze.burrow();
+        assert_eq!(super::Burrow::unpack(burrow.pack()), burrow);
+        assert_eq!(maze.with_burrow(&burrow), maze);
+    }
+}
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Maze; [
+        part1_example, part1_live, part2_example, part2_live,
+    ]);
+}
@@ -154,7 +154,8 @@
 //
 // What is the least energy required to organize the amphipods?
 
-pub fn solve(input: super::Maze<5>) -> usize {
+#[macros::solution(year = 2021, day = 23, part = 1)]
+pub fn solve(input: super::Maze) -> usize {
     input.shortest_path()
 }
 
@@ -247,14 +247,18 @@
 // Using the initial configuration from the full diagram, what is the least
 // energy required to organize the amphipods?
 
-pub fn solve(input: super::Maze<7>) -> usize {
-    input.shortest_path()
+#[macros::solution(year = 2021, day = 23, part = 2)]
+pub fn solve(input: super::Maze) -> usize {
+    input.unfold().shortest_path()
 }
 
 #[cfg(test)]
 mod tests {
     ::aoc::tests_for_problem!(super::solve, {
+        #[input(part1_example)]
+        unfolded_example => 44169,
         part2_example => 44169,
+        #[timeout(120)]
         part2_live => 41366,
     });
 }
@@ -0,0 +1,73 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use aoc::gen::Rng;
+
+/// A MONAD program: 14 blocks that each push a digit onto a base-26 stack
+/// held in `z` or pop one and compare it with the next digit. Every program
+/// accepts at least one model number. MONAD always has 14 digits, so
+/// `scale` is ignored.
+#[macros::generator(year = 2021, day = 24)]
+pub fn generate(rng: &mut Rng, _scale: usize) -> String {
+    program(rng, 14, 0)
+}
+
+/// A MONAD-like program of `digits` blocks (an even number) that accepts at
+/// least one model number. Each instruction is followed, `noise` percent of
+/// the time, by one that leaves `w` as it is, so the blocks come out of
+/// different lengths with their constants at different offsets.
+pub fn program(rng: &mut Rng, digits: usize, noise: usize) -> String {
+    const NOPS: [&str; 3] = ["add w 0", "mul w 1", "div w 1"];
+    let mut out = String::new();
+    let mut pending = Vec::new();
+    let mut pushes = 0;
+    for block in 0..digits {
+        let remaining = digits - block;
+        let push = pending.is_empty()
+            || (pushes < digits / 2 && pending.len() < remaining && rng.chance(50));
+        let (div, check, offset) = if push {
+            pushes += 1;
+            let offset = rng.range(0..=16);
+            pending.push(offset);
+            (1, rng.range(10..=16), offset)
+        } else {
+            // the popped digit plus its offset and this check must be within
+            // 8 of each other, and the check must not be positive
+            let pushed = pending.pop().unwrap();
+            let check = rng.range(-8..=pushed.min(8)) - pushed;
+            (26, check, rng.range(0..=16))
+        };
+        let instructions = format!(
+            "inp w\nmul x 0\nadd x z\nmod x 26\ndiv z {div}\nadd x {check}\neql x w\neql x 0\n\
+             mul y 0\nadd y 25\nmul y x\nadd y 1\nmul z y\nmul y 0\nadd y w\nadd y {offset}\n\
+             mul y x\nadd z y\n"
+        );
+        for instruction in instructions.lines() {
+            out.push_str(instruction);
+            out.push('\n');
+            if noise > 0 && rng.chance(noise) {
+                let nop = rng.choose(&NOPS);
+                out.push_str(nop);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
@@ -18,18 +18,25 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+pub(crate) mod gen;
 pub(crate) mod part1;
 pub(crate) mod part2;
+#[cfg(test)]
+mod reference;
 
 mod parser;
 
 use std::collections::BTreeMap;
+use std::fmt::Display;
 use std::ops::Index;
 use std::ops::IndexMut;
+use std::str::FromStr;
 
+use anyhow::bail;
+use anyhow::ensure;
 use anyhow::Context;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Variable {
     W,
     X,
@@ -37,13 +44,33 @@ pub enum Variable {
     Z,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Value {
     Variable(Variable),
 
     Literal(isize),
 }
 
+impl Display for Variable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::W => write!(f, "w"),
+            Self::X => write!(f, "x"),
+            Self::Y => write!(f, "y"),
+            Self::Z => write!(f, "z"),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Variable(v) => write!(f, "{v}"),
+            Self::Literal(v) => write!(f, "{v}"),
+        }
+    }
+}
+
 impl Value {
     fn literal(&self) -> Option<&isize> {
         match self {
@@ -53,7 +80,8 @@ impl Value {
     }
 }
 
-#[derive(macros::Unwrap, Debug, Clone, Copy)]
+#[derive(macros::Unwrap, Debug, Clone, Copy, PartialEq, Eq)]
+#[unwrap(kind)]
 pub enum Instruction {
     Input(Variable),
 
@@ -68,7 +96,41 @@ pub enum Instruction {
     Equals(Variable, Value),
 }
 
+impl InstructionKind {
+    const fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Input => "inp",
+            Self::Add => "add",
+            Self::Multiply => "mul",
+            Self::Divide => "div",
+            Self::Modulo => "mod",
+            Self::Equals => "eql",
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.kind().mnemonic(), self.lhs())?;
+        match self.rhs() {
+            Some(rhs) => write!(f, " {rhs}"),
+            None => Ok(()),
+        }
+    }
+}
+
 impl Instruction {
+    fn lhs(&self) -> Variable {
+        match *self {
+            Instruction::Input(v)
+            | Instruction::Add(v, _)
+            | Instruction::Multiply(v, _)
+            | Instruction::Divide(v, _)
+            | Instruction::Modulo(v, _)
+            | Instruction::Equals(v, _) => v,
+        }
+    }
+
     fn rhs(&self) -> Option<&Value> {
         match self {
             Instruction::Input(_) => None,
@@ -84,7 +146,7 @@ impl Instruction {
 ::aoc::derive_FromStr_for_nom!(Instruction, parser::instruction);
 
 #[derive(macros::FromLines)]
-#[from_lines(Instruction)]
+#[from_lines(Instruction, at_line)]
 pub struct Instructions(Vec<Instruction>);
 
 impl Instructions {
@@ -92,69 +154,110 @@ impl Instructions {
         self.0.iter().filter(|i| i.is_input()).count()
     }
 
-    pub fn opt(&self) -> (usize, usize) {
-        let blocks = self.count_inputs();
-        debug_assert_eq!(blocks, 14);
-        let block_len = self.0.len() / blocks;
-        debug_assert_eq!(block_len, 18);
-
-        let stack = Vec::new();
-        let links = BTreeMap::new();
-
-        let (_, links) = (0..blocks)
-            .map(|b| {
-                let lhs = self.0[b * block_len + 5].rhs().unwrap().literal().unwrap();
-                let rhs = self.0[b * block_len + 15].rhs().unwrap().literal().unwrap();
-                (*lhs, *rhs)
+    /// The instructions from each `inp` up to the next.
+    fn blocks(&self) -> impl Iterator<Item = &[Instruction]> {
+        let mut rest = &self.0[..];
+        std::iter::from_fn(move || {
+            let len = rest
+                .iter()
+                .skip(1)
+                .position(Instruction::is_input)
+                .map_or(rest.len(), |n| n + 1);
+            let (block, tail) = rest.split_at(len);
+            rest = tail;
+            (!block.is_empty()).then_some(block)
+        })
+    }
+
+    /// The constants of a MONAD block: what it adds to `x` before comparing
+    /// it with the digit, and to the digit before pushing it.
+    fn constants(block: &[Instruction]) -> (isize, isize) {
+        let check = block
+            .iter()
+            .find_map(|i| match i {
+                Instruction::Add(Variable::X, v) => v.literal().copied(),
+                _ => None,
             })
-            .enumerate()
-            .fold(
-                (stack, links),
-                |(mut stack, mut links), (idx, (lhs, rhs))| {
-                    if lhs > 0 {
-                        stack.push((idx, rhs));
-                    } else {
-                        let (a, b) = stack.pop().unwrap();
-                        links.insert(idx, (a, b + lhs));
-                    }
-
-                    (stack, links)
-                },
-            );
-
-        let min = BTreeMap::new();
-        let max = BTreeMap::new();
-        let (min, max) = links
-            .into_iter()
-            .fold((min, max), |(mut min, mut max), (a, (b, v))| {
-                min.insert(a, std::cmp::max(1, 1 + v));
-                min.insert(b, std::cmp::max(1, 1 - v));
-
-                max.insert(a, std::cmp::min(9, 9 + v));
-                max.insert(b, std::cmp::min(9, 9 - v));
-
-                (min, max)
-            });
-
-        let min = (0..blocks).map(|x| min[&x] as u8).collect::<Vec<_>>();
-        let alu = ArithmeticLogicUnit::new(self, &min).unwrap();
-        assert_eq!(alu.z, 0);
+            .unwrap_or(0);
+        let offset = block
+            .iter()
+            .skip_while(|i| **i != Instruction::Add(Variable::Y, Value::Variable(Variable::W)))
+            .find_map(|i| match i {
+                Instruction::Add(Variable::Y, v) => v.literal().copied(),
+                _ => None,
+            })
+            .unwrap_or(0);
+        (check, offset)
+    }
 
-        let min = min
-            .into_iter()
-            .map(|v| v as usize)
-            .fold(0, |prev, next| (prev * 10) + next);
+    /// The smallest and largest model numbers the program accepts, or why
+    /// it is not a MONAD whose blocks pair up digits.
+    pub fn opt(&self) -> Result<(usize, usize), anyhow::Error> {
+        ensure!(
+            self.0.first().is_some_and(Instruction::is_input),
+            "the program does not start with `inp`"
+        );
+        let blocks = self.count_inputs();
 
-        let max = (0..blocks).map(|x| max[&x] as u8).collect::<Vec<_>>();
-        let alu = ArithmeticLogicUnit::new(self, &max).unwrap();
-        assert_eq!(alu.z, 0);
+        let mut stack = Vec::new();
+        let mut links = BTreeMap::new();
+        for (idx, (lhs, rhs)) in self.blocks().map(Self::constants).enumerate() {
+            if lhs > 0 {
+                stack.push((idx, rhs));
+            } else {
+                let (a, b) = stack
+                    .pop()
+                    .with_context(|| format!("digit {idx} is compared with no earlier digit"))?;
+                let v = b
+                    .checked_add(lhs)
+                    .filter(|v| (-8..=8).contains(v))
+                    .with_context(|| format!("digits {a} and {idx} can never match"))?;
+                links.insert(idx, (a, v));
+            }
+        }
+        if let Some((idx, _)) = stack.pop() {
+            bail!("digit {idx} is compared with no later digit");
+        }
+
+        let mut min = BTreeMap::new();
+        let mut max = BTreeMap::new();
+        for (a, (b, v)) in links {
+            min.insert(a, std::cmp::max(1, 1 + v));
+            min.insert(b, std::cmp::max(1, 1 - v));
 
-        let max = max
-            .into_iter()
-            .map(|v| v as usize)
-            .fold(0, |prev, next| (prev * 10) + next);
+            max.insert(a, std::cmp::min(9, 9 + v));
+            max.insert(b, std::cmp::min(9, 9 - v));
+        }
 
-        (min, max)
+        let accepted = |digits: &BTreeMap<usize, isize>| {
+            let digits = (0..blocks).map(|x| digits[&x] as u8).collect::<Vec<_>>();
+            let alu = ArithmeticLogicUnit::new(self, &digits)?;
+            ensure!(alu.z == 0, "the program rejects {digits:?}");
+            digits
+                .into_iter()
+                .try_fold(0usize, |prev, next| {
+                    prev.checked_mul(10)?.checked_add(next as usize)
+                })
+                .context("the model number does not fit in a usize")
+        };
+        Ok((accepted(&min)?, accepted(&max)?))
+    }
+}
+
+/// A MONAD program, with the model numbers it accepts worked out when it is
+/// read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, macros::TryFromStr)]
+pub struct Monad {
+    min: usize,
+    max: usize,
+}
+
+impl FromStr for Monad {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (min, max) = s.parse::<Instructions>()?.opt()?;
+        Ok(Self { min, max })
     }
 }
 
@@ -207,10 +310,22 @@ impl ArithmeticLogicUnit {
             Instruction::Input(v) => {
                 self[v] = inp.next().context("insufficient input length")? as isize
             }
-            Instruction::Add(a, b) => self[a] += self.eval(b),
-            Instruction::Multiply(a, b) => self[a] *= self.eval(b),
-            Instruction::Divide(a, b) => self[a] /= self.eval(b),
-            Instruction::Modulo(a, b) => self[a] %= self.eval(b),
+            Instruction::Add(a, b) => {
+                self[a] = self[a]
+                    .checked_add(self.eval(b))
+                    .context("`add` overflows")?
+            }
+            Instruction::Multiply(a, b) => {
+                self[a] = self[a]
+                    .checked_mul(self.eval(b))
+                    .context("`mul` overflows")?
+            }
+            Instruction::Divide(a, b) => {
+                self[a] = self[a].checked_div(self.eval(b)).context("`div` by zero")?
+            }
+            Instruction::Modulo(a, b) => {
+                self[a] = self[a].checked_rem(self.eval(b)).context("`mod` by zero")?
+            }
             Instruction::Equals(a, b) => self[a] = if self[a] == self.eval(b) { 1 } else { 0 },
         }
         Ok(())
@@ -246,6 +361,18 @@ mod tests {
         assert_eq!(alu.x, -9);
     }
 
+    #[test]
+    fn test_malformed() {
+        let v: Instructions = "inp x\ndiv x 0\n".parse().unwrap();
+        let err = ArithmeticLogicUnit::new(&v, &[1]).unwrap_err();
+        assert_eq!(err.to_string(), "`div` by zero");
+
+        let err = "inp w\nadd x 1\n".parse::<super::Monad>().unwrap_err();
+        assert_eq!(err.to_string(), "digit 0 is compared with no later digit");
+        let err = "add x 1\n".parse::<super::Monad>().unwrap_err();
+        assert_eq!(err.to_string(), "the program does not start with `inp`");
+    }
+
     #[test]
     fn test_3x_check() {
         const INPUT: &str = "inp z\ninp x\nmul z 3\neql z x\n";
@@ -363,3 +490,45 @@ mod tests {
         assert_eq!(alu.z, 1);
     }
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Instructions, super::Monad; [live]);
+}
+
+#[cfg(all(test, feature = "proptest"))]
+mod roundtrip {
+    use proptest::prelude::*;
+
+    use super::Instruction;
+    use super::Value;
+    use super::Variable;
+
+    fn variable() -> impl Strategy<Value = Variable> {
+        prop_oneof![
+            Just(Variable::W),
+            Just(Variable::X),
+            Just(Variable::Y),
+            Just(Variable::Z),
+        ]
+    }
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        let value = prop_oneof![
+            variable().prop_map(Value::Variable),
+            any::<isize>().prop_map(Value::Literal),
+        ];
+        prop_oneof![
+            variable().prop_map(Instruction::Input),
+            (variable(), value.clone()).prop_map(|(l, r)| Instruction::Add(l, r)),
+            (variable(), value.clone()).prop_map(|(l, r)| Instruction::Multiply(l, r)),
+            (variable(), value.clone()).prop_map(|(l, r)| Instruction::Divide(l, r)),
+            (variable(), value.clone()).prop_map(|(l, r)| Instruction::Modulo(l, r)),
+            (variable(), value).prop_map(|(l, r)| Instruction::Equals(l, r)),
+        ]
+    }
+
+    ::aoc::proptest_roundtrip! {
+        test_instruction: Instruction = instruction(),
+    }
+}
@@ -125,9 +125,9 @@
 // fourteen-digit model number that contains no 0 digits. What is the largest
 // model number accepted by MONAD?
 
-pub fn solve(input: super::Instructions) -> usize {
-    let (_, max) = input.opt();
-    max
+#[macros::solution(year = 2021, day = 24, part = 1)]
+pub fn solve(input: super::Monad) -> usize {
+    input.max
 }
 
 #[cfg(test)]
@@ -24,9 +24,9 @@
 //
 // What is the smallest model number accepted by MONAD?
 
-pub fn solve(input: super::Instructions) -> usize {
-    let (min, _) = input.opt();
-    min
+#[macros::solution(year = 2021, day = 24, part = 2)]
+pub fn solve(input: super::Monad) -> usize {
+    input.min
 }
 
 #[cfg(test)]
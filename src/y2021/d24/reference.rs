@@ -0,0 +1,83 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use super::ArithmeticLogicUnit;
+use super::Instructions;
+
+/// The smallest and largest model numbers that leave `z` at zero, found by
+/// running the ALU on every number in turn, from the lowest up and from the
+/// highest down. Nothing is assumed about the program, so this is only
+/// practical for a handful of digits.
+pub fn opt(instructions: &Instructions) -> (usize, usize) {
+    let digits = instructions.count_inputs() as u32;
+    let accepted = |n: &usize| {
+        let inputs = model_number(*n, digits);
+        ArithmeticLogicUnit::new(instructions, &inputs).is_ok_and(|alu| alu.z == 0)
+    };
+    let numbers = 0..9usize.pow(digits);
+    let min = numbers
+        .clone()
+        .find(accepted)
+        .expect("no model number is accepted");
+    let max = numbers.rev().find(accepted).unwrap();
+    (to_decimal(min, digits), to_decimal(max, digits))
+}
+
+/// The digits, 1 to 9 and most significant first, of the `n`th model number
+/// with `digits` digits.
+fn model_number(n: usize, digits: u32) -> Vec<u8> {
+    (0..digits)
+        .rev()
+        .map(|place| (n / 9usize.pow(place) % 9) as u8 + 1)
+        .collect()
+}
+
+fn to_decimal(n: usize, digits: u32) -> usize {
+    model_number(n, digits)
+        .into_iter()
+        .fold(0, |n, d| n * 10 + d as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Instructions;
+
+    #[test]
+    fn test_differential() {
+        aoc::differential::check(
+            0..32,
+            |rng| {
+                let digits = 2 * rng.below(3) + 2;
+                super::super::gen::program(rng, digits, 20)
+            },
+            |s| s.parse::<Instructions>().unwrap().opt().unwrap(),
+            |s| super::opt(&s.parse().unwrap()),
+        );
+    }
+
+    #[test]
+    fn test_two_digits() {
+        // accepts `ab` when a == b + 3
+        let instructions: Instructions = "inp w\nadd z w\ninp x\nadd x 3\neql z x\nadd z -1\n"
+            .parse()
+            .unwrap();
+        assert_eq!(super::opt(&instructions), (41, 96));
+    }
+}
@@ -23,10 +23,16 @@ pub(crate) mod part1;
 use std::borrow::Cow;
 use std::str::FromStr;
 
-#[derive(Clone, Copy, PartialEq, Eq, macros::Unwrap)]
+use aoc::matrix::GridError;
+use aoc::matrix::Matrix;
+
+#[derive(Clone, Copy, PartialEq, Eq, macros::FromGrid, macros::Unwrap)]
 enum Tile {
+    #[tile('.')]
     Vacant,
+    #[tile('>')]
     Eastward,
+    #[tile('v')]
     Southward,
 }
 
@@ -40,19 +46,6 @@ impl Tile {
     }
 }
 
-impl TryFrom<u8> for Tile {
-    type Error = anyhow::Error;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            b'.' => Ok(Self::Vacant),
-            b'>' => Ok(Self::Eastward),
-            b'v' => Ok(Self::Southward),
-            _ => Err(anyhow::anyhow!("invalid character: {}", value)),
-        }
-    }
-}
-
 type Position = (usize, usize);
 
 #[derive(Clone, macros::TryFromStr)]
@@ -74,6 +67,22 @@ impl OceanFloor {
         }
     }
 
+    /// Every state after the current one, ending with the deadlocked state.
+    #[cfg(feature = "render")]
+    pub fn ticks(&self) -> impl Iterator<Item = Self> {
+        std::iter::successors(self.tick(), Self::tick)
+    }
+
+    #[cfg(feature = "render")]
+    pub fn frame(&self) -> aoc::render::Frame {
+        let matrix = aoc::matrix::Matrix::new(self.width, self.tiles.iter().copied());
+        aoc::render::Frame::new(&matrix, |tile| match tile {
+            Tile::Vacant => [0x00, 0x1f, 0x3f],
+            Tile::Eastward => [0xff, 0x7f, 0x00],
+            Tile::Southward => [0x00, 0xdf, 0x7f],
+        })
+    }
+
     pub fn tick(&self) -> Option<Self> {
         let east = self.tick_impl(Tile::Eastward);
         match east {
@@ -90,7 +99,8 @@ impl OceanFloor {
             .tiles
             .iter()
             .enumerate()
-            .filter_map(|(idx, t)| (*t == needle).then(|| self.idx_to_pos(idx)))
+            .filter(|&(_, t)| *t == needle)
+            .map(|(idx, _)| self.idx_to_pos(idx))
             .filter_map(|idx| {
                 let (pos, t) = self.peek(needle, idx);
                 t.is_vacant().then_some((idx, pos))
@@ -133,26 +143,55 @@ impl OceanFloor {
 }
 
 impl FromStr for OceanFloor {
-    type Err = anyhow::Error;
+    type Err = GridError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut width = 0;
-        let mut tiles = Vec::with_capacity(s.len());
-        for line in s.lines() {
-            if width == 0 {
-                width = line.len();
-            }
-            if line.len() != width {
-                return Err(anyhow::anyhow!("invalid input line: {}", s));
-            }
-            for c in line.bytes() {
-                let t: Tile = c.try_into()?;
-                tiles.push(t)
-            }
-        }
+        let matrix = Matrix::<Tile>::from_grid(s)?;
         Ok(Self {
-            width,
-            tiles: tiles.into_boxed_slice(),
+            width: matrix.width(),
+            tiles: matrix.into_iter().map(|(_, tile)| tile).collect(),
         })
     }
 }
+
+#[cfg(all(test, feature = "render"))]
+mod tests {
+    #[test]
+    fn test_render_example() {
+        let floor: super::OceanFloor = include_str!("inputs/example").parse().unwrap();
+        let frames = std::iter::once(floor.clone())
+            .chain(floor.ticks())
+            .map(|f| f.frame().scale(4))
+            .collect::<Vec<_>>();
+        assert_eq!(frames.len(), floor.tick_to_deadlock().0);
+        assert_eq!((frames[0].width(), frames[0].height()), (40, 36));
+
+        let mut gif = Vec::new();
+        aoc::render::write_gif(&mut gif, frames, 5).unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+    }
+}
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::OceanFloor; [example, live]);
+}
+
+#[cfg(test)]
+mod roundtrip {
+    use aoc::matrix::Matrix;
+
+    use super::Tile;
+
+    #[test]
+    fn test_grid() {
+        for input in [include_str!("inputs/example"), include_str!("inputs/live")] {
+            let matrix = Matrix::<Tile>::from_grid(input).unwrap();
+            assert_eq!(matrix.to_string(), input);
+        }
+        assert_eq!(
+            Tile::try_from(b'x').err().unwrap().to_string(),
+            "unexpected 'x'"
+        );
+    }
+}
@@ -333,6 +333,7 @@
 // Find somewhere safe to land your submarine. What is the first step on which
 // no sea cucumbers move?
 
+#[macros::solution(year = 2021, day = 25, part = 1)]
 pub fn solve(input: super::OceanFloor) -> usize {
     let (v, _) = input.tick_to_deadlock();
     v
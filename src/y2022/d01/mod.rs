@@ -21,17 +21,16 @@
 use std::cmp::Ordering;
 use std::iter::Sum;
 
-mod parser;
 pub(crate) mod part1;
 pub(crate) mod part2;
 
-#[derive(macros::TryFromStr)]
-pub struct Elves(Vec<Elf>);
+#[derive(macros::FromSections, macros::TryFromStr)]
+pub struct Elves(#[section(each)] Vec<Elf>);
 
+#[derive(macros::FromLines)]
+#[from_lines(usize)]
 pub struct Elf(Vec<usize>);
 
-::aoc::derive_FromStr_for_nom!(Elves, parser::elves);
-
 impl Elf {
     pub fn sum(&self) -> usize {
         self.0.iter().sum()
@@ -48,7 +47,7 @@ impl Eq for Elf {}
 
 impl PartialOrd for Elf {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.sum().partial_cmp(&other.sum())
+        Some(self.cmp(other))
     }
 }
 
@@ -63,3 +62,24 @@ impl<'a> Sum<&'a Elf> for usize {
         iter.map(|e| e.sum()).sum()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Elves;
+
+    #[test]
+    fn test_parse_example() {
+        let Elves(elves) = include_str!("inputs/example").parse().unwrap();
+        assert_eq!(elves.len(), 5);
+    }
+
+    #[test]
+    fn test_parse_live() {
+        let _: Elves = include_str!("inputs/live").parse().unwrap();
+    }
+}
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Elves; [example, live]);
+}
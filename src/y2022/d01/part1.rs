@@ -78,8 +78,9 @@
 // carrying?
 //
 
+#[macros::solution(year = 2022, day = 1, part = 1)]
 pub fn solve(input: super::Elves) -> usize {
-    input.0.last().unwrap().sum()
+    input.0.iter().max().unwrap().sum()
 }
 
 #[cfg(test)]
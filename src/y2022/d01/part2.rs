@@ -36,8 +36,11 @@
 // Find the top three Elves carrying the most Calories. How many Calories are
 // those Elves carrying in total?
 
+#[macros::solution(year = 2022, day = 1, part = 2)]
 pub fn solve(input: super::Elves) -> usize {
-    input.0.iter().rev().take(3).sum()
+    let mut elves = input.0;
+    elves.sort_unstable();
+    elves.iter().rev().take(3).sum()
 }
 
 #[cfg(test)]
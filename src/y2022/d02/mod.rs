@@ -169,5 +169,10 @@ impl InstructionP2 {
 ::aoc::derive_FromStr_for_nom!(Instruction, parser::instruction);
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, macros::FromLines)]
-#[from_lines(Instruction)]
+#[from_lines(Instruction, at_line)]
 struct Instructions(Vec<Instruction>);
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Instructions; [example, live]);
+}
@@ -68,6 +68,7 @@
 // What would your total score be if everything goes exactly according to your
 // strategy guide?
 
+#[macros::solution(year = 2022, day = 2, part = 1)]
 fn solve(s: super::Instructions) -> usize {
     s.0.iter().map(|i| i.p1().score()).sum()
 }
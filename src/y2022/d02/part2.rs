@@ -39,6 +39,7 @@
 // Following the Elf's instructions for the second column, what would your total
 // score be if everything goes exactly according to your strategy guide?
 
+#[macros::solution(year = 2022, day = 2, part = 2)]
 fn solve(s: super::Instructions) -> usize {
     s.0.iter().map(|i| i.p2().score()).sum()
 }
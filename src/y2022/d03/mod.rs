@@ -43,14 +43,26 @@ impl Display for Priority {
     }
 }
 
+/// A byte that is not an item letter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidItem(u8);
+
+impl Display for InvalidItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid item: {:?}", self.0 as char)
+    }
+}
+
+impl std::error::Error for InvalidItem {}
+
 impl TryFrom<u8> for Priority {
-    type Error = u8;
+    type Error = InvalidItem;
 
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
             b'a'..=b'z' => Ok(Self(value - b'a' + 1)),
             b'A'..=b'Z' => Ok(Self(value - b'A' + 27)),
-            _ => Err(value),
+            _ => Err(InvalidItem(value)),
         }
     }
 }
@@ -71,3 +83,22 @@ impl Sack {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, macros::FromLines)]
 #[from_lines(Sack)]
 pub struct Sacks(Vec<Sack>);
+
+#[cfg(test)]
+mod tests {
+    use super::InvalidItem;
+    use super::Sacks;
+
+    #[test]
+    fn test_invalid_item() {
+        let err = "vJrwpWtwJgWr\nab1c\n".parse::<Sacks>().unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.error, InvalidItem(b'1'));
+        assert_eq!(err.to_string(), "line 2: invalid item: '1'");
+    }
+}
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Sacks; [example, live]);
+}
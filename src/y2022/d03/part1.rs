@@ -105,6 +105,7 @@ fn solve_sack(mut sack: Sack) -> usize {
     }
 }
 
+#[macros::solution(year = 2022, day = 3, part = 1)]
 fn solve(sacks: Sacks) -> usize {
     sacks.0.iter().cloned().map(solve_sack).sum()
 }
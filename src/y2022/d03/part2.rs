@@ -93,6 +93,7 @@ fn solve_chunk((mut s1, mut s2, mut s3): (Sack, Sack, Sack)) -> usize {
     }
 }
 
+#[macros::solution(year = 2022, day = 3, part = 2)]
 fn solve(sacks: super::Sacks) -> usize {
     sacks.0.iter().cloned().tuples().map(solve_chunk).sum()
 }
@@ -36,9 +36,9 @@ impl Assignment {
     }
 }
 
-impl From<(u8, u8)> for Assignment {
-    fn from((start, end): (u8, u8)) -> Self {
-        Self(start..=end)
+impl From<RangeInclusive<u8>> for Assignment {
+    fn from(range: RangeInclusive<u8>) -> Self {
+        Self(range)
     }
 }
 
@@ -46,12 +46,9 @@ struct AssignmentPair(Assignment, Assignment);
 
 ::aoc::derive_FromStr_for_nom!(AssignmentPair, parser::assignment_pair);
 
-impl From<((u8, u8), (u8, u8))> for AssignmentPair {
-    fn from(((start1, end1), (start2, end2)): ((u8, u8), (u8, u8))) -> Self {
-        Self(
-            Assignment::from((start1, end1)),
-            Assignment::from((start2, end2)),
-        )
+impl From<(RangeInclusive<u8>, RangeInclusive<u8>)> for AssignmentPair {
+    fn from((first, second): (RangeInclusive<u8>, RangeInclusive<u8>)) -> Self {
+        Self(Assignment::from(first), Assignment::from(second))
     }
 }
 
@@ -70,5 +67,10 @@ impl AssignmentPair {
 }
 
 #[derive(macros::FromLines)]
-#[from_lines(AssignmentPair)]
+#[from_lines(AssignmentPair, at_line)]
 struct Assignments(Vec<AssignmentPair>);
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Assignments; [example, live]);
+}
@@ -18,8 +18,8 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use aoc::nom::range_inclusive;
 use nom::character::complete::char;
-use nom::character::complete::u8;
 use nom::combinator::map;
 use nom::sequence::separated_pair;
 use nom::IResult;
@@ -28,11 +28,7 @@ use super::AssignmentPair;
 
 pub(super) fn assignment_pair(s: &str) -> IResult<&str, AssignmentPair> {
     map(
-        separated_pair(
-            separated_pair(u8, char('-'), u8),
-            char(','),
-            separated_pair(u8, char('-'), u8),
-        ),
+        separated_pair(range_inclusive("-"), char(','), range_inclusive("-")),
         AssignmentPair::from,
     )(s)
 }
@@ -75,6 +75,7 @@
 // In how many assignment pairs does one range fully contain the other?
 //
 
+#[macros::solution(year = 2022, day = 4, part = 1)]
 fn solve(input: super::Assignments) -> usize {
     input
         .0
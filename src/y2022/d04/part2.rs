@@ -35,6 +35,7 @@
 // In how many assignment pairs do the ranges overlap?
 //
 
+#[macros::solution(year = 2022, day = 4, part = 2)]
 fn solve(input: super::Assignments) -> usize {
     input
         .0
@@ -22,8 +22,8 @@ mod parser;
 pub(crate) mod part1;
 pub(crate) mod part2;
 
-#[derive(derive_more::Display, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-#[display(fmt = "move {quantity} from {from} to {to}")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, macros::Scan)]
+#[scan("move {quantity} from {from} to {to}")]
 struct Instruction {
     quantity: u8,
     from: u8,
@@ -33,13 +33,17 @@ struct Instruction {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct Slot(Option<char>);
 
-#[derive(macros::TryFromStr)]
+/// The starting stacks, one row of slots per line from the top down.
+#[derive(Clone, Debug)]
+struct Drawing(Vec<Vec<Slot>>);
+
+#[derive(macros::FromSections, macros::TryFromStr)]
 struct Input {
-    rows: Vec<Vec<Slot>>,
+    drawing: Drawing,
     instructions: Vec<Instruction>,
 }
 
-::aoc::derive_FromStr_for_nom!(Input, parser::input);
+::aoc::derive_FromStr_for_nom!(Drawing, parser::drawing);
 
 #[derive(Debug)]
 struct State {
@@ -89,8 +93,8 @@ impl State {
     }
 }
 
-impl From<Vec<Vec<Slot>>> for State {
-    fn from(rows: Vec<Vec<Slot>>) -> Self {
+impl From<Drawing> for State {
+    fn from(Drawing(rows): Drawing) -> Self {
         // input rows are top to bottom, left to right
         // output columns are left to right, bottom to top
 
@@ -113,3 +117,42 @@ impl From<Vec<Vec<Slot>>> for State {
         Self { columns }
     }
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Input; [example, live]);
+}
+
+#[cfg(all(test, feature = "proptest"))]
+mod roundtrip {
+    use proptest::prelude::*;
+
+    use super::Instruction;
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        any::<(u8, u8, u8)>().prop_map(|(quantity, from, to)| Instruction { quantity, from, to })
+    }
+
+    ::aoc::proptest_roundtrip! {
+        test_instruction: Instruction = instruction(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Input;
+
+    #[test]
+    fn test_error_location() {
+        let input = "[A]\n 1 \n\nmove 1 from 1 to 1\nmove 1 from x to 1\n";
+        let err = input.parse::<Input>().err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "in `instructions` at line 5: error: expected `from` (invalid digit found in string)
+ --> 5:13
+  |
+5 | move 1 from x to 1
+  |             ^"
+        );
+    }
+}
@@ -25,14 +25,11 @@ use nom::character::complete::u8;
 use nom::combinator::map;
 use nom::multi::separated_list1;
 use nom::sequence::delimited;
-use nom::sequence::preceded;
 use nom::sequence::separated_pair;
 use nom::sequence::terminated;
-use nom::sequence::tuple;
 use nom::IResult;
 
-use super::Input;
-use super::Instruction;
+use super::Drawing;
 use super::Slot;
 
 fn slot(s: &str) -> IResult<&str, Slot> {
@@ -53,31 +50,15 @@ fn rows(s: &str) -> IResult<&str, Vec<Vec<Slot>>> {
     separated_list1(line_ending, row)(s)
 }
 
-fn instruction(s: &str) -> IResult<&str, Instruction> {
-    map(
-        tuple((
-            preceded(tag("move "), u8),
-            preceded(tag(" from "), u8),
-            preceded(tag(" to "), u8),
-        )),
-        |(quantity, from, to)| Instruction { quantity, from, to },
-    )(s)
-}
-
 fn col_ident(s: &str) -> IResult<&str, ()> {
     let seq = separated_pair(tag(" "), u8, tag(" "));
     let list = separated_list1(tag(" "), seq);
     map(list, |_| ())(s)
 }
 
-pub(super) fn input(s: &str) -> IResult<&str, Input> {
+pub(super) fn drawing(s: &str) -> IResult<&str, Drawing> {
     map(
-        tuple((
-            terminated(rows, line_ending),
-            terminated(col_ident, line_ending),
-            line_ending,
-            separated_list1(line_ending, instruction),
-        )),
-        |(rows, _, _, instructions)| Input { rows, instructions },
+        terminated(terminated(rows, line_ending), col_ident),
+        Drawing,
     )(s)
 }
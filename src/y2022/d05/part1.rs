@@ -91,8 +91,9 @@
 // each stack?
 //
 
+#[macros::solution(year = 2022, day = 5, part = 1)]
 fn solve(input: super::Input) -> String {
-    let mut state: super::State = input.rows.clone().into();
+    let mut state: super::State = input.drawing.clone().into();
     for i in input.instructions.iter().copied() {
         state.execute(i, false);
     }
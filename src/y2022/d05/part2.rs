@@ -77,8 +77,9 @@
 //
 //
 
+#[macros::solution(year = 2022, day = 5, part = 2)]
 fn solve(input: super::Input) -> String {
-    let mut state: super::State = input.rows.clone().into();
+    let mut state: super::State = input.drawing.clone().into();
     for i in input.instructions.iter().copied() {
         state.execute(i, true);
     }
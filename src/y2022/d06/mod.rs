@@ -18,11 +18,84 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use std::str::FromStr;
+
 use itertools::Itertools;
 
 pub(crate) mod part1;
 pub(crate) mod part2;
 
-fn find_unique_len(s: &[u8], len: usize) -> usize {
-    s.windows(len).position(|w| w.iter().all_unique()).unwrap() + len
+/// The length of a start-of-packet marker.
+const PACKET_LEN: usize = 4;
+/// The length of a start-of-message marker, which is the longer of the two.
+const MESSAGE_LEN: usize = 14;
+
+/// Why a datastream could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum ParseError {
+    #[display(fmt = "unexpected {_0:?} in the datastream")]
+    Char(char),
+    #[display(fmt = "no {MESSAGE_LEN} characters in a row are all different")]
+    NoMarker,
+}
+
+impl std::error::Error for ParseError {}
+
+/// A buffer of lowercase letters with a start-of-message marker somewhere in
+/// it.
+#[derive(Debug, Clone, PartialEq, Eq, macros::TryFromStr)]
+pub struct Datastream(Vec<u8>);
+
+impl FromStr for Datastream {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_suffix('\n').unwrap_or(s);
+        if let Some(c) = s.chars().find(|c| !c.is_ascii_lowercase()) {
+            return Err(ParseError::Char(c));
+        }
+        let this = Self(s.as_bytes().to_vec());
+        match this.marker(MESSAGE_LEN) {
+            Some(_) => Ok(this),
+            None => Err(ParseError::NoMarker),
+        }
+    }
+}
+
+impl Datastream {
+    /// How many characters have been read when the first `len` in a row are
+    /// all different.
+    fn marker(&self, len: usize) -> Option<usize> {
+        self.0
+            .windows(len)
+            .position(|w| w.iter().all_unique())
+            .map(|idx| idx + len)
+    }
+
+    /// [`Datastream::marker`] for a `len` no longer than [`MESSAGE_LEN`],
+    /// which parsing guarantees is there.
+    fn find_unique_len(&self, len: usize) -> usize {
+        debug_assert!(len <= MESSAGE_LEN);
+        self.marker(len).expect("checked when parsed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Datastream;
+    use super::ParseError;
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!("abcD".parse::<Datastream>(), Err(ParseError::Char('D')));
+        assert_eq!(
+            "abcdefghijklm\n".parse::<Datastream>(),
+            Err(ParseError::NoMarker)
+        );
+    }
+}
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Datastream; [example1, example2, example3, example4, example5, live]);
 }
@@ -71,8 +71,9 @@
 // marker is detected?
 //
 
-fn solve(input: &str) -> usize {
-    super::find_unique_len(input.as_bytes(), 4)
+#[macros::solution(year = 2022, day = 6, part = 1)]
+fn solve(input: super::Datastream) -> usize {
+    input.find_unique_len(super::PACKET_LEN)
 }
 
 #[cfg(test)]
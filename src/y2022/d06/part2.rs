@@ -37,8 +37,9 @@
 // marker is detected?
 //
 
-fn solve(input: &str) -> usize {
-    super::find_unique_len(input.as_bytes(), 14)
+#[macros::solution(year = 2022, day = 6, part = 2)]
+fn solve(input: super::Datastream) -> usize {
+    input.find_unique_len(super::MESSAGE_LEN)
 }
 
 #[cfg(test)]
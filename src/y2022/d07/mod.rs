@@ -24,10 +24,10 @@ pub(crate) mod part2;
 
 use hashbrown::HashMap;
 
-#[derive(Debug, PartialEq, macros::TryFromStr)]
-struct Shell(Vec<Command>);
+#[derive(Debug, PartialEq)]
+struct Shell<'a>(Vec<Command<'a>>);
 
-::aoc::derive_FromStr_for_nom!(Shell, parser::shell);
+::aoc::derive_TryFrom_bytes_for_nom!(Shell<'a>, parser::shell);
 
 #[derive(Debug)]
 struct Filesystem {
@@ -36,20 +36,21 @@ struct Filesystem {
 }
 
 #[derive(Debug, PartialEq, macros::Unwrap)]
-enum Command {
-    ChangeDir(String),
-    List(Vec<ListLine>),
+#[unwrap(kind)]
+enum Command<'a> {
+    ChangeDir(&'a str),
+    List(Vec<ListLine<'a>>),
 }
 
 #[derive(Debug, PartialEq, macros::Unwrap)]
-enum ListLine {
-    File(FileLine),
-    Directory(String),
+enum ListLine<'a> {
+    File(FileLine<'a>),
+    Directory(&'a str),
 }
 
 #[derive(Debug, PartialEq)]
-struct FileLine {
-    name: String,
+struct FileLine<'a> {
+    name: &'a str,
     size: usize,
 }
 
@@ -88,18 +89,18 @@ struct DirectoryNode {
     directories_size: usize,
 }
 
-impl Shell {
+impl Shell<'_> {
     fn evaluate(self) -> Filesystem {
         let mut fs = Filesystem::new();
 
         for cmd in self.0 {
             match cmd {
-                Command::ChangeDir(path) => fs.chdir(&path),
+                Command::ChangeDir(path) => fs.chdir(path),
                 Command::List(output) => {
                     for line in output {
                         match line {
-                            ListLine::File(FileLine { name, size }) => fs.touch(&name, size),
-                            ListLine::Directory(name) => fs.mkdir(&name),
+                            ListLine::File(FileLine { name, size }) => fs.touch(name, size),
+                            ListLine::Directory(name) => fs.mkdir(name),
                         };
                     }
                 }
@@ -226,25 +227,52 @@ impl Filesystem {
     }
 }
 
-impl Command {
-    fn chdir(s: &str) -> Self {
-        Self::ChangeDir(s.to_owned())
+impl<'a> Command<'a> {
+    fn chdir(s: &'a str) -> Self {
+        Self::ChangeDir(s)
     }
 
-    fn list(v: Vec<ListLine>) -> Self {
+    fn list(v: Vec<ListLine<'a>>) -> Self {
         Self::List(v)
     }
 }
 
-impl ListLine {
-    fn file(s: &str, size: usize) -> Self {
-        Self::File(FileLine {
-            name: s.to_owned(),
-            size,
-        })
+impl<'a> ListLine<'a> {
+    fn file(name: &'a str, size: usize) -> Self {
+        Self::File(FileLine { name, size })
     }
 
-    fn directory(s: &str) -> Self {
-        Self::Directory(s.to_owned())
+    fn directory(s: &'a str) -> Self {
+        Self::Directory(s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Command;
+    use super::CommandKind;
+    use super::Shell;
+
+    #[test]
+    fn test_commands() {
+        let Shell(commands) = Shell::try_from(include_bytes!("inputs/example").as_slice()).unwrap();
+        let kinds = commands.iter().map(Command::kind).collect::<Vec<_>>();
+        assert_eq!(kinds.iter().filter(|&&k| k == CommandKind::List).count(), 4);
+        assert_eq!(kinds[..2], [CommandKind::ChangeDir, CommandKind::List]);
+
+        let mut commands = commands.into_iter();
+        let root = commands.next().unwrap().map_change_dir(|_| "~");
+        assert_eq!(root, Command::ChangeDir("~"));
+        let root = root.try_into_list().unwrap_err();
+        assert_eq!(root.try_into_change_dir(), Ok("~"));
+        assert_eq!(
+            commands.next().unwrap().try_into_list().map(|v| v.len()),
+            Ok(4)
+        );
+    }
+}
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(bytes super::Shell<'_>; [example, live]);
+}
@@ -126,7 +126,8 @@
 // sum of the total sizes of those directories?
 //
 
-fn solve(shell: super::Shell) -> usize {
+#[macros::solution(year = 2022, day = 7, part = 1, bytes)]
+fn solve(shell: super::Shell<'_>) -> usize {
     let fs = shell.evaluate();
     let mut total = 0;
     for node in fs.walk() {
@@ -141,7 +142,7 @@ fn solve(shell: super::Shell) -> usize {
 
 #[cfg(test)]
 mod test {
-    ::aoc::tests_for_problem!(super::solve, {
+    ::aoc::tests_for_problem!(bytes super::solve, {
         example => 95437,
         live => 1390824,
     });
@@ -46,7 +46,8 @@
 // the filesystem to run the update. What is the total size of that directory?
 //
 
-fn solve(shell: super::Shell) -> usize {
+#[macros::solution(year = 2022, day = 7, part = 2, bytes)]
+fn solve(shell: super::Shell<'_>) -> usize {
     const CAP: usize = 70_000_000;
     const REQ: usize = 30_000_000;
 
@@ -73,7 +74,7 @@ fn solve(shell: super::Shell) -> usize {
 
 #[cfg(test)]
 mod test {
-    ::aoc::tests_for_problem!(super::solve, {
+    ::aoc::tests_for_problem!(bytes super::solve, {
         example => 24933642,
         live => 7490863,
     });
@@ -21,6 +21,7 @@
 pub(crate) mod part1;
 pub(crate) mod part2;
 
+use ::aoc::matrix::GridError;
 use ::aoc::matrix::Matrix;
 use aoc::matrix::Position;
 use aoc::matrix::RelativePosition;
@@ -55,14 +56,11 @@ impl From<u8> for Camp {
 
 struct Forest<T>(Matrix<T>);
 
-impl From<&str> for Forest<u8> {
-    fn from(value: &str) -> Self {
-        Self(
-            value
-                .lines()
-                .map(|s| s.bytes().map(::aoc::chardigit))
-                .collect(),
-        )
+impl TryFrom<&str> for Forest<u8> {
+    type Error = GridError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Matrix::try_from_grid(value, aoc::digit).map(Self)
     }
 }
 
@@ -173,3 +171,8 @@ impl Forest<Camp> {
         self.0.iter().map(|(_, t)| t.score).max().unwrap()
     }
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Forest<u8>; [example, live]);
+}
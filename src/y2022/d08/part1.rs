@@ -62,6 +62,7 @@
 // Consider your map; how many trees are visible from outside the grid?
 //
 
+#[macros::solution(year = 2022, day = 8, part = 1)]
 fn solve(forest: super::Forest<u8>) -> usize {
     forest.visible()
 }
@@ -68,6 +68,7 @@
 //
 //
 
+#[macros::solution(year = 2022, day = 8, part = 2)]
 fn solve(forest: super::Forest<u8>) -> usize {
     forest.score()
 }
@@ -19,58 +19,63 @@
 // SOFTWARE.
 
 use std::fmt::Display;
-use std::str::FromStr;
+use std::num::NonZeroUsize;
 
 use euclid::Point2D;
 use hashbrown::HashSet;
 
-mod parser;
 pub(crate) mod part1;
 pub(crate) mod part2;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, macros::Scan)]
 enum Step {
+    #[scan("U")]
     Up,
+    #[scan("D")]
     Down,
+    #[scan("L")]
     Left,
+    #[scan("R")]
     Right,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, macros::Scan)]
+#[scan("{step} {distance}")]
 struct Instruction {
     step: Step,
     distance: usize,
 }
 
-impl Display for Instruction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?} {}", self.step, self.distance)
-    }
-}
-
-::aoc::derive_FromStr_for_nom!(Instruction, parser::instruction);
-
-#[derive(macros::FromLines)]
-#[from_lines(Instruction)]
+#[derive(macros::FromLines, macros::Display)]
+#[from_lines(Instruction, at_line)]
 struct Instructions(Vec<Instruction>);
 
-struct Rope<const N: usize> {
-    knots: [Point2D<isize, Self>; N],
+struct Rope {
+    knots: Vec<Point2D<isize, Self>>,
     tails: HashSet<Point2D<isize, Self>>,
 }
 
-impl<const N: usize> Rope<N> {
-    fn new() -> Self {
+impl Rope {
+    fn new(knots: NonZeroUsize) -> Self {
         let mut tails = HashSet::new();
         tails.insert(Point2D::new(0, 0));
         Self {
-            knots: [Point2D::new(0, 0); N],
+            knots: vec![Point2D::new(0, 0); knots.get()],
             tails,
         }
     }
 
-    const fn tail(&self) -> Point2D<isize, Self> {
-        self.knots[N - 1]
+    /// A rope of `knots` knots whose head has followed `instructions`.
+    fn follow(knots: NonZeroUsize, Instructions(instructions): &Instructions) -> Self {
+        let mut this = Self::new(knots);
+        for &instruction in instructions {
+            this.instruction(instruction);
+        }
+        this
+    }
+
+    fn tail(&self) -> Point2D<isize, Self> {
+        self.knots[self.knots.len() - 1]
     }
 
     fn num_visited(&self) -> usize {
@@ -94,7 +99,7 @@ impl<const N: usize> Rope<N> {
             }
         }
 
-        for n in 1..N {
+        for n in 1..self.knots.len() {
             let (head, tail) = self.knots.split_at_mut(n);
             ripple(&head[n - 1], &mut tail[0]);
         }
@@ -111,20 +116,7 @@ impl<const N: usize> Rope<N> {
     }
 }
 
-impl<const N: usize> TryFrom<&str> for Rope<N> {
-    type Error = <Instruction as FromStr>::Err;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut this = Self::new();
-        for line in value.lines() {
-            let instruction = line.parse()?;
-            this.instruction(instruction);
-        }
-        Ok(this)
-    }
-}
-
-impl<const N: usize> Display for Rope<N> {
+impl Display for Rope {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let (max_x, max_y) = self
             .tails
@@ -161,3 +153,39 @@ impl<const N: usize> Display for Rope<N> {
         Ok(())
     }
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Instructions; [example, example2, live]);
+}
+
+#[cfg(all(test, feature = "proptest"))]
+mod roundtrip {
+    use proptest::prelude::*;
+
+    use super::Instruction;
+    use super::Step;
+
+    fn instruction() -> impl Strategy<Value = Instruction> {
+        let step = prop_oneof![
+            Just(Step::Up),
+            Just(Step::Down),
+            Just(Step::Left),
+            Just(Step::Right),
+        ];
+        (step, any::<usize>()).prop_map(|(step, distance)| Instruction { step, distance })
+    }
+
+    ::aoc::proptest_roundtrip! {
+        test_instruction: Instruction = instruction(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    macros::test_roundtrip!(
+        super::Instructions,
+        include_str!("inputs/example"),
+        include_str!("inputs/live")
+    );
+}
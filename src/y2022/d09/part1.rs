@@ -282,14 +282,25 @@
 //! Simulate your complete hypothetical series of motions. How many positions
 //! does the tail of the rope visit at least once?
 
-fn solve(field: super::Rope<2>) -> usize {
-    field.num_visited()
+::aoc::params! {
+    pub struct Params {
+        /// Knots in the rope, counting the head.
+        knots: std::num::NonZeroUsize = std::num::NonZeroUsize::new(2).unwrap(),
+    }
+}
+
+#[macros::solution(year = 2022, day = 9, part = 1)]
+fn solve(instructions: super::Instructions, params: Params) -> usize {
+    super::Rope::follow(params.knots, &instructions).num_visited()
 }
 
 #[cfg(test)]
 mod tests {
-    ::aoc::tests_for_problem!(super::solve, {
+    ::aoc::tests_for_problem!(params super::solve, {
         example => 13,
         live => 6098,
+        #[input(example2)]
+        #[params(ten_knots)]
+        example2_ten_knots => 36,
     });
 }
@@ -461,15 +461,26 @@
 //! Simulate your complete series of motions on a larger rope with ten knots.
 //! How many positions does the tail of the rope visit at least once?
 
-fn solve(chain: super::Rope<10>) -> usize {
-    chain.num_visited()
+::aoc::params! {
+    pub struct Params {
+        /// Knots in the rope, counting the head.
+        knots: std::num::NonZeroUsize = std::num::NonZeroUsize::new(10).unwrap(),
+    }
+}
+
+#[macros::solution(year = 2022, day = 9, part = 2)]
+fn solve(instructions: super::Instructions, params: Params) -> usize {
+    super::Rope::follow(params.knots, &instructions).num_visited()
 }
 
 #[cfg(test)]
 mod tests {
-    ::aoc::tests_for_problem!(super::solve, {
+    ::aoc::tests_for_problem!(params super::solve, {
         example => 1,
         example2 => 36,
         live => 2597,
+        #[input(example)]
+        #[params(two_knots)]
+        example_two_knots => 13,
     });
 }
@@ -0,0 +1,146 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use aoc::gen::Rng;
+use aoc::matrix::Matrix;
+use aoc::matrix::Position;
+
+const WIDTH: usize = 40;
+const CYCLES: usize = 6 * WIDTH;
+
+/// Sprite positions to try; the two at either end light nothing.
+const XS: std::ops::RangeInclusive<isize> = -2..=WIDTH as isize + 1;
+
+/// A program that draws eight random letters on the CRT, followed by more
+/// instructions up to about 145 lines at scale 1.
+#[macros::generator(year = 2022, day = 10)]
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let alphabet = aoc::ocr::alphabet(6).unwrap().chars().collect::<Vec<_>>();
+    let xs = loop {
+        // the sprite starts at 1, so not every first letter can be drawn
+        let text = (0..WIDTH / 5)
+            .map(|_| *rng.choose(&alphabet))
+            .collect::<String>();
+        let screen = aoc::ocr::draw(&text, 6).unwrap();
+        if let Some(xs) = sprite_positions(rng, &screen) {
+            break xs;
+        }
+    };
+
+    let mut lines = Vec::new();
+    let mut x = 1;
+    let mut run = 0;
+    for next in xs {
+        if next != x {
+            lines.extend(std::iter::repeat_n("noop".to_owned(), run - 2));
+            lines.push(format!("addx {}", next - x));
+            x = next;
+            run = 0;
+        }
+        run += 1;
+    }
+    lines.extend(std::iter::repeat_n("noop".to_owned(), run));
+
+    while lines.len() < 145 * scale {
+        lines.push(match rng.range(-10..=10) {
+            0 => "noop".to_owned(),
+            v => format!("addx {v}"),
+        });
+    }
+    let mut out = lines.join("\n");
+    out.push('\n');
+    out
+}
+
+/// Choose the sprite's position on each cycle so that exactly the lit
+/// pixels of `screen` are drawn, or `None` if that is impossible.
+///
+/// The position can only change after an `addx`, which takes two cycles, so
+/// every position except the last is held for at least two cycles.
+fn sprite_positions(rng: &mut Rng, screen: &Matrix<bool>) -> Option<Vec<isize>> {
+    let lit = |x: isize, cycle: usize| {
+        let col = (cycle % WIDTH) as isize;
+        let pixel = screen[Position::new(cycle / WIDTH, cycle % WIDTH)];
+        pixel == (x - 1..=x + 1).contains(&col)
+    };
+    let n = XS.count();
+    let idx = |x: isize| (x - XS.start()) as usize;
+
+    // done[cycle][x][held] is whether the rest of the screen can be drawn
+    // with the sprite at x on this cycle, where `held` is whether it has
+    // been there for at least two cycles
+    let mut done = vec![vec![[false; 2]; n]; CYCLES];
+    for x in XS {
+        done[CYCLES - 1][idx(x)] = [lit(x, CYCLES - 1); 2];
+    }
+    for cycle in (0..CYCLES - 1).rev() {
+        let any_move = XS.clone().any(|x| done[cycle + 1][idx(x)][0]);
+        for x in XS {
+            if lit(x, cycle) {
+                let stay = done[cycle + 1][idx(x)][1];
+                done[cycle][idx(x)] = [stay, stay || any_move];
+            }
+        }
+    }
+
+    let mut x = 1;
+    let mut held = false;
+    if !done[0][idx(x)][0] {
+        return None;
+    }
+    let mut xs = vec![x];
+    for done in &done[1..] {
+        let stay = done[idx(x)][1];
+        let moves = if held {
+            XS.clone()
+                .filter(|&to| to != x && done[idx(to)][0])
+                .collect()
+        } else {
+            Vec::new()
+        };
+        if stay && (moves.is_empty() || rng.chance(50)) {
+            held = true;
+        } else {
+            x = *rng.choose(&moves);
+            held = false;
+        }
+        xs.push(x);
+    }
+    Some(xs)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::gen::Rng;
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        let alphabet = aoc::ocr::alphabet(6).unwrap();
+        for seed in 0..8 {
+            let input = super::generate(&mut Rng::new(seed), 1);
+            assert!(input.lines().count() >= 145);
+            aoc::parse_and_solve(input.as_str(), super::super::part1::solve).unwrap();
+            let crt = super::super::Crt::try_from(input.as_str()).unwrap();
+            let text = super::super::part2::solve(crt).unwrap().to_string();
+            assert_eq!(text.len(), 8);
+            assert!(text.chars().all(|c| alphabet.contains(c)), "{text}");
+        }
+    }
+}
@@ -21,6 +21,7 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
+pub(crate) mod gen;
 mod parser;
 pub(crate) mod part1;
 pub(crate) mod part2;
@@ -83,7 +84,6 @@ impl TryFrom<&str> for Clock {
         let mut this = Self::new();
         for line in value.lines() {
             let inst: Instruction = line.parse()?;
-            // eprintln!("instruction: {inst:?}\n{this:?}");
             this.instruction(inst);
         }
         Ok(this)
@@ -122,8 +122,9 @@ impl Crt {
     fn tick(&mut self) {
         let col = self.tick as isize % 40;
         self.tick += 1;
-        if (self.x - 1..=self.x + 1).contains(&col) {
-            self.pixels[self.tick - 1] = true;
+        // the program may keep running after the screen is drawn
+        if let Some(pixel) = self.pixels.get_mut(self.tick - 1) {
+            *pixel = (self.x - 1..=self.x + 1).contains(&col);
         }
     }
 }
@@ -135,7 +136,6 @@ impl TryFrom<&str> for Crt {
         let mut this = Self::new();
         for line in value.lines() {
             let inst: Instruction = line.parse()?;
-            eprintln!("instruction: {inst:?}\n{this}");
             this.instruction(inst);
         }
         Ok(this)
@@ -154,3 +154,8 @@ impl Display for Crt {
         Ok(())
     }
 }
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Clock, super::Crt; [example0, example1, live]);
+}
@@ -232,7 +232,8 @@
 //! Find the signal strength during the 20th, 60th, 100th, 140th, 180th, and
 //! 220th cycles. What is the sum of these six signal strengths?
 
-fn solve(clock: super::Clock) -> isize {
+#[macros::solution(year = 2022, day = 10, part = 1)]
+pub(super) fn solve(clock: super::Clock) -> isize {
     clock.signal_strengths()
 }
 
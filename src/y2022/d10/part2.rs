@@ -158,14 +158,25 @@
 //! Render the image given by your program. What eight capital letters appear on
 //! your CRT?
 
-fn solve(crt: super::Crt) -> String {
+fn render(crt: super::Crt) -> String {
     crt.to_string()
 }
 
+#[macros::solution(year = 2022, day = 10, part = 2)]
+pub(super) fn solve(crt: super::Crt) -> anyhow::Result<aoc::Answer> {
+    aoc::ocr::recognize_answer(&render(crt))
+}
+
 #[cfg(test)]
 mod test {
-    ::aoc::tests_for_problem!(super::solve, {
-        example1 => "##..##..##..##..##..##..##..##..##..##..\n###...###...###...###...###...###...###.\n####....####....####....####....####....\n#####.....#####.....#####.....#####.....\n######......######......######......####\n#######.......#######.......#######.....\n",
-        live => "###..#....####.####.####.#.....##..####.\n#..#.#....#.......#.#....#....#..#.#....\n#..#.#....###....#..###..#....#....###..\n###..#....#.....#...#....#....#.##.#....\n#.#..#....#....#....#....#....#..#.#....\n#..#.####.####.####.#....####..###.####.\n",
+    ::aoc::tests_for_problem!(|input: super::super::Crt| super::solve(input).unwrap(), {
+        live => aoc::Answer::Str("RLEZFLGE".to_owned()),
     });
+
+    mod render {
+        ::aoc::tests_for_problem!(super::super::render, {
+            example1 => "##..##..##..##..##..##..##..##..##..##..\n###...###...###...###...###...###...###.\n####....####....####....####....####....\n#####.....#####.....#####.....#####.....\n######......######......######......####\n#######.......#######.......#######.....\n",
+            live => "###..#....####.####.####.#.....##..####.\n#..#.#....#.......#.#....#....#..#.#....\n#..#.#....###....#..###..#....#....###..\n###..#....#.....#...#....#....#.##.#....\n#.#..#....#....#....#....#....#..#.#....\n#..#.####.####.####.#....####..###.####.\n",
+        });
+    }
 }
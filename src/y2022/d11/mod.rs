@@ -34,7 +34,6 @@ enum Operation {
 struct Item(usize);
 
 struct Monkey {
-    #[allow(dead_code)]
     id: usize,
     items: Vec<Item>,
     operation: Operation,
@@ -47,6 +46,27 @@ struct Monkey {
 
 ::aoc::derive_FromStr_for_nom!(Monkey, parser::monkey);
 
+/// The note as [`parser::monkey`] reads it.
+impl Display for Monkey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let items = self.items.iter().map(|Item(item)| item).join(", ");
+        let op = match self.operation {
+            Operation::Add => '+',
+            Operation::Multiply => '*',
+        };
+        let operand = match self.operand {
+            Some(operand) => operand.to_string(),
+            None => "old".to_owned(),
+        };
+        writeln!(f, "Monkey {}:", self.id)?;
+        writeln!(f, "  Starting items: {items}")?;
+        writeln!(f, "  Operation: new = old {op} {operand}")?;
+        writeln!(f, "  Test: divisible by {}", self.divisor)?;
+        writeln!(f, "    If true: throw to monkey {}", self.toss_true)?;
+        writeln!(f, "    If false: throw to monkey {}", self.toss_false)
+    }
+}
+
 impl Monkey {
     fn tick(&mut self, mut func: impl FnMut(usize) -> usize) -> Vec<(usize, Item)> {
         let mut tosses = Vec::new();
@@ -57,7 +77,7 @@ impl Monkey {
                 Operation::Multiply => item * self.operand.unwrap_or(item),
             };
             let item = func(item);
-            if item % self.divisor == 0 {
+            if item.is_multiple_of(self.divisor) {
                 tosses.push((self.toss_true, Item(item)));
             } else {
                 tosses.push((self.toss_false, Item(item)));
@@ -67,17 +87,8 @@ impl Monkey {
     }
 }
 
-struct MonkeyState(Vec<Monkey>);
-
-impl Display for MonkeyState {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (idx, monkey) in self.0.iter().enumerate() {
-            let s = monkey.items.iter().map(|Item(item)| item).join(", ");
-            writeln!(f, "Monkey {idx}: {s}")?;
-        }
-        Ok(())
-    }
-}
+#[derive(macros::FromSections, macros::TryFromStr, macros::Display)]
+struct MonkeyState(#[section(each)] Vec<Monkey>);
 
 impl MonkeyState {
     fn tick(&mut self, mut func: impl FnMut(usize) -> usize) {
@@ -100,12 +111,16 @@ impl MonkeyState {
     }
 }
 
-::aoc::derive_FromStr_for_nom!(MonkeyState, parser::monkey_state);
-
-impl TryFrom<&str> for MonkeyState {
-    type Error = <MonkeyState as ::std::str::FromStr>::Err;
+#[cfg(test)]
+mod tests {
+    macros::test_roundtrip!(
+        super::MonkeyState,
+        include_str!("inputs/example"),
+        include_str!("inputs/live")
+    );
+}
 
-    fn try_from(s: &str) -> Result<Self, Self::Error> {
-        s.parse()
-    }
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::MonkeyState; [example, live]);
 }
@@ -1,39 +1,33 @@
 use ::aoc::nom::eol;
+use ::aoc::nom::tag;
 use ::aoc::nom::usize;
+use ::aoc::nom::IResult;
 use nom::branch::alt;
-use nom::bytes::complete::tag;
 use nom::character::complete::line_ending;
+use nom::combinator::all_consuming;
 use nom::combinator::map;
+use nom::error::context;
 use nom::multi::separated_list1;
 use nom::sequence::delimited;
 use nom::sequence::preceded;
 use nom::sequence::terminated;
 use nom::sequence::tuple;
-use nom::IResult;
 
 use super::Item;
 use super::Monkey;
-use super::MonkeyState;
 use super::Operation;
 
-pub(super) fn monkey_state(mut s: &str) -> IResult<&str, MonkeyState> {
-    let mut v = Vec::new();
-    while !s.is_empty() {
-        let (rest, o) = monkey(s)?;
-        s = rest;
-        v.push(o);
-    }
-    Ok((s, MonkeyState(v)))
+pub(super) fn monkey(s: &str) -> IResult<&str, Monkey> {
+    context("monkey", all_consuming(monkey_fields))(s)
 }
 
-pub(super) fn monkey(s: &str) -> IResult<&str, Monkey> {
+fn monkey_fields(s: &str) -> IResult<&str, Monkey> {
     let (s, id) = monkey_id(s)?;
     let (s, items) = starting_items(s)?;
     let (s, (operation, operand)) = operation(s)?;
     let (s, divisor) = divisor(s)?;
     let (s, toss_true) = toss_true(s)?;
     let (s, toss_false) = toss_false(s)?;
-    let (s, _) = eol(s)?;
     Ok((s, Monkey {
         id,
         items,
@@ -47,14 +41,20 @@ pub(super) fn monkey(s: &str) -> IResult<&str, Monkey> {
 }
 
 fn monkey_id(s: &str) -> IResult<&str, usize> {
-    terminated(delimited(tag("Monkey "), usize, tag(":")), line_ending)(s)
+    context(
+        "monkey id",
+        terminated(delimited(tag("Monkey "), usize, tag(":")), line_ending),
+    )(s)
 }
 
 fn starting_items(s: &str) -> IResult<&str, Vec<Item>> {
-    delimited(
-        tag("  Starting items: "),
-        separated_list1(tag(", "), map(usize, Item)),
-        line_ending,
+    context(
+        "starting items",
+        delimited(
+            tag("  Starting items: "),
+            separated_list1(tag(", "), map(usize, Item)),
+            line_ending,
+        ),
     )(s)
 }
 
@@ -70,47 +70,79 @@ fn operation(s: &str) -> IResult<&str, (Operation, Option<usize>)> {
         alt((map(tag("old"), |_| None), map(usize, Some)))(s)
     }
 
-    tuple((
-        preceded(tag("  Operation: new = old "), op),
-        delimited(tag(" "), operand, line_ending),
-    ))(s)
+    context(
+        "operation",
+        tuple((
+            preceded(tag("  Operation: new = old "), op),
+            delimited(tag(" "), operand, line_ending),
+        )),
+    )(s)
 }
 
 fn divisor(s: &str) -> IResult<&str, usize> {
-    delimited(tag("  Test: divisible by "), usize, line_ending)(s)
+    context(
+        "test",
+        delimited(tag("  Test: divisible by "), usize, line_ending),
+    )(s)
 }
 
 fn toss_true(s: &str) -> IResult<&str, usize> {
-    delimited(tag("    If true: throw to monkey "), usize, line_ending)(s)
+    context(
+        "true branch",
+        delimited(tag("    If true: throw to monkey "), usize, line_ending),
+    )(s)
 }
 
 fn toss_false(s: &str) -> IResult<&str, usize> {
-    delimited(tag("    If false: throw to monkey "), usize, line_ending)(s)
+    context(
+        "false branch",
+        delimited(tag("    If false: throw to monkey "), usize, eol),
+    )(s)
 }
 
 #[cfg(test)]
 mod tests {
-    use nom::Parser;
+    #[test]
+    fn test_parses() {
+        let example = include_str!("./inputs/example");
+        let live = include_str!("./inputs/live");
 
-    fn consume<'a, P, O, E>(mut s: &'a str, mut parser: P) -> Result<Vec<O>, nom::Err<E>>
-    where
-        P: Parser<&'a str, O, E>,
-    {
-        let mut v: Vec<O> = Vec::new();
-        while !s.is_empty() {
-            let (rest, o) = parser.parse(s)?;
-            s = rest;
-            v.push(o);
+        for section in example.split("\n\n").chain(live.split("\n\n")) {
+            let _ = super::monkey(section).unwrap();
         }
-        Ok(v)
     }
 
     #[test]
-    fn test_parses() {
-        let example = include_str!("./inputs/example");
-        let live = include_str!("./inputs/live");
+    fn test_located_error() {
+        let example = include_str!("./inputs/example").replacen("79, 98", "79; 98", 1);
+        let Err(err) = example
+            .split("\n\n")
+            .next()
+            .unwrap()
+            .parse::<super::Monkey>()
+        else {
+            panic!("malformed input parsed");
+        };
+        assert_eq!((err.line(), err.column()), (2, 21));
+        assert_eq!(err.expected(), "a line ending");
+        assert_eq!(err.context(), ["starting items", "monkey"]);
+        assert_eq!(
+            err.to_string(),
+            "\
+error: expected a line ending
+ --> 2:21
+  |
+2 |   Starting items: 79; 98
+  |                     ^
+  = while parsing starting items
+  = while parsing monkey"
+        );
 
-        let _ = consume(example, super::monkey).unwrap();
-        let _ = consume(live, super::monkey).unwrap();
+        let Err(err) = example.parse::<super::super::MonkeyState>() else {
+            panic!("malformed input parsed");
+        };
+        assert!(err
+            .to_string()
+            .starts_with("in `0` at line 2: error: expected a line ending"));
     }
 }
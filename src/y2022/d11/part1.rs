@@ -62,10 +62,11 @@
 //!
 //! Starting items lists your worry level for each item the monkey is currently
 //! holding in the order they will be inspected. Operation shows how your worry
-//! level changes as that monkey inspects an item. (An operation like new = old
-//! * 5 means that your worry level after the monkey inspected the item is five
-//! times whatever your worry level was before inspection.) Test shows how the
-//! monkey uses your worry level to decide where to throw an item next.
+//! level changes as that monkey inspects an item. (An operation like
+//! new = old * 5 means that your worry level after the monkey inspected the
+//! item is five times whatever your worry level was before inspection.) Test
+//! shows how the monkey uses your worry level to decide where to throw an
+//! item next.
 //! If true shows what happens with an item if the Test was true.
 //! If false shows what happens with an item if the Test was false.
 //! After each monkey inspects an item but before it tests your worry level,
@@ -257,6 +258,7 @@
 //! over 20 rounds. What is the level of monkey business after 20 rounds of
 //! stuff-slinging simian shenanigans?
 
+#[macros::solution(year = 2022, day = 11, part = 1)]
 fn solve(mut state: super::MonkeyState) -> usize {
     for _ in 0..20 {
         state.tick(|v| v / 3);
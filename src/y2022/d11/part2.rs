@@ -113,6 +113,7 @@
 //! Starting again from the initial state in your puzzle input, what is the
 //! level of monkey business after 10000 rounds?
 
+#[macros::solution(year = 2022, day = 11, part = 2)]
 fn solve(mut state: super::MonkeyState) -> usize {
     let modulo: usize = state.0.iter().map(|m| m.divisor).product();
     for _ in 0..10000 {
@@ -0,0 +1,82 @@
+// Copyright (c) 2021-2022 Brandon LeBlanc <brandon@leblanc.codes>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in
+// all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use aoc::gen::Rng;
+use aoc::matrix::Matrix;
+use aoc::matrix::Position;
+
+/// A heightmap rising from `a` on the left to `z` on the right, 41 rows by
+/// 171 columns at scale 1.
+///
+/// Cells are lowered at random, except along a winding path from `S` to `E`
+/// that climbs at most one step at a time, so there is always a route.
+#[macros::generator(year = 2022, day = 12)]
+pub fn generate(rng: &mut Rng, scale: usize) -> String {
+    let rows = aoc::gen::side(41, scale);
+    let cols = aoc::gen::side(171, scale);
+    let base = |col: usize| (col * 26 / cols) as u8;
+
+    let mut path = Matrix::new(cols, vec![false; rows * cols]);
+    let start = rng.below(rows);
+    let mut row = start;
+    for col in 0..cols {
+        path[Position::new(row, col)] = true;
+        if col + 1 < cols && rng.chance(30) {
+            let next = rng.below(rows);
+            while row != next {
+                row = if next < row { row - 1 } else { row + 1 };
+                path[Position::new(row, col)] = true;
+            }
+        }
+    }
+
+    let mut out = String::with_capacity(rows * (cols + 1));
+    for (pos, &on_path) in path.iter() {
+        let c = match (pos.x, pos.y) {
+            (r, 0) if r == start => 'S',
+            (r, c) if r == row && c == cols - 1 => 'E',
+            (_, c) if on_path => (b'a' + base(c)) as char,
+            (_, c) => (b'a' + base(c).saturating_sub(rng.below(3) as u8)) as char,
+        };
+        out.push(c);
+        if pos.y == cols - 1 {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::gen::Rng;
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for seed in 0..4 {
+            let input = super::generate(&mut Rng::new(seed), 1);
+            let part1 = aoc::parse_and_solve(input.as_str(), super::super::part1::solve).unwrap();
+            let part2 = aoc::parse_and_solve(input.as_str(), super::super::part2::solve).unwrap();
+            assert!(
+                part2 <= part1 && part1 < usize::MAX,
+                "seed {seed}: no path\n{input}"
+            );
+        }
+    }
+}
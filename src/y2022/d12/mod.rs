@@ -18,12 +18,13 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+pub(crate) mod gen;
 pub(crate) mod part1;
 pub(crate) mod part2;
 
 use std::collections::BinaryHeap;
-use std::convert::Infallible;
 
+use aoc::matrix::GridError;
 use aoc::matrix::Matrix;
 use aoc::matrix::Position;
 use aoc::matrix::RelativePosition;
@@ -36,6 +37,21 @@ struct Input {
     dest: Position,
 }
 
+/// Why a heightmap could not be read.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display, derive_more::From)]
+enum ParseError {
+    #[display(fmt = "{_0}")]
+    Grid(GridError),
+    #[display(fmt = "the heightmap has no start marked `S`")]
+    NoStart,
+    #[display(fmt = "the heightmap has no destination marked `E`")]
+    NoDest,
+    #[display(fmt = "the heightmap marks `{_0}` more than once")]
+    Repeated(char),
+}
+
+impl std::error::Error for ParseError {}
+
 const REL: [RelativePosition; 4] = [
     RelativePosition::TopCenter,
     RelativePosition::MiddleLeft,
@@ -91,36 +107,24 @@ impl Input {
 }
 
 impl TryFrom<&str> for Input {
-    type Error = Infallible;
+    type Error = ParseError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut start = None;
-        let mut dest = None;
-        let mut cells = Vec::new();
-        for (row, line) in value.lines().enumerate() {
-            let mut row_cells = Vec::with_capacity(line.len());
-            for (col, cell) in line.bytes().enumerate() {
-                match cell {
-                    b'S' => {
-                        start = Some(Position::new(row, col));
-                        row_cells.push(0_u8);
-                    }
-                    b'E' => {
-                        dest = Some(Position::new(row, col));
-                        row_cells.push(b'z' - b'a');
-                    }
-                    cell @ b'a'..=b'z' => {
-                        row_cells.push(cell - b'a');
-                    }
-                    _ => unreachable!(),
-                }
-            }
-            cells.push(row_cells);
-        }
+        let (matrix, markers) = Matrix::try_from_marked_grid(value, &['S', 'E'], |c| match c {
+            'S' => Some(0),
+            'E' => Some(b'z' - b'a'),
+            'a'..='z' => Some(c as u8 - b'a'),
+            _ => None,
+        })?;
+        let only = |marker, missing| match markers.all(marker) {
+            [] => Err(missing),
+            [pos] => Ok(*pos),
+            _ => Err(ParseError::Repeated(marker)),
+        };
         Ok(Self {
-            matrix: Matrix::from_iter(cells),
-            start: start.unwrap(),
-            dest: dest.unwrap(),
+            start: only('S', ParseError::NoStart)?,
+            dest: only('E', ParseError::NoDest)?,
+            matrix,
         })
     }
 }
@@ -131,6 +135,8 @@ struct Step<'a> {
     pos: Position,
 }
 
+// Steps from different inputs are deliberately incomparable.
+#[allow(clippy::non_canonical_partial_ord_impl)]
 impl<'a> PartialOrd for Step<'a> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         if std::ptr::eq(self.input, other.input) {
@@ -148,3 +154,30 @@ impl<'a> Ord for Step<'a> {
         Ord::cmp(&i1, &i2)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Input;
+    use super::ParseError;
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Input::try_from("abc\nbcE").unwrap_err(),
+            ParseError::NoStart
+        );
+        assert_eq!(
+            Input::try_from("Sbc\nbSE").unwrap_err(),
+            ParseError::Repeated('S')
+        );
+        assert_eq!(
+            Input::try_from("Sbc\nb?E").unwrap_err().to_string(),
+            "unexpected '?' at row 2, column 2"
+        );
+    }
+}
+
+#[cfg(all(test, feature = "fuzz"))]
+mod fuzz {
+    ::aoc::fuzz_parser!(super::Input; [example, live]);
+}
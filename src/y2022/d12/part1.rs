@@ -44,20 +44,26 @@
 //!
 //! For example:
 //!
+//! ```text
 //! Sabqponm
 //! abcryxxl
 //! accszExk
 //! acctuvwj
 //! abdefghi
+//! ```
+//!
 //! Here, you start in the top-left corner; your goal is near the middle. You
 //! could start by moving down or right, but eventually you'll need to head
 //! toward the e at the bottom. From there, you can spiral around to the goal:
 //!
+//! ```text
 //! v..v<<<<
 //! >v.vv<<^
 //! .>vv>E^^
 //! ..v>>>^^
 //! ..>>>>>^
+//! ```
+//!
 //! In the above diagram, the symbols indicate whether the path exits each
 //! square moving up (^), down (v), left (<), or right (>). The location that
 //! should get the best signal is still E, and . marks unvisited squares.
@@ -67,7 +73,8 @@
 //! What is the fewest steps required to move from your current position to the
 //! location that should get the best signal?
 
-fn solve(input: super::Input) -> usize {
+#[macros::solution(year = 2022, day = 12, part = 1, invalid(no_start))]
+pub(super) fn solve(input: super::Input) -> usize {
     input.shortest_path([input.start])
 }
 
@@ -76,5 +83,6 @@ mod tests {
     ::aoc::tests_for_problem!(super::solve, {
         example => 31,
         live => 517,
+        no_start => Err(super::super::ParseError::NoStart),
     });
 }
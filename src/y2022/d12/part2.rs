@@ -31,26 +31,33 @@
 //!
 //! Again consider the example from above:
 //!
+//! ```text
 //! Sabqponm
 //! abcryxxl
 //! accszExk
 //! acctuvwj
 //! abdefghi
+//! ```
+//!
 //! Now, there are six choices for starting position (five marked a, plus the
 //! square marked S that counts as being at elevation a). If you start at the
 //! bottom-left square, you can reach the goal most quickly:
 //!
+//! ```text
 //! ...v<<<<
 //! ...vv<<^
 //! ...v>E^^
 //! .>v>>>^^
 //! >^>>>>>^
+//! ```
+//!
 //! This path reaches the goal in only 29 steps, the fewest possible.
 //!
 //! What is the fewest steps required to move starting from any square with
 //! elevation a to the location that should get the best signal?
 
-fn solve(input: super::Input) -> usize {
+#[macros::solution(year = 2022, day = 12, part = 2, invalid(no_start))]
+pub(super) fn solve(input: super::Input) -> usize {
     let starts = input
         .matrix
         .iter()